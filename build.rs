@@ -4,8 +4,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
 
     tonic_build::compile_protos("proto/user.proto")?;
-    tonic_build::compile_protos("proto/post.proto")?;
-    tonic_build::compile_protos("proto/message.proto")?;
+    // 添付ファイルは attachment.proto の定義を共有する
+    tonic_build::configure()
+        .extern_path(".attachment", "crate::attachment_proto")
+        .compile_protos(&["proto/post.proto"], &["proto"])?;
+    // 新着メッセージは他のフレームより大きいため、フレームの oneof ではヒープに置く
+    tonic_build::configure()
+        .extern_path(".attachment", "crate::attachment_proto")
+        .boxed(".message.ChatServerFrame.frame.message")
+        .compile_protos(&["proto/message.proto"], &["proto"])?;
+    tonic_build::compile_protos("proto/conversation.proto")?;
+    tonic_build::compile_protos("proto/auth.proto")?;
+    tonic_build::compile_protos("proto/admin.proto")?;
+    tonic_build::compile_protos("proto/follow.proto")?;
+    tonic_build::compile_protos("proto/comment.proto")?;
+    tonic_build::compile_protos("proto/mention.proto")?;
+    tonic_build::compile_protos("proto/attachment.proto")?;
 
     Ok(())
 }
@@ -3,6 +3,26 @@ pub use sea_orm_migration::prelude::*;
 mod m20250213_100210_create_table_users;
 mod m20250213_105425_create_table_posts;
 mod m20250304_075607_create_table_messages;
+mod m20261017_090000_create_table_conversations;
+mod m20261017_100000_add_password_hash_to_users;
+mod m20261017_110000_create_table_refresh_tokens;
+mod m20261017_120000_add_role_to_users;
+mod m20261017_130000_add_post_user_id_id_index;
+mod m20261017_140000_create_table_post_revisions;
+mod m20261017_150000_create_table_follows;
+mod m20261017_160000_create_table_timeline_entries;
+mod m20261017_170000_create_table_post_likes;
+mod m20261017_180000_create_table_comments;
+mod m20261017_190000_add_repost_columns_to_post;
+mod m20261017_200000_create_table_hashtags;
+mod m20261017_205000_add_handle_to_users;
+mod m20261017_210000_create_table_mentions;
+mod m20261017_220000_add_unique_email_index_to_users;
+mod m20261017_230000_create_table_message_reactions;
+mod m20261017_233000_add_reply_to_message_id_to_messages;
+mod m20261017_234000_create_table_message_revisions;
+mod m20261017_235000_create_table_message_receipts;
+mod m20261018_000000_create_table_attachments;
 
 pub struct Migrator;
 
@@ -13,6 +33,26 @@ impl MigratorTrait for Migrator {
             Box::new(m20250213_100210_create_table_users::Migration),
             Box::new(m20250213_105425_create_table_posts::Migration),
             Box::new(m20250304_075607_create_table_messages::Migration),
+            Box::new(m20261017_090000_create_table_conversations::Migration),
+            Box::new(m20261017_100000_add_password_hash_to_users::Migration),
+            Box::new(m20261017_110000_create_table_refresh_tokens::Migration),
+            Box::new(m20261017_120000_add_role_to_users::Migration),
+            Box::new(m20261017_130000_add_post_user_id_id_index::Migration),
+            Box::new(m20261017_140000_create_table_post_revisions::Migration),
+            Box::new(m20261017_150000_create_table_follows::Migration),
+            Box::new(m20261017_160000_create_table_timeline_entries::Migration),
+            Box::new(m20261017_170000_create_table_post_likes::Migration),
+            Box::new(m20261017_180000_create_table_comments::Migration),
+            Box::new(m20261017_190000_add_repost_columns_to_post::Migration),
+            Box::new(m20261017_200000_create_table_hashtags::Migration),
+            Box::new(m20261017_205000_add_handle_to_users::Migration),
+            Box::new(m20261017_210000_create_table_mentions::Migration),
+            Box::new(m20261017_220000_add_unique_email_index_to_users::Migration),
+            Box::new(m20261017_230000_create_table_message_reactions::Migration),
+            Box::new(m20261017_233000_add_reply_to_message_id_to_messages::Migration),
+            Box::new(m20261017_234000_create_table_message_revisions::Migration),
+            Box::new(m20261017_235000_create_table_message_receipts::Migration),
+            Box::new(m20261018_000000_create_table_attachments::Migration),
         ]
     }
 }
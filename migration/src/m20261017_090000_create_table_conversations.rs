@@ -0,0 +1,195 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Conversations::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Conversations::Id))
+                    .col(string(Conversations::Name).not_null())
+                    .col(integer_null(Conversations::CreatedBy))
+                    .col(
+                        ColumnDef::new(Conversations::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Conversations::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversations_created_by")
+                            .from(Conversations::Table, Conversations::CreatedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConversationMembers::Table)
+                    .if_not_exists()
+                    .col(integer(ConversationMembers::ConversationId).not_null())
+                    .col(integer(ConversationMembers::UserId).not_null())
+                    // メンバーごとの既読位置（未読件数の算出に使用）
+                    .col(integer_null(ConversationMembers::LastReadMessageId))
+                    .col(
+                        ColumnDef::new(ConversationMembers::JoinedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ConversationMembers::ConversationId)
+                            .col(ConversationMembers::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_members_conversation_id")
+                            .from(
+                                ConversationMembers::Table,
+                                ConversationMembers::ConversationId,
+                            )
+                            .to(Conversations::Table, Conversations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_members_user_id")
+                            .from(ConversationMembers::Table, ConversationMembers::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_conversation_members_user_id")
+                    .table(ConversationMembers::Table)
+                    .col(ConversationMembers::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // グループ宛てのメッセージは receiver_id を持たず conversation_id を持つ
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Messages::Table)
+                    .add_column(integer_null(Messages::ConversationId))
+                    .modify_column(ColumnDef::new(Messages::ReceiverId).integer().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_messages_conversation_id")
+                            .from_tbl(Messages::Table)
+                            .from_col(Messages::ConversationId)
+                            .to_tbl(Conversations::Table)
+                            .to_col(Conversations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE messages ADD CONSTRAINT chk_messages_recipient \
+                 CHECK ((receiver_id IS NULL) <> (conversation_id IS NULL))",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_messages_conversation_id")
+                    .table(Messages::Table)
+                    .col(Messages::ConversationId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE messages DROP CONSTRAINT IF EXISTS chk_messages_recipient",
+            )
+            .await?;
+
+        // グループ宛てのメッセージは受信者を持たないため削除してから NOT NULL に戻す
+        manager
+            .get_connection()
+            .execute_unprepared("DELETE FROM messages WHERE conversation_id IS NOT NULL")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Messages::Table)
+                    .drop_foreign_key(Alias::new("fk_messages_conversation_id"))
+                    .drop_column(Messages::ConversationId)
+                    .modify_column(ColumnDef::new(Messages::ReceiverId).integer().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ConversationMembers::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Conversations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    ReceiverId,
+    ConversationId,
+}
+
+#[derive(DeriveIden)]
+enum Conversations {
+    Table,
+    Id,
+    Name,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ConversationMembers {
+    Table,
+    ConversationId,
+    UserId,
+    LastReadMessageId,
+    JoinedAt,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(UserRole::Enum)
+                    .values([UserRole::User, UserRole::Moderator, UserRole::Admin])
+                    .to_owned(),
+            )
+            .await?;
+
+        // 既存ユーザーはすべて一般ユーザーとして扱う
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(
+                        ColumnDef::new(Users::Role)
+                            .custom(UserRole::Enum)
+                            .not_null()
+                            .default(Expr::cust("'user'")),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::Role)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(UserRole::Enum).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Role,
+}
+
+#[derive(DeriveIden)]
+enum UserRole {
+    #[sea_orm(iden = "user_role")]
+    Enum,
+    User,
+    Moderator,
+    Admin,
+}
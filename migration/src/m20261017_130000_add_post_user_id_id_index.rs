@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 投稿者で絞り込んだ一覧を ID の降順にキーセットページングするための複合インデックス
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_user_id_id")
+                    .table(Post::Table)
+                    .col(Post::UserId)
+                    .col((Post::Id, IndexOrder::Desc))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_post_user_id_id")
+                    .table(Post::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+    UserId,
+}
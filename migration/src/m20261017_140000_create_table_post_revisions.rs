@@ -0,0 +1,66 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostRevisions::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PostRevisions::Id))
+                    .col(integer(PostRevisions::PostId).not_null())
+                    // 編集前の本文を保持する
+                    .col(string(PostRevisions::Body).not_null())
+                    .col(
+                        ColumnDef::new(PostRevisions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_revisions_post_id")
+                            .from(PostRevisions::Table, PostRevisions::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_revisions_post_id")
+                    .table(PostRevisions::Table)
+                    .col(PostRevisions::PostId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostRevisions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PostRevisions {
+    Table,
+    Id,
+    PostId,
+    Body,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+}
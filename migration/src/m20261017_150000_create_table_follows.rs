@@ -0,0 +1,101 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Follows::Table)
+                    .if_not_exists()
+                    .col(integer(Follows::FollowerId).not_null())
+                    .col(integer(Follows::FolloweeId).not_null())
+                    .col(
+                        ColumnDef::new(Follows::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    // 同じ相手を二重にフォローできないよう (フォローする側, される側) を主キーにする
+                    .primary_key(
+                        Index::create()
+                            .col(Follows::FollowerId)
+                            .col(Follows::FolloweeId),
+                    )
+                    .check(Expr::col(Follows::FollowerId).ne(Expr::col(Follows::FolloweeId)))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follows_follower_id")
+                            .from(Follows::Table, Follows::FollowerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follows_followee_id")
+                            .from(Follows::Table, Follows::FolloweeId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // フォロワー一覧の取得用
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_follows_followee_id")
+                    .table(Follows::Table)
+                    .col(Follows::FolloweeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // フォロー数・フォロワー数はフォロー操作と同じトランザクションで更新する非正規化カラム
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(integer(Users::FollowersCount).not_null().default(0))
+                    .add_column(integer(Users::FollowingCount).not_null().default(0))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::FollowersCount)
+                    .drop_column(Users::FollowingCount)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Follows::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Follows {
+    Table,
+    FollowerId,
+    FolloweeId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+    FollowersCount,
+    FollowingCount,
+}
@@ -0,0 +1,106 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 書き込み時にフォロワーへ配信した投稿（fan-out-on-write）
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimelineEntries::Table)
+                    .if_not_exists()
+                    .col(integer(TimelineEntries::UserId).not_null())
+                    .col(integer(TimelineEntries::PostId).not_null())
+                    .col(integer(TimelineEntries::AuthorId).not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(TimelineEntries::UserId)
+                            .col(TimelineEntries::PostId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timeline_entries_user_id")
+                            .from(TimelineEntries::Table, TimelineEntries::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timeline_entries_post_id")
+                            .from(TimelineEntries::Table, TimelineEntries::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timeline_entries_author_id")
+                            .from(TimelineEntries::Table, TimelineEntries::AuthorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // フォロー解除時に相手の投稿をまとめて取り除くため
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_timeline_entries_user_id_author_id")
+                    .table(TimelineEntries::Table)
+                    .col(TimelineEntries::UserId)
+                    .col(TimelineEntries::AuthorId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // false の投稿は読み込み時にフォロー関係から集める（fan-out-on-read）
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(boolean(Post::FannedOut).not_null().default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::FannedOut)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TimelineEntries::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TimelineEntries {
+    Table,
+    UserId,
+    PostId,
+    AuthorId,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+    FannedOut,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,104 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostLikes::Table)
+                    .if_not_exists()
+                    .col(integer(PostLikes::PostId).not_null())
+                    .col(integer(PostLikes::UserId).not_null())
+                    .col(
+                        ColumnDef::new(PostLikes::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    // 同じユーザーが同じ投稿に二重にいいねできないよう (投稿, ユーザー) を主キーにする
+                    .primary_key(
+                        Index::create()
+                            .col(PostLikes::PostId)
+                            .col(PostLikes::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_likes_post_id")
+                            .from(PostLikes::Table, PostLikes::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_likes_user_id")
+                            .from(PostLikes::Table, PostLikes::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 閲覧者がいいね済みの投稿を引くため
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_likes_user_id_post_id")
+                    .table(PostLikes::Table)
+                    .col(PostLikes::UserId)
+                    .col(PostLikes::PostId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // いいね数は一覧のたびに COUNT(*) しないよう、いいね操作と同じトランザクションで更新する
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(integer(Post::LikeCount).not_null().default(0))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::LikeCount)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PostLikes::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PostLikes {
+    Table,
+    PostId,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+    LikeCount,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Comments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Comments::Id))
+                    .col(integer(Comments::PostId).not_null())
+                    .col(integer(Comments::UserId).not_null())
+                    // 返信先のコメント（投稿への直接のコメントは NULL）
+                    .col(integer_null(Comments::ParentCommentId))
+                    // スレッドの起点となるトップレベルのコメント（トップレベル自身は NULL）
+                    // スレッド単位で返信をまとめて取得するために保持する
+                    .col(integer_null(Comments::RootCommentId))
+                    .col(text(Comments::Body).not_null())
+                    .col(
+                        ColumnDef::new(Comments::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Comments::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(Comments::DeletedAt).timestamp().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_post_id")
+                            .from(Comments::Table, Comments::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_user_id")
+                            .from(Comments::Table, Comments::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_parent_comment_id")
+                            .from(Comments::Table, Comments::ParentCommentId)
+                            .to(Comments::Table, Comments::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_root_comment_id")
+                            .from(Comments::Table, Comments::RootCommentId)
+                            .to(Comments::Table, Comments::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 投稿ごとのトップレベルのコメントを ID 順にページングするため
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comments_post_id_root_comment_id_id")
+                    .table(Comments::Table)
+                    .col(Comments::PostId)
+                    .col(Comments::RootCommentId)
+                    .col(Comments::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comments_root_comment_id")
+                    .table(Comments::Table)
+                    .col(Comments::RootCommentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // コメント数は一覧のたびに COUNT(*) しないよう、コメント操作と同じトランザクションで更新する
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(integer(Post::CommentCount).not_null().default(0))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::CommentCount)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Comments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Comments {
+    Table,
+    Id,
+    PostId,
+    UserId,
+    ParentCommentId,
+    RootCommentId,
+    Body,
+    CreatedAt,
+    UpdatedAt,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+    CommentCount,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(RepostKind::Enum)
+                    .values([RepostKind::Repost, RepostKind::Quote])
+                    .to_owned(),
+            )
+            .await?;
+
+        // 通常の投稿はどちらも NULL。リポスト元が削除されると repost_of_id のみ NULL になる
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(ColumnDef::new(Post::RepostOfId).integer().null())
+                    .add_column(
+                        ColumnDef::new(Post::RepostKind)
+                            .custom(RepostKind::Enum)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_post_repost_of_id")
+                    .from(Post::Table, Post::RepostOfId)
+                    .to(Post::Table, Post::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_repost_of_id")
+                    .table(Post::Table)
+                    .col(Post::RepostOfId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 引用ではない単純なリポストは 1 ユーザーにつき 1 件まで
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_user_id_repost_of_id_plain")
+                    .table(Post::Table)
+                    .col(Post::UserId)
+                    .col(Post::RepostOfId)
+                    .unique()
+                    .and_where(Expr::cust("repost_kind = 'repost'"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::RepostKind)
+                    .drop_column(Post::RepostOfId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(RepostKind::Enum).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+    UserId,
+    RepostOfId,
+    RepostKind,
+}
+
+#[derive(DeriveIden)]
+enum RepostKind {
+    #[sea_orm(iden = "repost_kind")]
+    Enum,
+    Repost,
+    Quote,
+}
@@ -0,0 +1,119 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Hashtags::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Hashtags::Id))
+                    // 正規化済み（小文字・半角）のタグ名
+                    .col(string(Hashtags::Name).not_null().unique_key())
+                    .col(
+                        ColumnDef::new(Hashtags::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostHashtags::Table)
+                    .if_not_exists()
+                    .col(integer(PostHashtags::PostId).not_null())
+                    .col(integer(PostHashtags::HashtagId).not_null())
+                    // トレンドの集計期間の判定に使う（投稿の編集でタグが残った場合は更新しない）
+                    .col(
+                        ColumnDef::new(PostHashtags::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(PostHashtags::PostId)
+                            .col(PostHashtags::HashtagId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_hashtags_post_id")
+                            .from(PostHashtags::Table, PostHashtags::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_hashtags_hashtag_id")
+                            .from(PostHashtags::Table, PostHashtags::HashtagId)
+                            .to(Hashtags::Table, Hashtags::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // タグごとの投稿を新しい順に引くため
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_hashtags_hashtag_id_post_id")
+                    .table(PostHashtags::Table)
+                    .col(PostHashtags::HashtagId)
+                    .col(PostHashtags::PostId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 直近の期間のタグを集計するため
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_hashtags_created_at")
+                    .table(PostHashtags::Table)
+                    .col(PostHashtags::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostHashtags::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Hashtags::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Hashtags {
+    Table,
+    Id,
+    Name,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PostHashtags {
+    Table,
+    PostId,
+    HashtagId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+}
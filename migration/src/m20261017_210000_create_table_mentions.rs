@@ -0,0 +1,133 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Mentions::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Mentions::Id))
+                    // メンションされたユーザー
+                    .col(integer(Mentions::UserId).not_null())
+                    // メンションしたユーザー
+                    .col(integer(Mentions::AuthorId).not_null())
+                    // 投稿とメッセージのどちらか一方のみを設定する
+                    .col(integer_null(Mentions::PostId))
+                    .col(integer_null(Mentions::MessageId))
+                    .col(
+                        ColumnDef::new(Mentions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mentions_user_id")
+                            .from(Mentions::Table, Mentions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mentions_author_id")
+                            .from(Mentions::Table, Mentions::AuthorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mentions_post_id")
+                            .from(Mentions::Table, Mentions::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mentions_message_id")
+                            .from(Mentions::Table, Mentions::MessageId)
+                            .to(Messages::Table, Messages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("(post_id IS NULL) <> (message_id IS NULL)"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // メンションされたユーザーごとに新しい順に引くため
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_mentions_user_id_id")
+                    .table(Mentions::Table)
+                    .col(Mentions::UserId)
+                    .col(Mentions::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 同じ投稿・メッセージで同じユーザーを何度メンションしても 1 件にする
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_mentions_post_id_user_id")
+                    .table(Mentions::Table)
+                    .col(Mentions::PostId)
+                    .col(Mentions::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_mentions_message_id_user_id")
+                    .table(Mentions::Table)
+                    .col(Mentions::MessageId)
+                    .col(Mentions::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Mentions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Mentions {
+    Table,
+    Id,
+    UserId,
+    AuthorId,
+    PostId,
+    MessageId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    Id,
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+/// メールアドレスの大文字・小文字を区別しない一意インデックスを追加します。
+///
+/// 大文字・小文字だけが異なるメールアドレスのユーザーが既に存在する場合は、
+/// どちらのアカウントを残すかを機械的に決められないため、インデックスを作成せずに
+/// 重複しているメールアドレスとユーザー ID を含むエラーで失敗します。
+/// 該当するユーザーを統合・変更・削除してから再度マイグレーションを実行してください。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let duplicates = manager
+            .get_connection()
+            .query_all(Statement::from_string(
+                manager.get_database_backend(),
+                "SELECT LOWER(email) AS email, STRING_AGG(id::text, ', ' ORDER BY id) AS ids \
+                 FROM users GROUP BY LOWER(email) HAVING COUNT(*) > 1 ORDER BY 1",
+            ))
+            .await?;
+        if !duplicates.is_empty() {
+            let mut details = Vec::with_capacity(duplicates.len());
+            for row in &duplicates {
+                let email: String = row.try_get("", "email")?;
+                let ids: String = row.try_get("", "ids")?;
+                details.push(format!("{} (user ids: {})", email, ids));
+            }
+            return Err(DbErr::Migration(format!(
+                "cannot create idx_users_lower_email: emails differing only by case must be \
+                 resolved first: {}",
+                details.join("; ")
+            )));
+        }
+
+        // 大文字・小文字だけが異なるメールアドレスでも同じアカウントとみなす
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_users_lower_email")
+                    .table(Users::Table)
+                    .col(Func::lower(Expr::col(Users::Email)))
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_lower_email")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Email,
+}
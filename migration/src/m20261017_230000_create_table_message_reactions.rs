@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageReactions::Table)
+                    .if_not_exists()
+                    .col(integer(MessageReactions::MessageId).not_null())
+                    .col(integer(MessageReactions::UserId).not_null())
+                    .col(string_len(MessageReactions::Emoji, 64).not_null())
+                    .col(
+                        ColumnDef::new(MessageReactions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    // 同じユーザーは同じメッセージに同じ絵文字を 1 回だけ付けられる（異なる絵文字は複数可）
+                    .primary_key(
+                        Index::create()
+                            .col(MessageReactions::MessageId)
+                            .col(MessageReactions::UserId)
+                            .col(MessageReactions::Emoji),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_reactions_message_id")
+                            .from(MessageReactions::Table, MessageReactions::MessageId)
+                            .to(Messages::Table, Messages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_reactions_user_id")
+                            .from(MessageReactions::Table, MessageReactions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MessageReactions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MessageReactions {
+    Table,
+    MessageId,
+    UserId,
+    Emoji,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
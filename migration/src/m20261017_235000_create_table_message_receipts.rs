@@ -0,0 +1,136 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 受信者ごとの配達・既読の記録。行がない、または read_at が NULL のメッセージは未読として扱う
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageReceipts::Table)
+                    .if_not_exists()
+                    .col(integer(MessageReceipts::MessageId).not_null())
+                    .col(integer(MessageReceipts::UserId).not_null())
+                    .col(ColumnDef::new(MessageReceipts::DeliveredAt).timestamp().null())
+                    .col(ColumnDef::new(MessageReceipts::ReadAt).timestamp().null())
+                    .primary_key(
+                        Index::create()
+                            .col(MessageReceipts::MessageId)
+                            .col(MessageReceipts::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_receipts_message_id")
+                            .from(MessageReceipts::Table, MessageReceipts::MessageId)
+                            .to(Messages::Table, Messages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_receipts_user_id")
+                            .from(MessageReceipts::Table, MessageReceipts::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_message_receipts_user_id")
+                    .table(MessageReceipts::Table)
+                    .col(MessageReceipts::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 既読になっている1対1のメッセージは、既読化で更新された updated_at を既読日時として移行する。
+        // グループの既読はメンバーの既読位置で管理されているため移行しない
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO message_receipts (message_id, user_id, delivered_at, read_at) \
+                 SELECT id, receiver_id, updated_at, updated_at FROM messages \
+                 WHERE receiver_id IS NOT NULL AND is_read = true",
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_is_read")
+                    .table(Messages::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Messages::Table)
+                    .drop_column(Messages::IsRead)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Messages::Table)
+                    .add_column(boolean(Messages::IsRead).default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE messages m SET is_read = true FROM message_receipts r \
+                 WHERE r.message_id = m.id AND r.user_id = m.receiver_id AND r.read_at IS NOT NULL",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_is_read")
+                    .table(Messages::Table)
+                    .col(Messages::IsRead)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(MessageReceipts::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MessageReceipts {
+    Table,
+    MessageId,
+    UserId,
+    DeliveredAt,
+    ReadAt,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    Id,
+    IsRead,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,117 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Attachments::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Attachments::Id))
+                    .col(integer(Attachments::UploaderId).not_null())
+                    // BlobStore 上のキー（本体は内容の SHA-256 ごとに 1 つだけ保存する）
+                    .col(string(Attachments::StorageKey).not_null())
+                    .col(string(Attachments::Filename).not_null())
+                    .col(string(Attachments::MimeType).not_null())
+                    .col(big_integer(Attachments::SizeBytes).not_null())
+                    // 内容の SHA-256（16 進数）
+                    .col(string_len(Attachments::Checksum, 64).not_null())
+                    // アップロード直後はどちらも NULL で、送信・投稿時にどちらか一方を設定する
+                    .col(integer_null(Attachments::MessageId))
+                    .col(integer_null(Attachments::PostId))
+                    .col(
+                        ColumnDef::new(Attachments::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_attachments_uploader_id")
+                            .from(Attachments::Table, Attachments::UploaderId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_attachments_message_id")
+                            .from(Attachments::Table, Attachments::MessageId)
+                            .to(Messages::Table, Messages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_attachments_post_id")
+                            .from(Attachments::Table, Attachments::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::cust("message_id IS NULL OR post_id IS NULL"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_attachments_message_id")
+                    .table(Attachments::Table)
+                    .col(Attachments::MessageId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_attachments_post_id")
+                    .table(Attachments::Table)
+                    .col(Attachments::PostId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Attachments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Attachments {
+    Table,
+    Id,
+    UploaderId,
+    StorageKey,
+    Filename,
+    MimeType,
+    SizeBytes,
+    Checksum,
+    MessageId,
+    PostId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+}
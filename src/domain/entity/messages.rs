@@ -8,16 +8,38 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub sender_id: i32,
-    pub receiver_id: i32,
+    pub receiver_id: Option<i32>,
     pub content: String,
-    pub is_read: bool,
     pub created_at: DateTime,
     pub updated_at: DateTime,
     pub deleted_at: Option<DateTime>,
+    pub conversation_id: Option<i32>,
+    pub reply_to_message_id: Option<i32>,
+    pub edited_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::attachments::Entity")]
+    Attachments,
+    #[sea_orm(
+        belongs_to = "super::conversations::Entity",
+        from = "Column::ConversationId",
+        to = "super::conversations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Conversations,
+    #[sea_orm(has_many = "super::message_revisions::Entity")]
+    MessageRevisions,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ReplyToMessageId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SelfRef,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::ReceiverId",
@@ -36,4 +58,22 @@ pub enum Relation {
     Users1,
 }
 
+impl Related<super::attachments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Attachments.def()
+    }
+}
+
+impl Related<super::conversations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Conversations.def()
+    }
+}
+
+impl Related<super::message_revisions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MessageRevisions.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,6 +2,22 @@
 
 pub mod prelude;
 
+pub mod attachments;
+pub mod comments;
+pub mod conversation_members;
+pub mod conversations;
+pub mod follows;
+pub mod hashtags;
+pub mod mentions;
+pub mod message_reactions;
+pub mod message_receipts;
+pub mod message_revisions;
 pub mod messages;
 pub mod post;
+pub mod post_hashtags;
+pub mod post_likes;
+pub mod post_revisions;
+pub mod refresh_tokens;
+pub mod sea_orm_active_enums;
+pub mod timeline_entries;
 pub mod users;
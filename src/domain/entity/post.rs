@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
+use super::sea_orm_active_enums::RepostKind;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -10,10 +11,35 @@ pub struct Model {
     pub body: String,
     pub user_id: i32,
     pub created_at: String,
+    pub fanned_out: bool,
+    pub like_count: i32,
+    pub comment_count: i32,
+    pub repost_of_id: Option<i32>,
+    pub repost_kind: Option<RepostKind>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::attachments::Entity")]
+    Attachments,
+    #[sea_orm(has_many = "super::comments::Entity")]
+    Comments,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::RepostOfId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SelfRef,
+    #[sea_orm(has_many = "super::post_hashtags::Entity")]
+    PostHashtags,
+    #[sea_orm(has_many = "super::post_likes::Entity")]
+    PostLikes,
+    #[sea_orm(has_many = "super::post_revisions::Entity")]
+    PostRevisions,
+    #[sea_orm(has_many = "super::timeline_entries::Entity")]
+    TimelineEntries,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::UserId",
@@ -24,6 +50,42 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::attachments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Attachments.def()
+    }
+}
+
+impl Related<super::comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comments.def()
+    }
+}
+
+impl Related<super::post_hashtags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostHashtags.def()
+    }
+}
+
+impl Related<super::post_likes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostLikes.def()
+    }
+}
+
+impl Related<super::post_revisions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostRevisions.def()
+    }
+}
+
+impl Related<super::timeline_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimelineEntries.def()
+    }
+}
+
 impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Users.def()
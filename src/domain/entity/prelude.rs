@@ -1,5 +1,20 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
+pub use super::attachments::Entity as Attachments;
+pub use super::comments::Entity as Comments;
+pub use super::conversation_members::Entity as ConversationMembers;
+pub use super::conversations::Entity as Conversations;
+pub use super::follows::Entity as Follows;
+pub use super::hashtags::Entity as Hashtags;
+pub use super::mentions::Entity as Mentions;
+pub use super::message_reactions::Entity as MessageReactions;
+pub use super::message_receipts::Entity as MessageReceipts;
+pub use super::message_revisions::Entity as MessageRevisions;
 pub use super::messages::Entity as Messages;
 pub use super::post::Entity as Post;
+pub use super::post_hashtags::Entity as PostHashtags;
+pub use super::post_likes::Entity as PostLikes;
+pub use super::post_revisions::Entity as PostRevisions;
+pub use super::refresh_tokens::Entity as RefreshTokens;
+pub use super::timeline_entries::Entity as TimelineEntries;
 pub use super::users::Entity as Users;
@@ -0,0 +1,33 @@
+use crate::domain::entity::attachments::Model as Attachment;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+/// 添付ファイルの紐付け先の種類と ID
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttachmentTarget {
+    Message(i32),
+    Post(i32),
+}
+
+/// アップロードを終えた添付ファイルのメタデータ
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewAttachment {
+    pub uploader_id: i32,
+    pub storage_key: String,
+    pub filename: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    /// 内容の SHA-256（16 進数）
+    pub checksum: String,
+}
+
+#[async_trait]
+pub trait AttachmentRepository {
+    /// アップロードされた添付ファイルを、どこにも紐付いていない状態で記録します。
+    async fn insert(&self, attachment: NewAttachment) -> Result<Attachment, DbErr>;
+
+    /// `user_id` のユーザーが閲覧できる添付ファイルを取得します。
+    /// アップロードした本人、投稿の添付（誰でも閲覧可）、論理削除されていないメッセージの
+    /// 送受信者・グループのメンバーが閲覧できます。閲覧できない場合は `None` を返します。
+    async fn find_visible(&self, user_id: i32, id: i32) -> Result<Option<Attachment>, DbErr>;
+}
@@ -0,0 +1,66 @@
+use crate::domain::entity::comments::Model as Comment;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+use std::collections::HashMap;
+
+/// 新しく作成するコメント
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewComment {
+    pub post_id: i32,
+    pub user_id: i32,
+    /// 返信先のコメント（投稿への直接のコメントは `None`）
+    pub parent_comment_id: Option<i32>,
+    /// スレッドの起点となるトップレベルのコメント（トップレベル自身は `None`）
+    pub root_comment_id: Option<i32>,
+    pub body: String,
+}
+
+#[async_trait]
+pub trait CommentRepository {
+    /// 投稿が存在するかどうかを返します。
+    async fn post_exists(&self, post_id: i32) -> Result<bool, DbErr>;
+
+    /// 論理削除されたコメントも含めて取得します。
+    async fn get_by_id(&self, id: i32) -> Result<Option<Comment>, DbErr>;
+
+    /// コメントを作成し、投稿のコメント数を加算します。
+    async fn insert(&self, comment: NewComment) -> Result<Comment, DbErr>;
+
+    /// 投稿のトップレベルのコメントを古い順に取得します。
+    /// `after_id` を指定した場合はその ID より新しいコメントのみを返します。
+    async fn find_threads(
+        &self,
+        post_id: i32,
+        after_id: Option<i32>,
+        limit: u64,
+    ) -> Result<Vec<Comment>, DbErr>;
+
+    /// `root_ids` の各スレッドに属する返信を、スレッドごとに古い順に最大 `limit_per_thread` 件ずつ取得します。
+    async fn find_replies(
+        &self,
+        root_ids: &[i32],
+        limit_per_thread: u64,
+    ) -> Result<Vec<Comment>, DbErr>;
+
+    /// `root_ids` の各スレッドの返信数（論理削除されたものを含む）を返します。
+    /// 返信のないスレッドは含みません。
+    async fn count_replies(&self, root_ids: &[i32]) -> Result<HashMap<i32, i64>, DbErr>;
+
+    /// 1 つのスレッドに属する返信を古い順に取得します。
+    /// `after_id` を指定した場合はその ID より新しい返信のみを返します。
+    async fn find_thread_replies(
+        &self,
+        root_id: i32,
+        after_id: Option<i32>,
+        limit: u64,
+    ) -> Result<Vec<Comment>, DbErr>;
+
+    /// コメントの本文を更新します。
+    async fn update_body(&self, id: i32, body: String) -> Result<Comment, DbErr>;
+
+    /// コメントを論理削除し、投稿のコメント数を減算します。
+    /// 返信のスレッド構造を保つため行自体は残します。
+    ///
+    /// 削除した場合は true、既に削除済みの場合は false を返します。
+    async fn soft_delete(&self, id: i32) -> Result<bool, DbErr>;
+}
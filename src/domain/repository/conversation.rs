@@ -0,0 +1,78 @@
+use crate::domain::entity::conversations;
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, FromQueryResult};
+
+/// 会話一覧（受信箱）の1行分の集計結果
+///
+/// 1対1の会話は `peer_id`、グループ会話は `conversation_id` と `name` が設定されます。
+#[derive(Clone, Debug, PartialEq, FromQueryResult)]
+pub struct ConversationSummary {
+    pub peer_id: Option<i32>,
+    pub conversation_id: Option<i32>,
+    pub name: Option<String>,
+    pub last_message_id: Option<i32>,
+    pub last_sender_id: Option<i32>,
+    pub last_message_content: Option<String>,
+    /// 最新メッセージの日時（メッセージのないグループは参加日時）
+    pub last_activity_at: NaiveDateTime,
+    pub unread_count: i64,
+}
+
+#[async_trait::async_trait]
+pub trait ConversationRepository {
+    /// グループ会話を作成します。
+    /// - `created_by`: 作成者のユーザーID（作成者は自動的にメンバーに含まれます）
+    /// - `name`: グループ名
+    /// - `member_ids`: 作成者以外の初期メンバーのユーザーID
+    async fn create_group(
+        &self,
+        created_by: i32,
+        name: String,
+        member_ids: Vec<i32>,
+    ) -> Result<conversations::Model, DbErr>;
+
+    /// グループ会話を取得します。
+    async fn get_by_id(&self, id: i32) -> Result<Option<conversations::Model>, DbErr>;
+
+    /// グループ名を変更します。
+    async fn rename(&self, id: i32, name: String) -> Result<conversations::Model, DbErr>;
+
+    /// メンバーを追加します。既にメンバーのユーザーは無視されます。
+    ///
+    /// 追加された件数を返します。
+    async fn add_members(&self, id: i32, user_ids: Vec<i32>) -> Result<i32, DbErr>;
+
+    /// メンバーを削除します。削除された場合は true を返します。
+    async fn remove_member(&self, id: i32, user_id: i32) -> Result<bool, DbErr>;
+
+    /// メンバーのユーザーID一覧を取得します。
+    async fn list_member_ids(&self, id: i32) -> Result<Vec<i32>, DbErr>;
+
+    /// 指定ユーザーがメンバーかどうかを判定します。
+    async fn is_member(&self, id: i32, user_id: i32) -> Result<bool, DbErr>;
+
+    /// 2人のユーザーが共にメンバーになっているグループ会話があるかを判定します。
+    async fn shares_conversation(&self, user_id: i32, peer_id: i32) -> Result<bool, DbErr>;
+
+    /// メンバーの既読位置を更新します。
+    /// - `message_id` が `None` の場合は会話内の最新メッセージまで既読にします。
+    ///
+    /// 更新後の既読位置を返します。
+    async fn mark_as_read(
+        &self,
+        id: i32,
+        user_id: i32,
+        message_id: Option<i32>,
+    ) -> Result<Option<i32>, DbErr>;
+
+    /// ユーザーの会話一覧を、相手（またはグループ）ごとに最新メッセージと未読件数を集計して取得します。
+    /// - `page` と `per_page`: ページネーション用
+    ///
+    /// 最新の会話から順に並び、返り値は (会話一覧, 全件数) のタプルです。
+    async fn list_conversations(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<ConversationSummary>, i32), DbErr>;
+}
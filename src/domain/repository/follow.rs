@@ -0,0 +1,35 @@
+use crate::domain::entity::users::Model as User;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait FollowRepository {
+    /// `follower_id` のユーザーが `followee_id` のユーザーをフォローします。
+    /// 既にフォローしている場合は何もしません。
+    ///
+    /// 新たにフォローした場合は true を返します。
+    async fn follow(&self, follower_id: i32, followee_id: i32) -> Result<bool, DbErr>;
+
+    /// フォローを解除します。解除された場合は true を返します。
+    async fn unfollow(&self, follower_id: i32, followee_id: i32) -> Result<bool, DbErr>;
+
+    /// 指定ユーザーのフォロワーを、フォローされた日時の新しい順に取得します。
+    ///
+    /// 返り値は (ユーザー一覧, 全件数) のタプルです。
+    async fn list_followers(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr>;
+
+    /// 指定ユーザーがフォローしているユーザーを、フォローした日時の新しい順に取得します。
+    ///
+    /// 返り値は (ユーザー一覧, 全件数) のタプルです。
+    async fn list_following(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr>;
+}
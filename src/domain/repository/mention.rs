@@ -0,0 +1,35 @@
+use crate::domain::entity::mentions::Model as Mention;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+/// メンションを含む本文の種類と ID
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MentionSource {
+    Post(i32),
+    Message(i32),
+}
+
+#[async_trait]
+pub trait MentionRepository {
+    /// 正規化済みのハンドルに一致する、論理削除されていないユーザーの ID を取得します。
+    /// 該当するユーザーのいないハンドルは無視します。
+    async fn resolve_handles(&self, handles: &[String]) -> Result<Vec<i32>, DbErr>;
+
+    /// `author_id` のユーザーから `user_ids` のユーザーへのメンションを記録します。
+    /// 同じ本文で記録済みのユーザーは無視し、新たに記録したメンションのみを返します。
+    async fn insert(
+        &self,
+        author_id: i32,
+        source: MentionSource,
+        user_ids: &[i32],
+    ) -> Result<Vec<Mention>, DbErr>;
+
+    /// `user_id` のユーザーへのメンションを新しい順に取得します。
+    /// `before_id` を指定した場合はその ID より古いメンションのみを返します。
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        before_id: Option<i32>,
+        limit: u64,
+    ) -> Result<Vec<Mention>, DbErr>;
+}
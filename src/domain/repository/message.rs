@@ -1,19 +1,40 @@
+use crate::domain::entity::attachments::Model as Attachment;
+use crate::domain::entity::message_receipts::Model as MessageReceipt;
+use crate::domain::entity::message_revisions::Model as MessageRevision;
 use crate::domain::entity::messages;
-use sea_orm::DbErr;
+use sea_orm::{DbErr, FromQueryResult};
+
+/// メッセージに付けられたリアクションの絵文字ごとの集計結果
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult)]
+pub struct ReactionCount {
+    pub message_id: i32,
+    pub emoji: String,
+    pub count: i64,
+    /// 閲覧者自身がこの絵文字でリアクション済みかどうか
+    pub reacted_by_viewer: bool,
+}
 
 #[async_trait::async_trait]
 pub trait MessageRepository {
     /// 新規メッセージを送信します。
     /// - `sender_id`: 送信者のユーザーID
-    /// - `receiver_id`: 受信者のユーザーID
+    /// - `receiver_id`: 受信者のユーザーID（1対1の場合）
+    /// - `conversation_id`: グループ会話のID（グループの場合）
     /// - `content`: メッセージ本文
+    /// - `reply_to_message_id`: 返信元のメッセージID（返信の場合）
+    /// - `attachment_ids`: 添付する、送信者がアップロードした未使用の添付ファイルのID
     ///
+    /// `receiver_id` と `conversation_id` はどちらか一方のみ指定します。
+    /// 添付できない添付ファイルが含まれる場合はメッセージを作成せずに `RecordNotUpdated` を返します。
     /// 成功時は送信されたメッセージ（Entity）を返します。
     async fn send_message(
         &self,
         sender_id: i32,
-        receiver_id: i32,
+        receiver_id: Option<i32>,
+        conversation_id: Option<i32>,
         content: String,
+        reply_to_message_id: Option<i32>,
+        attachment_ids: &[i32],
     ) -> Result<messages::Model, DbErr>;
 
     /// ユーザーのメッセージ一覧を取得します。
@@ -30,25 +51,28 @@ pub trait MessageRepository {
         per_page: i32,
     ) -> Result<(Vec<messages::Model>, i32, i32), DbErr>;
 
-    /// ユーザー間の会話履歴を取得します。
+    /// ユーザー間またはグループの会話履歴を取得します。
     /// - `user_id`: リクエストを送信するユーザーのID
-    /// - `peer_id`: 会話相手のユーザーID
+    /// - `peer_id`: 会話相手のユーザーID（1対1の場合）
+    /// - `conversation_id`: グループ会話のID（グループの場合）
     /// - `page` と `per_page`: ページネーション用
     ///
-    /// 返り値は、(メッセージリスト, 全件数) のタプルです。
+    /// 返り値は、(メッセージリスト, 全件数, `user_id` にとっての未読件数) のタプルです。
+    /// グループの未読件数はメンバーごとの既読位置から算出します。
     async fn get_conversation(
         &self,
         user_id: i32,
-        peer_id: i32,
+        peer_id: Option<i32>,
+        conversation_id: Option<i32>,
         page: i32,
         per_page: i32,
-    ) -> Result<(Vec<messages::Model>, i32), DbErr>;
+    ) -> Result<(Vec<messages::Model>, i32, i32), DbErr>;
 
-    /// 指定されたメッセージまたはユーザー間のメッセージを既読に更新します。
+    /// 指定された1対1のメッセージ、またはユーザー間のメッセージについて受信者の既読を記録します。
     /// - 単一の `message_id` を指定する場合や、
     /// - 複数の `message_ids`、あるいは特定ユーザー間の全メッセージ更新を行うために `from_user_id` / `to_user_id` を指定できます。
     ///
-    /// 更新された件数を返します。
+    /// 新たに既読になった件数を返します。メッセージ自体（`updated_at` など）は更新しません。
     async fn mark_as_read(
         &self,
         message_id: Option<i32>,
@@ -57,7 +81,69 @@ pub trait MessageRepository {
         to_user_id: Option<i32>,
     ) -> Result<i32, DbErr>;
 
+    /// ユーザーが受信したメッセージの配達を古い順に最大 `limit` 件記録し、新たに配達済みになった件数を返します。
+    /// `message_ids` が空の場合は未配達のメッセージが対象です（グループのメッセージを含みます）。
+    async fn mark_as_delivered(
+        &self,
+        user_id: i32,
+        message_ids: Vec<i32>,
+        limit: u64,
+    ) -> Result<i32, DbErr>;
+
+    /// 指定メッセージの受信者ごとの配達・既読の記録を取得します。
+    async fn find_receipts(&self, message_ids: &[i32]) -> Result<Vec<MessageReceipt>, DbErr>;
+
+    /// 指定メッセージの添付ファイルをアップロード順に取得します。
+    async fn find_attachments(&self, message_ids: &[i32]) -> Result<Vec<Attachment>, DbErr>;
+
+    /// 指定されたIDのうち、論理削除されていないメッセージを取得します。
+    async fn find_by_ids(&self, message_ids: Vec<i32>) -> Result<Vec<messages::Model>, DbErr>;
+
+    /// 2人のユーザーの間で1対1のメッセージがやり取りされたことがあるかを判定します（論理削除されたものも含みます）。
+    async fn has_direct_messages(&self, user_id: i32, peer_id: i32) -> Result<bool, DbErr>;
+
+    /// 返信元の表示用に、論理削除されたものも含めて指定されたIDのメッセージを取得します。
+    async fn find_by_ids_including_deleted(
+        &self,
+        message_ids: &[i32],
+    ) -> Result<Vec<messages::Model>, DbErr>;
+
+    /// 論理削除されていないメッセージの本文を書き換え、編集前の本文を履歴に残します。
+    /// `edited_at` を現在時刻に更新します。存在しない場合は `RecordNotFound` を返します。
+    async fn edit_message(
+        &self,
+        message_id: i32,
+        content: String,
+    ) -> Result<messages::Model, DbErr>;
+
+    /// メッセージの編集履歴（編集前の本文）を古い順に取得します。
+    async fn list_revisions(&self, message_id: i32) -> Result<Vec<MessageRevision>, DbErr>;
+
     /// 指定されたメッセージを削除します（論理削除など）。
     /// 成功時は true を返します。
     async fn delete_message(&self, message_id: i32) -> Result<bool, DbErr>;
+
+    /// メッセージにリアクションを付けます。既に同じ絵文字を付けている場合は false を返します。
+    async fn add_reaction(
+        &self,
+        message_id: i32,
+        user_id: i32,
+        emoji: String,
+    ) -> Result<bool, DbErr>;
+
+    /// メッセージのリアクションを取り消します。付けていなかった場合は false を返します。
+    async fn remove_reaction(
+        &self,
+        message_id: i32,
+        user_id: i32,
+        emoji: &str,
+    ) -> Result<bool, DbErr>;
+
+    /// 指定メッセージのリアクションを絵文字ごとに集計します。
+    /// メッセージごとに、最初にリアクションが付いた絵文字から順に並べます。
+    async fn find_reaction_counts(
+        &self,
+        viewer_id: i32,
+        message_ids: &[i32],
+    ) -> Result<Vec<ReactionCount>, DbErr>;
 }
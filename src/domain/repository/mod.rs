@@ -1,4 +1,10 @@
 pub mod post;
 pub mod user;
 
+pub mod attachment;
+pub mod comment;
+pub mod conversation;
+pub mod follow;
+pub mod mention;
 pub mod message;
+pub mod refresh_token;
@@ -1,13 +1,105 @@
+use crate::domain::entity::attachments::Model as Attachment;
 use crate::domain::entity::post::Model as Post;
+use crate::domain::entity::post_revisions::Model as PostRevision;
+use crate::domain::entity::sea_orm_active_enums::RepostKind;
+use crate::domain::entity::users::Model as User;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use sea_orm::DbErr;
 
+/// 投稿一覧のキーセットページネーション条件
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PostPage {
+    /// 指定した場合はこの ID より古い投稿のみを返す
+    pub before_id: Option<i32>,
+    pub limit: u64,
+}
+
+/// 集計期間内にハッシュタグが付けられた投稿の数
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashtagCount {
+    pub name: String,
+    pub post_count: i64,
+}
+
 #[async_trait]
 pub trait PostRepository {
-    async fn find_all(&self) -> Result<Vec<Post>, DbErr>;
+    /// 投稿を新しい順に取得します。
+    async fn find_all(&self, page: PostPage) -> Result<Vec<Post>, DbErr>;
     async fn get_by_id(&self, id: i32) -> Result<Option<Post>, DbErr>;
-    async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<Post>, DbErr>;
-    async fn insert(&self, body: String, user_id: i32) -> Result<Post, DbErr>;
-    async fn update(&self, id: i32, body: String) -> Result<Post, DbErr>;
+    /// 指定した ID の投稿をまとめて取得します。存在しない ID は無視します。
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Post>, DbErr>;
+    /// 指定ユーザーの投稿を新しい順に取得します。
+    async fn find_by_user_id(&self, user_id: i32, page: PostPage) -> Result<Vec<Post>, DbErr>;
+    /// ホームタイムライン（自分とフォロー中のユーザーの投稿）を新しい順に取得します。
+    /// 書き込み時に配信済みの投稿は `timeline_entries` から、それ以外はフォロー関係から集めます。
+    async fn find_home_timeline(&self, user_id: i32, page: PostPage) -> Result<Vec<Post>, DbErr>;
+    /// 投稿者のフォロワー数が `max_followers` 以下であれば、投稿を全フォロワーの
+    /// `timeline_entries` に書き込み、配信済みとして記録します。
+    ///
+    /// 配信した場合は true を返します。
+    async fn fan_out(
+        &self,
+        post_id: i32,
+        author_id: i32,
+        max_followers: i32,
+    ) -> Result<bool, DbErr>;
+    /// 投稿を作成し、`user_id` のユーザーがアップロードした未使用の添付ファイルと
+    /// 本文のハッシュタグ `tags`（正規化済み）を同じトランザクションで紐付けます。
+    /// 添付できない添付ファイルが含まれる場合は投稿を作成せずに `RecordNotUpdated` を返します。
+    async fn insert(
+        &self,
+        body: String,
+        user_id: i32,
+        attachment_ids: &[i32],
+        tags: &[String],
+    ) -> Result<Post, DbErr>;
+    /// `repost_of_id` の投稿をリポスト（`RepostKind::Quote` の場合は本文付きで引用）します。
+    ///
+    /// 単純なリポストは 1 ユーザーにつき 1 件までで、既にある場合はそれを返します。
+    async fn insert_repost(
+        &self,
+        user_id: i32,
+        repost_of_id: i32,
+        kind: RepostKind,
+        body: String,
+    ) -> Result<Post, DbErr>;
+    /// 投稿の本文を更新し、更新前の本文を編集履歴として保存します。
+    /// ハッシュタグも同じトランザクションで `tags`（正規化済み）に置き換えます。
+    async fn update(&self, id: i32, body: String, tags: &[String]) -> Result<Post, DbErr>;
+    /// 投稿の編集履歴を古い順に取得します。
+    async fn list_revisions(&self, post_id: i32) -> Result<Vec<PostRevision>, DbErr>;
+    /// 投稿を削除します。この投稿の単純なリポストも合わせて削除し、
+    /// 引用は本文を残したままリポスト元への参照のみを外します。
     async fn delete(&self, id: i32) -> Result<(), DbErr>;
+    /// 投稿にいいねします。既にいいね済みの場合は何もしません。
+    ///
+    /// 新たにいいねした場合は true を返します。
+    async fn like(&self, post_id: i32, user_id: i32) -> Result<bool, DbErr>;
+    /// いいねを取り消します。取り消した場合は true を返します。
+    async fn unlike(&self, post_id: i32, user_id: i32) -> Result<bool, DbErr>;
+    /// 投稿にいいねしたユーザーを、いいねした日時の新しい順に取得します。
+    ///
+    /// 返り値は (ユーザー一覧, 全件数) のタプルです。
+    async fn list_likers(
+        &self,
+        post_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr>;
+    /// `post_ids` のうち `user_id` のユーザーがいいね済みの投稿IDを取得します。
+    async fn find_liked_post_ids(&self, user_id: i32, post_ids: &[i32]) -> Result<Vec<i32>, DbErr>;
+    /// 指定投稿の添付ファイルをアップロード順に取得します。
+    async fn find_attachments(&self, post_ids: &[i32]) -> Result<Vec<Attachment>, DbErr>;
+    /// 投稿のハッシュタグを `tags`（正規化済み）に置き換えます。
+    /// 引き続き付いているタグは付与日時を変えずに残します。
+    async fn set_hashtags(&self, post_id: i32, tags: &[String]) -> Result<(), DbErr>;
+    /// 指定したハッシュタグ（正規化済み）の付いた投稿を新しい順に取得します。
+    async fn find_by_hashtag(&self, tag: &str, page: PostPage) -> Result<Vec<Post>, DbErr>;
+    /// `since` 以降に付けられた投稿数の多いハッシュタグを上位 `limit` 件取得します。
+    async fn trending_hashtags(
+        &self,
+        since: NaiveDateTime,
+        limit: u64,
+    ) -> Result<Vec<HashtagCount>, DbErr>;
 }
@@ -0,0 +1,27 @@
+use crate::domain::entity::refresh_tokens;
+use chrono::NaiveDateTime;
+use sea_orm::DbErr;
+
+#[async_trait::async_trait]
+pub trait RefreshTokenRepository {
+    /// リフレッシュトークンを保存します。
+    /// - `token_hash`: トークン本体の SHA-256 ハッシュ
+    async fn create(
+        &self,
+        user_id: i32,
+        token_hash: String,
+        expires_at: NaiveDateTime,
+    ) -> Result<refresh_tokens::Model, DbErr>;
+
+    /// ハッシュからリフレッシュトークンを取得します。失効済みのトークンも返します。
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<refresh_tokens::Model>, DbErr>;
+
+    /// トークンを失効させます。
+    ///
+    /// 未失効のトークンを失効させた場合のみ true を返すため、同じトークンによる
+    /// 同時リフレッシュのうち1つだけが成功します。
+    async fn revoke(&self, id: i32) -> Result<bool, DbErr>;
+
+    /// ユーザーの未失効トークンをすべて失効させ、失効させた件数を返します。
+    async fn revoke_all_for_user(&self, user_id: i32) -> Result<u64, DbErr>;
+}
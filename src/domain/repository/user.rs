@@ -1,10 +1,39 @@
+use crate::domain::entity::sea_orm_active_enums::UserRole;
 use crate::domain::entity::users::Model as User;
 use async_trait::async_trait;
 
+/// ユーザー一覧の検索条件とページネーション
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserFilter {
+    /// 0 始まりのページ番号（負の場合は先頭ページ）
+    pub page: i32,
+    /// 1 ページあたりの件数。0 以下の場合は 20 件、最大 100 件に丸める
+    pub per_page: i32,
+    /// 名前の部分一致（大文字小文字を区別しない）
+    pub name: Option<String>,
+    /// 住所の部分一致（大文字小文字を区別しない）
+    pub address: Option<String>,
+    /// 性別の完全一致
+    pub gender: Option<String>,
+    /// true の場合は論理削除されたユーザーも含める
+    pub include_deleted: bool,
+}
+
+/// メールアドレスやハンドルの一意制約に違反したエラーかどうかを返します。
+pub fn is_unique_violation(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(e) if e.is_unique_violation())
+}
+
 #[async_trait]
 pub trait UserRepository {
     async fn get_by_id(&self, id: i32) -> Result<User, sqlx::Error>;
-    async fn list(&self) -> Result<Vec<User>, sqlx::Error>;
+    /// 条件に一致するユーザーを ID 順に取得し、(ユーザー一覧, 全件数) を返します。
+    async fn list(&self, filter: UserFilter) -> Result<(Vec<User>, i32), sqlx::Error>;
+    /// 論理削除されていないユーザーをメールアドレスで検索します（大文字・小文字は区別しません）。
+    async fn find_by_email(&self, email: &str) -> Result<User, sqlx::Error>;
+    /// 論理削除されていないユーザーを正規化済みのハンドルで検索します。
+    async fn find_by_handle(&self, handle: &str) -> Result<User, sqlx::Error>;
+    #[allow(clippy::too_many_arguments)]
     async fn create(
         &self,
         name: String,
@@ -13,7 +42,10 @@ pub trait UserRepository {
         age: Option<i32>,
         gender: Option<String>,
         address: Option<String>,
+        password_hash: Option<String>,
     ) -> Result<User, sqlx::Error>;
+    /// `None` の項目は更新しません。`handle` は正規化済みの値を渡します。
+    #[allow(clippy::too_many_arguments)]
     async fn update(
         &self,
         id: i32,
@@ -23,7 +55,13 @@ pub trait UserRepository {
         age: Option<i32>,
         gender: Option<String>,
         address: Option<String>,
+        handle: Option<String>,
     ) -> Result<User, sqlx::Error>;
     async fn delete(&self, id: i32) -> Result<User, sqlx::Error>;
+    /// 論理削除されたユーザーを復元します。
+    async fn restore(&self, id: i32) -> Result<User, sqlx::Error>;
+    /// ユーザーのロールを変更します。
+    async fn update_role(&self, id: i32, role: UserRole) -> Result<User, sqlx::Error>;
+    /// ユーザーを物理削除します。存在しない場合は `RowNotFound` を返します。
     async fn hard_delete(&self, id: i32) -> Result<(), sqlx::Error>;
 }
@@ -0,0 +1,169 @@
+use crate::admin_proto::admin_service_server::AdminService;
+use crate::admin_proto::{
+    AdminUser, HardDeleteUserRequest, HardDeleteUserResponse, ListAllUsersRequest,
+    ListAllUsersResponse, RemoveMessageRequest, RemoveMessageResponse, RemovePostRequest,
+    RemovePostResponse, RestoreUserRequest, RestoreUserResponse, Role, SetUserRoleRequest,
+    SetUserRoleResponse,
+};
+use crate::domain::entity::sea_orm_active_enums::UserRole;
+use crate::domain::entity::users::Model as User;
+use crate::domain::repository::user::UserFilter;
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::usecase::admin_usecase::AdminUseCase;
+use crate::usecase::policy::AccessError;
+use sea_orm::DbErr;
+use tonic::{Request, Response, Status};
+
+pub struct AdminHandler<U> {
+    usecase: U,
+}
+
+impl<U: AdminUseCase> AdminHandler<U> {
+    pub fn new(usecase: U) -> Self {
+        Self { usecase }
+    }
+
+    fn to_proto_role(role: UserRole) -> Role {
+        match role {
+            UserRole::User => Role::User,
+            UserRole::Moderator => Role::Moderator,
+            UserRole::Admin => Role::Admin,
+        }
+    }
+
+    // ユーザーエンティティを管理用の Proto メッセージに変換するヘルパー関数
+    fn to_proto_user(user: User) -> AdminUser {
+        AdminUser {
+            id: user.id as u64,
+            name: user.name,
+            email: user.email,
+            role: Self::to_proto_role(user.role).into(),
+            created_at: user.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            deleted_at: user
+                .deleted_at
+                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn to_status(e: AccessError) -> Status {
+        match e {
+            AccessError::PermissionDenied => Status::permission_denied(e.to_string()),
+            AccessError::Sqlx(sqlx::Error::RowNotFound) => Status::not_found("User not found"),
+            AccessError::Database(DbErr::RecordNotFound(_)) => Status::not_found("Post not found"),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<U: AdminUseCase + Send + Sync + 'static> AdminService for AdminHandler<U> {
+    async fn list_all_users(
+        &self,
+        request: Request<ListAllUsersRequest>,
+    ) -> Result<Response<ListAllUsersResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        let (users, total_count) = self
+            .usecase
+            .list_all_users(
+                caller,
+                UserFilter {
+                    page: req.page,
+                    per_page: req.per_page,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(ListAllUsersResponse {
+            users: users.into_iter().map(Self::to_proto_user).collect(),
+            total_count,
+        }))
+    }
+
+    async fn restore_user(
+        &self,
+        request: Request<RestoreUserRequest>,
+    ) -> Result<Response<RestoreUserResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        let user = self
+            .usecase
+            .restore_user(caller, req.user_id as i32)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(RestoreUserResponse {
+            user: Some(Self::to_proto_user(user)),
+        }))
+    }
+
+    async fn hard_delete_user(
+        &self,
+        request: Request<HardDeleteUserRequest>,
+    ) -> Result<Response<HardDeleteUserResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        self.usecase
+            .hard_delete_user(caller, req.user_id as i32)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(HardDeleteUserResponse { success: true }))
+    }
+
+    async fn set_user_role(
+        &self,
+        request: Request<SetUserRoleRequest>,
+    ) -> Result<Response<SetUserRoleResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        let role = match req.role() {
+            Role::User => UserRole::User,
+            Role::Moderator => UserRole::Moderator,
+            Role::Admin => UserRole::Admin,
+            Role::Unspecified => return Err(Status::invalid_argument("role is required")),
+        };
+
+        let user = self
+            .usecase
+            .set_user_role(caller, req.user_id as i32, role)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(SetUserRoleResponse {
+            user: Some(Self::to_proto_user(user)),
+        }))
+    }
+
+    async fn remove_post(
+        &self,
+        request: Request<RemovePostRequest>,
+    ) -> Result<Response<RemovePostResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        self.usecase
+            .remove_post(caller, req.post_id as i32)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(RemovePostResponse { success: true }))
+    }
+
+    async fn remove_message(
+        &self,
+        request: Request<RemoveMessageRequest>,
+    ) -> Result<Response<RemoveMessageResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        let success = self
+            .usecase
+            .remove_message(caller, req.message_id as i32)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(RemoveMessageResponse { success }))
+    }
+}
@@ -0,0 +1,146 @@
+use crate::attachment_proto::attachment_service_server::AttachmentService;
+use crate::attachment_proto::download_attachment_response::Part as DownloadPart;
+use crate::attachment_proto::upload_attachment_request::Part as UploadPart;
+use crate::attachment_proto::{
+    Attachment, DownloadAttachmentRequest, DownloadAttachmentResponse, UploadAttachmentRequest,
+    UploadAttachmentResponse,
+};
+use crate::domain::entity::attachments::Model as AttachmentModel;
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::usecase::attachment_usecase::{
+    is_valid_filename, normalize_mime_type, AttachmentError, AttachmentUseCase,
+};
+use std::pin::Pin;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status, Streaming};
+
+/// ダウンロード時に 1 メッセージで返す内容の最大バイト数
+const DOWNLOAD_CHUNK_BYTES: usize = 64 * 1024;
+
+/// 添付ファイルエンティティを Proto メッセージに変換します（投稿・メッセージの添付ファイルでも共有する）。
+pub(crate) fn to_proto_attachment(attachment: AttachmentModel) -> Attachment {
+    Attachment {
+        id: attachment.id as u64,
+        filename: attachment.filename,
+        mime_type: attachment.mime_type,
+        size: attachment.size_bytes as u64,
+        checksum: attachment.checksum,
+        created_at: attachment
+            .created_at
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+    }
+}
+
+pub struct AttachmentHandler<U> {
+    usecase: U,
+}
+
+impl<U: AttachmentUseCase> AttachmentHandler<U> {
+    pub fn new(usecase: U) -> Self {
+        Self { usecase }
+    }
+
+    fn attachment_status(e: AttachmentError) -> Status {
+        match e {
+            AttachmentError::InvalidFilename
+            | AttachmentError::InvalidMimeType
+            | AttachmentError::Empty
+            | AttachmentError::TooLarge(_) => Status::invalid_argument(e.to_string()),
+            AttachmentError::NotFound => Status::not_found("Attachment not found"),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<U: AttachmentUseCase + Send + Sync + 'static> AttachmentService for AttachmentHandler<U> {
+    type DownloadAttachmentStream =
+        Pin<Box<dyn Stream<Item = Result<DownloadAttachmentResponse, Status>> + Send>>;
+
+    async fn upload_attachment(
+        &self,
+        request: Request<Streaming<UploadAttachmentRequest>>,
+    ) -> Result<Response<UploadAttachmentResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let mut inbound = request.into_inner();
+
+        // 内容を受け取る前にメタデータを検証し、不正なアップロードは早めに打ち切る
+        let metadata = match inbound.message().await? {
+            Some(UploadAttachmentRequest {
+                part: Some(UploadPart::Metadata(metadata)),
+            }) => metadata,
+            _ => return Err(Status::invalid_argument("first message must be metadata")),
+        };
+        if !is_valid_filename(&metadata.filename) {
+            return Err(Self::attachment_status(AttachmentError::InvalidFilename));
+        }
+        if normalize_mime_type(&metadata.mime_type).is_none() {
+            return Err(Self::attachment_status(AttachmentError::InvalidMimeType));
+        }
+
+        let max_bytes = self.usecase.max_bytes();
+        let mut data = Vec::new();
+        while let Some(message) = inbound.message().await? {
+            match message.part {
+                Some(UploadPart::Chunk(chunk)) => {
+                    if data.len() + chunk.len() > max_bytes {
+                        return Err(Self::attachment_status(AttachmentError::TooLarge(
+                            max_bytes,
+                        )));
+                    }
+                    data.extend_from_slice(&chunk);
+                }
+                _ => {
+                    return Err(Status::invalid_argument(
+                        "metadata must be sent only in the first message",
+                    ))
+                }
+            }
+        }
+
+        let attachment = self
+            .usecase
+            .upload(
+                caller.user_id,
+                metadata.filename,
+                metadata.mime_type,
+                data.into(),
+            )
+            .await
+            .map_err(Self::attachment_status)?;
+
+        Ok(Response::new(UploadAttachmentResponse {
+            attachment: Some(to_proto_attachment(attachment)),
+        }))
+    }
+
+    async fn download_attachment(
+        &self,
+        request: Request<DownloadAttachmentRequest>,
+    ) -> Result<Response<Self::DownloadAttachmentStream>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let (attachment, data) = self
+            .usecase
+            .download(caller.user_id, req.id as i32)
+            .await
+            .map_err(Self::attachment_status)?;
+
+        let metadata = DownloadPart::Metadata(to_proto_attachment(attachment));
+        let chunks = (0..data.len())
+            .step_by(DOWNLOAD_CHUNK_BYTES)
+            .map(move |start| {
+                let end = (start + DOWNLOAD_CHUNK_BYTES).min(data.len());
+                DownloadPart::Chunk(data.slice(start..end).to_vec())
+            });
+        let stream = tokio_stream::iter(
+            std::iter::once(metadata)
+                .chain(chunks)
+                .map(|part| DownloadAttachmentResponse { part: Some(part) })
+                .map(Ok),
+        );
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
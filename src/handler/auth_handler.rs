@@ -0,0 +1,106 @@
+use crate::auth_proto::auth_service_server::AuthService;
+use crate::auth_proto::{
+    LoginRequest, LoginResponse, LogoutRequest, LogoutResponse, RefreshTokenRequest,
+    RefreshTokenResponse, RegisterRequest, RegisterResponse,
+};
+use crate::usecase::auth_usecase::{AuthError, AuthUseCase};
+use tonic::{Request, Response, Status};
+
+pub struct AuthHandler<U> {
+    usecase: U,
+}
+
+impl<U: AuthUseCase> AuthHandler<U> {
+    pub fn new(usecase: U) -> Self {
+        Self { usecase }
+    }
+
+    fn to_status(e: AuthError) -> Status {
+        match e {
+            AuthError::InvalidCredentials | AuthError::InvalidToken => {
+                Status::unauthenticated(e.to_string())
+            }
+            AuthError::WeakPassword => Status::invalid_argument(e.to_string()),
+            AuthError::EmailTaken => Status::already_exists(e.to_string()),
+            AuthError::Hash(_)
+            | AuthError::Token(_)
+            | AuthError::Database(_)
+            | AuthError::Repository(_) => Status::internal(e.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<U: AuthUseCase + Send + Sync + 'static> AuthService for AuthHandler<U> {
+    async fn register(
+        &self,
+        request: Request<RegisterRequest>,
+    ) -> Result<Response<RegisterResponse>, Status> {
+        let req = request.into_inner();
+        if req.name.trim().is_empty() || req.email.trim().is_empty() {
+            return Err(Status::invalid_argument("name and email are required"));
+        }
+
+        let user = self
+            .usecase
+            .register(req.name, req.email, req.password)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(RegisterResponse {
+            user_id: user.id as u64,
+        }))
+    }
+
+    async fn login(
+        &self,
+        request: Request<LoginRequest>,
+    ) -> Result<Response<LoginResponse>, Status> {
+        let req = request.into_inner();
+        let tokens = self
+            .usecase
+            .login(req.email, req.password)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(LoginResponse {
+            user_id: tokens.user_id as u64,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            expires_in: tokens.expires_in,
+        }))
+    }
+
+    async fn refresh_token(
+        &self,
+        request: Request<RefreshTokenRequest>,
+    ) -> Result<Response<RefreshTokenResponse>, Status> {
+        let req = request.into_inner();
+        let tokens = self
+            .usecase
+            .refresh(req.refresh_token)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(RefreshTokenResponse {
+            user_id: tokens.user_id as u64,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            expires_in: tokens.expires_in,
+        }))
+    }
+
+    async fn logout(
+        &self,
+        request: Request<LogoutRequest>,
+    ) -> Result<Response<LogoutResponse>, Status> {
+        let req = request.into_inner();
+        let success = self
+            .usecase
+            .logout(req.refresh_token, req.all_devices)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(LogoutResponse { success }))
+    }
+}
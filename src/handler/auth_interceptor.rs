@@ -0,0 +1,155 @@
+use crate::domain::entity::sea_orm_active_enums::UserRole;
+use crate::infra::token::TokenManager;
+use crate::usecase::policy::Caller;
+use std::sync::Arc;
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+/// 認証済みの呼び出し元ユーザー
+///
+/// `AuthInterceptor` によってリクエストの extensions に設定されます。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurrentUser {
+    pub user_id: i32,
+    pub role: UserRole,
+}
+
+// tonic::Status をそのまま返すため、エラー型のサイズに関する lint は抑制する
+#[allow(clippy::result_large_err)]
+impl CurrentUser {
+    /// リクエストから認証済みユーザーを取得します。
+    pub fn from_request<T>(request: &Request<T>) -> Result<Self, Status> {
+        request
+            .extensions()
+            .get::<CurrentUser>()
+            .copied()
+            .ok_or_else(|| Status::unauthenticated("authentication required"))
+    }
+
+    /// 認可の判定に使う呼び出し元の情報に変換します。
+    pub fn caller(&self) -> Caller {
+        Caller::new(self.user_id, self.role)
+    }
+
+    /// リクエストに含まれるユーザーIDを認証済みユーザーと照合します。
+    /// - `claimed` が 0（未指定）の場合は認証済みユーザーのIDを使用します。
+    /// - 別のユーザーIDが指定された場合は `permission_denied` を返します。
+    pub fn resolve(&self, claimed: u64) -> Result<i32, Status> {
+        if claimed == 0 || claimed == self.user_id as u64 {
+            Ok(self.user_id)
+        } else {
+            Err(Status::permission_denied(
+                "user_id does not match the authenticated user",
+            ))
+        }
+    }
+}
+
+/// `authorization: Bearer <token>` メタデータのアクセストークンを検証し、
+/// 呼び出し元を `CurrentUser` としてリクエストに設定するインターセプター
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    tokens: Arc<TokenManager>,
+}
+
+impl AuthInterceptor {
+    pub fn new(tokens: Arc<TokenManager>) -> Self {
+        Self { tokens }
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("missing bearer token"))?;
+
+        let current = self
+            .tokens
+            .verify_access_token(token.trim())
+            .ok()
+            .and_then(|claims| {
+                claims.user_id().map(|user_id| CurrentUser {
+                    user_id,
+                    role: claims.role,
+                })
+            })
+            .ok_or_else(|| Status::unauthenticated("invalid or expired access token"))?;
+
+        request.extensions_mut().insert(current);
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tonic::Code;
+
+    fn new_interceptor() -> (AuthInterceptor, Arc<TokenManager>) {
+        let tokens = Arc::new(TokenManager::new(
+            b"test-secret",
+            Duration::minutes(15),
+            Duration::days(30),
+        ));
+        (AuthInterceptor::new(Arc::clone(&tokens)), tokens)
+    }
+
+    fn request_with_authorization(value: &str) -> Request<()> {
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert("authorization", value.parse().unwrap());
+        request
+    }
+
+    #[test]
+    fn test_valid_token_sets_current_user() {
+        let (mut interceptor, tokens) = new_interceptor();
+        let token = tokens.issue_access_token(7, UserRole::Admin).unwrap();
+
+        let request = interceptor
+            .call(request_with_authorization(&format!("Bearer {}", token)))
+            .expect("Interceptor rejected a valid token");
+        let current = CurrentUser::from_request(&request).expect("CurrentUser not set");
+        assert_eq!(current.user_id, 7);
+        assert_eq!(current.role, UserRole::Admin);
+    }
+
+    #[test]
+    fn test_missing_or_invalid_token_is_rejected() {
+        let (mut interceptor, tokens) = new_interceptor();
+        let token = tokens.issue_access_token(7, UserRole::User).unwrap();
+
+        let status = interceptor.call(Request::new(())).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+
+        let status = interceptor
+            .call(request_with_authorization(&format!("Basic {}", token)))
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+
+        let status = interceptor
+            .call(request_with_authorization("Bearer not-a-token"))
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+    }
+
+    #[test]
+    fn test_resolve_rejects_other_user_id() {
+        let current = CurrentUser {
+            user_id: 7,
+            role: UserRole::User,
+        };
+        assert_eq!(current.resolve(0).unwrap(), 7);
+        assert_eq!(current.resolve(7).unwrap(), 7);
+        assert_eq!(
+            current.resolve(8).unwrap_err().code(),
+            Code::PermissionDenied
+        );
+    }
+}
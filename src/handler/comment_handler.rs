@@ -0,0 +1,183 @@
+use crate::comment_proto::comment_service_server::CommentService;
+use crate::comment_proto::{
+    Comment, CommentThread, CreateCommentRequest, CreateCommentResponse, DeleteCommentRequest,
+    DeleteCommentResponse, ListCommentsRequest, ListCommentsResponse, ListRepliesRequest,
+    ListRepliesResponse, UpdateCommentRequest, UpdateCommentResponse,
+};
+use crate::domain::entity::comments::Model as CommentModel;
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::infra::cursor::{decode_cursor, encode_cursor};
+use crate::usecase::comment_usecase::{
+    CommentError, CommentThread as CommentThreadModel, CommentUseCase,
+};
+use tonic::{Request, Response, Status};
+
+pub struct CommentHandler<U> {
+    usecase: U,
+}
+
+impl<U: CommentUseCase> CommentHandler<U> {
+    pub fn new(usecase: U) -> Self {
+        Self { usecase }
+    }
+
+    // コメントエンティティを Proto メッセージに変換するヘルパー関数
+    fn to_proto_comment(comment: CommentModel) -> Comment {
+        Comment {
+            id: comment.id as u64,
+            post_id: comment.post_id as u64,
+            user_id: comment.user_id as u64,
+            parent_comment_id: comment.parent_comment_id.unwrap_or_default() as u64,
+            body: comment.body,
+            created_at: comment.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            updated_at: comment.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            deleted: comment.deleted_at.is_some(),
+        }
+    }
+
+    fn to_proto_thread(thread: CommentThreadModel) -> CommentThread {
+        CommentThread {
+            comment: Some(Self::to_proto_comment(thread.comment)),
+            replies: thread
+                .replies
+                .into_iter()
+                .map(Self::to_proto_comment)
+                .collect(),
+            reply_count: thread.reply_count,
+            replies_page_token: thread
+                .next_reply_after_id
+                .map(encode_cursor)
+                .unwrap_or_default(),
+        }
+    }
+
+    // 空のページトークンは先頭ページとして扱う
+    #[allow(clippy::result_large_err)]
+    fn decode_page_token(page_token: &str) -> Result<Option<i32>, Status> {
+        if page_token.is_empty() {
+            return Ok(None);
+        }
+        decode_cursor(page_token)
+            .map(Some)
+            .ok_or_else(|| Status::invalid_argument("invalid page_token"))
+    }
+
+    // 本文が空のコメントは作成・編集できない
+    #[allow(clippy::result_large_err)]
+    fn validate_body(body: &str) -> Result<(), Status> {
+        if body.trim().is_empty() {
+            return Err(Status::invalid_argument("body must not be empty"));
+        }
+        Ok(())
+    }
+
+    fn to_status(e: CommentError) -> Status {
+        match e {
+            CommentError::PostNotFound => Status::not_found("Post not found"),
+            CommentError::CommentNotFound => Status::not_found("Comment not found"),
+            CommentError::InvalidParent => Status::invalid_argument(e.to_string()),
+            CommentError::PermissionDenied => Status::permission_denied(e.to_string()),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<U: CommentUseCase + Send + Sync + 'static> CommentService for CommentHandler<U> {
+    async fn create_comment(
+        &self,
+        request: Request<CreateCommentRequest>,
+    ) -> Result<Response<CreateCommentResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        Self::validate_body(&req.body)?;
+        let parent_comment_id = (req.parent_comment_id > 0).then_some(req.parent_comment_id as i32);
+
+        let comment = self
+            .usecase
+            .create_comment(
+                caller.user_id,
+                req.post_id as i32,
+                parent_comment_id,
+                req.body,
+            )
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(CreateCommentResponse {
+            comment: Some(Self::to_proto_comment(comment)),
+        }))
+    }
+
+    async fn list_comments(
+        &self,
+        request: Request<ListCommentsRequest>,
+    ) -> Result<Response<ListCommentsResponse>, Status> {
+        let req = request.into_inner();
+        let after_id = Self::decode_page_token(&req.page_token)?;
+
+        let (threads, next_after_id) = self
+            .usecase
+            .list_comments(req.post_id as i32, after_id, req.per_page)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(ListCommentsResponse {
+            threads: threads.into_iter().map(Self::to_proto_thread).collect(),
+            next_page_token: next_after_id.map(encode_cursor).unwrap_or_default(),
+        }))
+    }
+
+    async fn list_replies(
+        &self,
+        request: Request<ListRepliesRequest>,
+    ) -> Result<Response<ListRepliesResponse>, Status> {
+        let req = request.into_inner();
+        let after_id = Self::decode_page_token(&req.page_token)?;
+
+        let (replies, next_after_id) = self
+            .usecase
+            .list_replies(req.comment_id as i32, after_id, req.per_page)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(ListRepliesResponse {
+            replies: replies.into_iter().map(Self::to_proto_comment).collect(),
+            next_page_token: next_after_id.map(encode_cursor).unwrap_or_default(),
+        }))
+    }
+
+    async fn update_comment(
+        &self,
+        request: Request<UpdateCommentRequest>,
+    ) -> Result<Response<UpdateCommentResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        Self::validate_body(&req.body)?;
+
+        let comment = self
+            .usecase
+            .update_comment(caller, req.id as i32, req.body)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(UpdateCommentResponse {
+            comment: Some(Self::to_proto_comment(comment)),
+        }))
+    }
+
+    async fn delete_comment(
+        &self,
+        request: Request<DeleteCommentRequest>,
+    ) -> Result<Response<DeleteCommentResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+
+        self.usecase
+            .delete_comment(caller, req.id as i32)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(DeleteCommentResponse { success: true }))
+    }
+}
@@ -0,0 +1,242 @@
+use crate::conversation_proto::conversation_service_server::ConversationService;
+use crate::conversation_proto::{
+    AddMembersRequest, AddMembersResponse, ConversationSummary, CreateGroupRequest,
+    CreateGroupResponse, GetGroupRequest, GetGroupResponse, Group, LeaveGroupRequest,
+    LeaveGroupResponse, ListConversationsRequest, ListConversationsResponse,
+    MarkGroupAsReadRequest, MarkGroupAsReadResponse, RemoveMemberRequest, RemoveMemberResponse,
+    RenameGroupRequest, RenameGroupResponse,
+};
+use crate::domain::entity::conversations::Model as Conversation;
+use crate::domain::repository::conversation::ConversationSummary as Summary;
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::usecase::conversation_usecase::{ConversationError, ConversationUseCase};
+use sea_orm::DbErr;
+use tonic::{Request, Response, Status};
+
+pub struct ConversationHandler<U> {
+    usecase: U,
+}
+
+impl<U: ConversationUseCase> ConversationHandler<U> {
+    pub fn new(usecase: U) -> Self {
+        Self { usecase }
+    }
+
+    // グループ会話エンティティを Proto メッセージに変換するヘルパー関数
+    fn to_proto_group(conversation: Conversation, member_ids: Vec<i32>) -> Group {
+        Group {
+            id: conversation.id as u64,
+            name: conversation.name,
+            created_by: conversation.created_by.unwrap_or(0) as u64,
+            member_ids: member_ids.into_iter().map(|id| id as u64).collect(),
+            created_at: conversation
+                .created_at
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            updated_at: conversation
+                .updated_at
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        }
+    }
+
+    // 会話一覧の集計結果を Proto メッセージに変換するヘルパー関数
+    fn to_proto_summary(summary: Summary) -> ConversationSummary {
+        ConversationSummary {
+            peer_id: summary.peer_id.unwrap_or(0) as u64,
+            conversation_id: summary.conversation_id.unwrap_or(0) as u64,
+            name: summary.name.unwrap_or_default(),
+            last_message_id: summary.last_message_id.unwrap_or(0) as u64,
+            last_sender_id: summary.last_sender_id.unwrap_or(0) as u64,
+            last_message_content: summary.last_message_content.unwrap_or_default(),
+            last_activity_at: summary
+                .last_activity_at
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            unread_count: summary.unread_count as i32,
+        }
+    }
+
+    fn to_status(e: DbErr) -> Status {
+        match e {
+            DbErr::RecordNotFound(_) => Status::not_found("Conversation not found"),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+
+    fn conversation_status(e: ConversationError) -> Status {
+        match e {
+            ConversationError::OwnerHasMembers => Status::failed_precondition(e.to_string()),
+            ConversationError::PermissionDenied => Status::permission_denied(e.to_string()),
+            ConversationError::Database(e) => Self::to_status(e),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<U: ConversationUseCase + Send + Sync + 'static> ConversationService
+    for ConversationHandler<U>
+{
+    async fn create_group(
+        &self,
+        request: Request<CreateGroupRequest>,
+    ) -> Result<Response<CreateGroupResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
+        if req.name.trim().is_empty() {
+            return Err(Status::invalid_argument("name is required"));
+        }
+
+        let member_ids = req.member_ids.iter().map(|&id| id as i32).collect();
+        let (conversation, member_ids) = self
+            .usecase
+            .create_group(user_id, req.name, member_ids)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(CreateGroupResponse {
+            group: Some(Self::to_proto_group(conversation, member_ids)),
+        }))
+    }
+
+    async fn get_group(
+        &self,
+        request: Request<GetGroupRequest>,
+    ) -> Result<Response<GetGroupResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
+        let (conversation, member_ids) = self
+            .usecase
+            .get_group(user_id, req.conversation_id as i32)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(GetGroupResponse {
+            group: Some(Self::to_proto_group(conversation, member_ids)),
+        }))
+    }
+
+    async fn add_members(
+        &self,
+        request: Request<AddMembersRequest>,
+    ) -> Result<Response<AddMembersResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
+        let member_ids = req.member_ids.iter().map(|&id| id as i32).collect();
+        let added_count = self
+            .usecase
+            .add_members(user_id, req.conversation_id as i32, member_ids)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(AddMembersResponse { added_count }))
+    }
+
+    async fn remove_member(
+        &self,
+        request: Request<RemoveMemberRequest>,
+    ) -> Result<Response<RemoveMemberResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        caller.resolve(req.user_id)?;
+        let success = self
+            .usecase
+            .remove_member(
+                caller.caller(),
+                req.conversation_id as i32,
+                req.member_id as i32,
+            )
+            .await
+            .map_err(Self::conversation_status)?;
+
+        Ok(Response::new(RemoveMemberResponse { success }))
+    }
+
+    async fn rename_group(
+        &self,
+        request: Request<RenameGroupRequest>,
+    ) -> Result<Response<RenameGroupResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
+        if req.name.trim().is_empty() {
+            return Err(Status::invalid_argument("name is required"));
+        }
+
+        let (conversation, member_ids) = self
+            .usecase
+            .rename_group(user_id, req.conversation_id as i32, req.name)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(RenameGroupResponse {
+            group: Some(Self::to_proto_group(conversation, member_ids)),
+        }))
+    }
+
+    async fn leave_group(
+        &self,
+        request: Request<LeaveGroupRequest>,
+    ) -> Result<Response<LeaveGroupResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
+        let success = self
+            .usecase
+            .leave_group(user_id, req.conversation_id as i32)
+            .await
+            .map_err(Self::conversation_status)?;
+
+        Ok(Response::new(LeaveGroupResponse { success }))
+    }
+
+    async fn mark_group_as_read(
+        &self,
+        request: Request<MarkGroupAsReadRequest>,
+    ) -> Result<Response<MarkGroupAsReadResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
+        let message_id = if req.message_id > 0 {
+            Some(req.message_id as i32)
+        } else {
+            None
+        };
+
+        let last_read_message_id = self
+            .usecase
+            .mark_as_read(user_id, req.conversation_id as i32, message_id)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(MarkGroupAsReadResponse {
+            last_read_message_id: last_read_message_id.unwrap_or(0) as u64,
+        }))
+    }
+
+    async fn list_conversations(
+        &self,
+        request: Request<ListConversationsRequest>,
+    ) -> Result<Response<ListConversationsResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
+        let (conversations, total_count) = self
+            .usecase
+            .list_conversations(user_id, req.page, req.per_page)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(ListConversationsResponse {
+            conversations: conversations
+                .into_iter()
+                .map(Self::to_proto_summary)
+                .collect(),
+            total_count,
+        }))
+    }
+}
@@ -0,0 +1,132 @@
+use crate::domain::entity::users::Model as User;
+use crate::follow_proto::follow_service_server::FollowService;
+use crate::follow_proto::{
+    FollowRequest, FollowResponse, FollowUser, ListFollowersRequest, ListFollowersResponse,
+    ListFollowingRequest, ListFollowingResponse, UnfollowRequest, UnfollowResponse,
+};
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::usecase::follow_usecase::{FollowError, FollowUseCase};
+use tonic::{Request, Response, Status};
+
+pub struct FollowHandler<U> {
+    usecase: U,
+}
+
+impl<U: FollowUseCase> FollowHandler<U> {
+    pub fn new(usecase: U) -> Self {
+        Self { usecase }
+    }
+
+    // ユーザーエンティティをフォロー一覧用の Proto メッセージに変換するヘルパー関数
+    fn to_proto_user(user: User) -> FollowUser {
+        FollowUser {
+            id: user.id as u64,
+            name: user.name,
+            description: user.description.unwrap_or_default(),
+            followers_count: user.followers_count.max(0) as u32,
+            following_count: user.following_count.max(0) as u32,
+        }
+    }
+
+    // 0 を呼び出し元として扱う ID を解決するヘルパー関数
+    fn user_id_or_caller(user_id: u64, caller: &CurrentUser) -> i32 {
+        if user_id > 0 {
+            user_id as i32
+        } else {
+            caller.user_id
+        }
+    }
+
+    fn to_status(e: FollowError) -> Status {
+        match e {
+            FollowError::SelfFollow => Status::invalid_argument(e.to_string()),
+            FollowError::UserNotFound => Status::not_found("User not found"),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<U: FollowUseCase + Send + Sync + 'static> FollowService for FollowHandler<U> {
+    async fn follow(
+        &self,
+        request: Request<FollowRequest>,
+    ) -> Result<Response<FollowResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        if req.user_id == 0 {
+            return Err(Status::invalid_argument("user_id is required"));
+        }
+
+        let followed = self
+            .usecase
+            .follow(caller.user_id, req.user_id as i32)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(FollowResponse { followed }))
+    }
+
+    async fn unfollow(
+        &self,
+        request: Request<UnfollowRequest>,
+    ) -> Result<Response<UnfollowResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        if req.user_id == 0 {
+            return Err(Status::invalid_argument("user_id is required"));
+        }
+
+        let unfollowed = self
+            .usecase
+            .unfollow(caller.user_id, req.user_id as i32)
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(UnfollowResponse { unfollowed }))
+    }
+
+    async fn list_followers(
+        &self,
+        request: Request<ListFollowersRequest>,
+    ) -> Result<Response<ListFollowersResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let (users, total_count) = self
+            .usecase
+            .list_followers(
+                Self::user_id_or_caller(req.user_id, &caller),
+                req.page,
+                req.per_page,
+            )
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(ListFollowersResponse {
+            users: users.into_iter().map(Self::to_proto_user).collect(),
+            total_count,
+        }))
+    }
+
+    async fn list_following(
+        &self,
+        request: Request<ListFollowingRequest>,
+    ) -> Result<Response<ListFollowingResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let (users, total_count) = self
+            .usecase
+            .list_following(
+                Self::user_id_or_caller(req.user_id, &caller),
+                req.page,
+                req.per_page,
+            )
+            .await
+            .map_err(Self::to_status)?;
+
+        Ok(Response::new(ListFollowingResponse {
+            users: users.into_iter().map(Self::to_proto_user).collect(),
+            total_count,
+        }))
+    }
+}
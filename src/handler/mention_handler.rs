@@ -0,0 +1,59 @@
+use crate::domain::entity::mentions::Model as MentionModel;
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::infra::cursor::{decode_cursor, encode_cursor};
+use crate::mention_proto::mention_service_server::MentionService;
+use crate::mention_proto::{ListMentionsRequest, ListMentionsResponse, Mention};
+use crate::usecase::mention_usecase::MentionUseCase;
+use tonic::{Request, Response, Status};
+
+pub struct MentionHandler<U> {
+    usecase: U,
+}
+
+impl<U: MentionUseCase> MentionHandler<U> {
+    pub fn new(usecase: U) -> Self {
+        Self { usecase }
+    }
+
+    // メンションエンティティを Proto メッセージに変換するヘルパー関数
+    fn to_proto_mention(mention: MentionModel) -> Mention {
+        Mention {
+            id: mention.id as u64,
+            author_id: mention.author_id as u64,
+            post_id: mention.post_id.unwrap_or_default() as u64,
+            message_id: mention.message_id.unwrap_or_default() as u64,
+            created_at: mention.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<U: MentionUseCase + Send + Sync + 'static> MentionService for MentionHandler<U> {
+    async fn list_mentions(
+        &self,
+        request: Request<ListMentionsRequest>,
+    ) -> Result<Response<ListMentionsResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        // 空のページトークンは先頭ページとして扱う
+        let before_id = if req.page_token.is_empty() {
+            None
+        } else {
+            Some(
+                decode_cursor(&req.page_token)
+                    .ok_or_else(|| Status::invalid_argument("invalid page_token"))?,
+            )
+        };
+
+        let (mentions, next_before_id) = self
+            .usecase
+            .list_mentions(caller.user_id, before_id, req.per_page)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListMentionsResponse {
+            mentions: mentions.into_iter().map(Self::to_proto_mention).collect(),
+            next_page_token: next_before_id.map(encode_cursor).unwrap_or_default(),
+        }))
+    }
+}
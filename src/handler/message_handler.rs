@@ -1,20 +1,49 @@
+use crate::domain::entity::message_receipts::Model as MessageReceiptModel;
+use crate::domain::entity::message_revisions::Model as MessageRevisionModel;
+use crate::domain::repository::message::ReactionCount;
+use crate::handler::attachment_handler::to_proto_attachment;
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::infra::message_hub::MessageEvent;
+use crate::message_proto::chat_client_frame::Frame as ClientFrame;
+use crate::message_proto::chat_server_frame::Frame as ServerFrame;
 use crate::message_proto::message_service_server::MessageService;
 use crate::message_proto::{
-    DeleteMessageRequest, DeleteMessageResponse, GetConversationRequest, GetConversationResponse,
-    ListMessagesRequest, ListMessagesResponse, MarkAsReadRequest, MarkAsReadResponse, Message,
-    SendMessageRequest, SendMessageResponse,
+    AddReactionRequest, AddReactionResponse, ChatClientFrame, ChatError, ChatServerFrame,
+    DeleteMessageRequest, DeleteMessageResponse, EditMessageRequest, EditMessageResponse,
+    GetConversationRequest, GetConversationResponse, ListMessageRevisionsRequest,
+    ListMessageRevisionsResponse, ListMessagesRequest, ListMessagesResponse,
+    MarkAsDeliveredRequest, MarkAsDeliveredResponse, MarkAsReadRequest, MarkAsReadResponse,
+    MentionNotice, Message, MessageRevision, PeerTyping, QuotedMessage, Reaction, ReadReceipt,
+    Receipt, RemoveReactionRequest, RemoveReactionResponse, SendMessageRequest,
+    SendMessageResponse, SubscribeMessagesRequest,
 };
-use crate::usecase::message_usecase::MessageUseCase;
+use crate::usecase::message_usecase::{
+    is_valid_reaction, MessageError, MessageUseCase, MessageView,
+};
+use crate::usecase::policy::AccessError;
 use chrono::NaiveDateTime;
-use tonic::{Request, Response, Status};
+use sea_orm::DbErr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+
+/// 返信元の要約に含める本文の最大文字数
+const QUOTE_PREVIEW_CHARS: usize = 100;
 
 pub struct MessageHandler<U> {
-    usecase: U,
+    // チャットセッションのタスクと共有するため Arc で保持する
+    usecase: Arc<U>,
 }
 
 impl<U: MessageUseCase> MessageHandler<U> {
     pub fn new(usecase: U) -> Self {
-        Self { usecase }
+        Self {
+            usecase: Arc::new(usecase),
+        }
     }
 
     // NaiveDateTime を文字列に変換するヘルパー関数
@@ -22,35 +51,285 @@ impl<U: MessageUseCase> MessageHandler<U> {
         dt.format("%Y-%m-%d %H:%M:%S").to_string()
     }
 
+    // 0 を未指定として扱う ID をオプションに変換するヘルパー関数
+    fn optional_id(id: u64) -> Option<i32> {
+        if id > 0 {
+            Some(id as i32)
+        } else {
+            None
+        }
+    }
+
+    fn attachment_ids(ids: Vec<u64>) -> Vec<i32> {
+        ids.into_iter().map(|id| id as i32).collect()
+    }
+
+    fn access_status(e: AccessError) -> Status {
+        match e {
+            AccessError::PermissionDenied => Status::permission_denied(e.to_string()),
+            AccessError::Database(DbErr::RecordNotFound(_)) => {
+                Status::not_found("Message not found")
+            }
+            _ => Status::internal(e.to_string()),
+        }
+    }
+
+    fn to_status(e: DbErr) -> Status {
+        match e {
+            DbErr::RecordNotFound(_) => Status::not_found("Conversation not found"),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+
+    fn message_status(e: MessageError) -> Status {
+        match e {
+            MessageError::InvalidTarget
+            | MessageError::InvalidReplyTo
+            | MessageError::InvalidAttachments
+            | MessageError::TooManyMessageIds => Status::invalid_argument(e.to_string()),
+            MessageError::MessageNotFound => Status::not_found("Message not found"),
+            MessageError::EditWindowExpired => Status::failed_precondition(e.to_string()),
+            MessageError::PermissionDenied => Status::permission_denied(e.to_string()),
+            MessageError::Database(e) => Self::to_status(e),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+
     // メッセージエンティティを Proto メッセージに変換するヘルパー関数
     fn to_proto_message(message: &crate::domain::entity::messages::Model) -> Message {
         Message {
             id: message.id as u64,
             sender_id: message.sender_id as u64,
-            receiver_id: message.receiver_id as u64,
+            receiver_id: message.receiver_id.unwrap_or(0) as u64,
             content: message.content.clone(),
-            is_read: message.is_read,
+            is_read: false,
             created_at: Self::format_datetime(message.created_at),
             updated_at: Self::format_datetime(message.updated_at),
+            conversation_id: message.conversation_id.unwrap_or(0) as u64,
+            reactions: vec![],
+            reply_to_message_id: message.reply_to_message_id.unwrap_or(0) as u64,
+            reply_to: None,
+            receipts: vec![],
+            attachments: vec![],
+            edited_at: message
+                .edited_at
+                .map(Self::format_datetime)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn to_proto_revision(revision: MessageRevisionModel) -> MessageRevision {
+        MessageRevision {
+            id: revision.id as u64,
+            message_id: revision.message_id as u64,
+            content: revision.content,
+            created_at: Self::format_datetime(revision.created_at),
+        }
+    }
+
+    // 返信元のメッセージを要約に変換するヘルパー関数（削除済みの本文は返さない）
+    fn to_quoted_message(original: &crate::domain::entity::messages::Model) -> QuotedMessage {
+        let deleted = original.deleted_at.is_some();
+        QuotedMessage {
+            id: original.id as u64,
+            sender_id: original.sender_id as u64,
+            content: if deleted {
+                String::new()
+            } else {
+                original.content.chars().take(QUOTE_PREVIEW_CHARS).collect()
+            },
+            created_at: Self::format_datetime(original.created_at),
+            deleted,
+        }
+    }
+
+    fn to_proto_receipt(receipt: MessageReceiptModel) -> Receipt {
+        Receipt {
+            user_id: receipt.user_id as u64,
+            delivered_at: receipt
+                .delivered_at
+                .map(Self::format_datetime)
+                .unwrap_or_default(),
+            read_at: receipt
+                .read_at
+                .map(Self::format_datetime)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn to_proto_view(view: MessageView) -> Message {
+        // 1対1のメッセージは受信者の既読の記録から既読かどうかを判定する
+        let is_read = view
+            .receipts
+            .iter()
+            .any(|r| Some(r.user_id) == view.message.receiver_id && r.read_at.is_some());
+        Message {
+            is_read,
+            reactions: Self::to_proto_reactions(view.reactions),
+            reply_to: view.reply_to.as_ref().map(Self::to_quoted_message),
+            receipts: view
+                .receipts
+                .into_iter()
+                .map(Self::to_proto_receipt)
+                .collect(),
+            attachments: view
+                .attachments
+                .into_iter()
+                .map(to_proto_attachment)
+                .collect(),
+            ..Self::to_proto_message(&view.message)
+        }
+    }
+
+    fn to_proto_reactions(reactions: Vec<ReactionCount>) -> Vec<Reaction> {
+        reactions
+            .into_iter()
+            .map(|r| Reaction {
+                emoji: r.emoji,
+                count: r.count,
+                reacted_by_viewer: r.reacted_by_viewer,
+            })
+            .collect()
+    }
+
+    // リアクションの絵文字を検証するヘルパー関数
+    #[allow(clippy::result_large_err)]
+    fn validate_reaction(emoji: String) -> Result<String, Status> {
+        if is_valid_reaction(&emoji) {
+            Ok(emoji)
+        } else {
+            Err(Status::invalid_argument("invalid emoji"))
+        }
+    }
+
+    // ハブのイベントをチャットのサーバーフレームに変換するヘルパー関数
+    fn to_chat_frame(event: MessageEvent) -> ChatServerFrame {
+        let frame = match event {
+            MessageEvent::Received {
+                message,
+                reply_to,
+                attachments,
+            } => ServerFrame::Message(Box::new(Self::to_proto_view(MessageView::new(
+                message,
+                reply_to,
+                attachments,
+            )))),
+            MessageEvent::Typing { user_id, typing } => ServerFrame::PeerTyping(PeerTyping {
+                user_id: user_id as u64,
+                typing,
+            }),
+            MessageEvent::Read {
+                reader_id,
+                message_ids,
+            } => ServerFrame::ReadReceipt(ReadReceipt {
+                reader_id: reader_id as u64,
+                message_ids: message_ids.into_iter().map(|id| id as u64).collect(),
+            }),
+            MessageEvent::Mentioned(mention) => ServerFrame::Mention(MentionNotice {
+                id: mention.id as u64,
+                author_id: mention.author_id as u64,
+                post_id: mention.post_id.unwrap_or_default() as u64,
+                message_id: mention.message_id.unwrap_or_default() as u64,
+                created_at: Self::format_datetime(mention.created_at),
+            }),
+        };
+        ChatServerFrame { frame: Some(frame) }
+    }
+
+    // クライアントのフレームの処理に失敗したことを伝えるエラーフレームを作成する
+    fn to_chat_error(frame_id: u64, status: Status) -> ChatServerFrame {
+        ChatServerFrame {
+            frame: Some(ServerFrame::Error(ChatError {
+                frame_id,
+                code: status.code() as i32,
+                message: status.message().to_string(),
+            })),
+        }
+    }
+
+    // 相手のユーザーIDを検証して変換するヘルパー関数
+    #[allow(clippy::result_large_err)]
+    fn peer_id(peer_id: u64) -> Result<i32, Status> {
+        i32::try_from(peer_id).map_err(|_| Status::invalid_argument("peer_id is out of range"))
+    }
+
+    // チャットセッションで受信したフレームを処理し、送信者へ返すフレームがあれば返す
+    #[allow(clippy::result_large_err)]
+    async fn handle_chat_frame(
+        usecase: &U,
+        user_id: i32,
+        frame: ChatClientFrame,
+    ) -> Result<Option<ChatServerFrame>, Status> {
+        match frame.frame {
+            Some(ClientFrame::SendMessage(req)) => {
+                if req.sender_id != 0 && req.sender_id != user_id as u64 {
+                    return Err(Status::permission_denied(
+                        "sender_id does not match the authenticated user",
+                    ));
+                }
+                let view = usecase
+                    .send_message(
+                        user_id,
+                        Self::optional_id(req.receiver_id),
+                        Self::optional_id(req.conversation_id),
+                        req.content,
+                        Self::optional_id(req.reply_to_message_id),
+                        Self::attachment_ids(req.attachment_ids),
+                    )
+                    .await
+                    .map_err(Self::message_status)?;
+                Ok(Some(ChatServerFrame {
+                    frame: Some(ServerFrame::Message(Box::new(Self::to_proto_view(view)))),
+                }))
+            }
+            Some(ClientFrame::Typing(typing)) => {
+                usecase
+                    .notify_typing(user_id, Self::peer_id(typing.peer_id)?, typing.typing)
+                    .await
+                    .map_err(Self::message_status)?;
+                Ok(None)
+            }
+            Some(ClientFrame::ReadAck(ack)) => {
+                let message_ids = ack.message_ids.iter().map(|&id| id as i32).collect();
+                usecase
+                    .acknowledge_read(user_id, Self::peer_id(ack.peer_id)?, message_ids)
+                    .await
+                    .map_err(Self::to_status)?;
+                Ok(None)
+            }
+            Some(ClientFrame::Join(_)) => Err(Status::invalid_argument("already joined")),
+            None => Err(Status::invalid_argument("frame is required")),
         }
     }
 }
 
 #[tonic::async_trait]
 impl<U: MessageUseCase + Send + Sync + 'static> MessageService for MessageHandler<U> {
+    type SubscribeMessagesStream = Pin<Box<dyn Stream<Item = Result<Message, Status>> + Send>>;
+    type ChatStream = Pin<Box<dyn Stream<Item = Result<ChatServerFrame, Status>> + Send>>;
+
     async fn send_message(
         &self,
         request: Request<SendMessageRequest>,
     ) -> Result<Response<SendMessageResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
         let req = request.into_inner();
-        let message = self
+        let sender_id = caller.resolve(req.sender_id)?;
+        let view = self
             .usecase
-            .send_message(req.sender_id as i32, req.receiver_id as i32, req.content)
+            .send_message(
+                sender_id,
+                Self::optional_id(req.receiver_id),
+                Self::optional_id(req.conversation_id),
+                req.content,
+                Self::optional_id(req.reply_to_message_id),
+                Self::attachment_ids(req.attachment_ids),
+            )
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(Self::message_status)?;
 
         Ok(Response::new(SendMessageResponse {
-            message: Some(Self::to_proto_message(&message)),
+            message: Some(Self::to_proto_view(view)),
         }))
     }
 
@@ -58,14 +337,16 @@ impl<U: MessageUseCase + Send + Sync + 'static> MessageService for MessageHandle
         &self,
         request: Request<ListMessagesRequest>,
     ) -> Result<Response<ListMessagesResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
         let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
         let (messages, total_count, unread_count) = self
             .usecase
-            .list_messages(req.user_id as i32, req.unread_only, req.page, req.per_page)
+            .list_messages(user_id, req.unread_only, req.page, req.per_page)
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
 
-        let proto_messages = messages.iter().map(|m| Self::to_proto_message(m)).collect();
+        let proto_messages = messages.into_iter().map(Self::to_proto_view).collect();
 
         Ok(Response::new(ListMessagesResponse {
             messages: proto_messages,
@@ -78,23 +359,32 @@ impl<U: MessageUseCase + Send + Sync + 'static> MessageService for MessageHandle
         &self,
         request: Request<GetConversationRequest>,
     ) -> Result<Response<GetConversationResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
         let req = request.into_inner();
-        let (messages, total_count) = self
+        let user_id = caller.resolve(req.user_id)?;
+        if req.peer_id == 0 && req.conversation_id == 0 {
+            return Err(Status::invalid_argument(
+                "peer_id or conversation_id is required",
+            ));
+        }
+        let (messages, total_count, unread_count) = self
             .usecase
             .get_conversation(
-                req.user_id as i32,
-                req.peer_id as i32,
+                user_id,
+                Self::optional_id(req.peer_id),
+                Self::optional_id(req.conversation_id),
                 req.page,
                 req.per_page,
             )
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(Self::to_status)?;
 
-        let proto_messages = messages.iter().map(|m| Self::to_proto_message(m)).collect();
+        let proto_messages = messages.into_iter().map(Self::to_proto_view).collect();
 
         Ok(Response::new(GetConversationResponse {
             messages: proto_messages,
             total_count,
+            unread_count,
         }))
     }
 
@@ -102,6 +392,7 @@ impl<U: MessageUseCase + Send + Sync + 'static> MessageService for MessageHandle
         &self,
         request: Request<MarkAsReadRequest>,
     ) -> Result<Response<MarkAsReadResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
         let req = request.into_inner();
 
         // 単一のメッセージIDまたは複数のメッセージIDを処理
@@ -116,27 +407,217 @@ impl<U: MessageUseCase + Send + Sync + 'static> MessageService for MessageHandle
         // from_user_id と to_user_id の処理
         let from_user_id = req.from_user_id.map(|id| id as i32);
         let to_user_id = req.to_user_id.map(|id| id as i32);
+        if message_id.is_none()
+            && req.message_ids.is_empty()
+            && (from_user_id.is_none() || to_user_id.is_none())
+        {
+            return Err(Status::invalid_argument(
+                "message_id, message_ids, or both from_user_id and to_user_id are required",
+            ));
+        }
 
         let updated_count = self
             .usecase
-            .mark_as_read(message_id, message_ids, from_user_id, to_user_id)
+            .mark_as_read(caller, message_id, message_ids, from_user_id, to_user_id)
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(Self::access_status)?;
 
         Ok(Response::new(MarkAsReadResponse { updated_count }))
     }
 
+    async fn mark_as_delivered(
+        &self,
+        request: Request<MarkAsDeliveredRequest>,
+    ) -> Result<Response<MarkAsDeliveredResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let message_ids = req.message_ids.iter().map(|&id| id as i32).collect();
+        let delivered_count = self
+            .usecase
+            .mark_as_delivered(caller.user_id, message_ids, req.limit)
+            .await
+            .map_err(Self::message_status)?;
+
+        Ok(Response::new(MarkAsDeliveredResponse { delivered_count }))
+    }
+
     async fn delete_message(
         &self,
         request: Request<DeleteMessageRequest>,
     ) -> Result<Response<DeleteMessageResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
         let req = request.into_inner();
         let success = self
             .usecase
-            .delete_message(req.message_id as i32)
+            .delete_message(caller, req.message_id as i32)
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(Self::access_status)?;
 
         Ok(Response::new(DeleteMessageResponse { success }))
     }
+
+    async fn edit_message(
+        &self,
+        request: Request<EditMessageRequest>,
+    ) -> Result<Response<EditMessageResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        if req.content.trim().is_empty() {
+            return Err(Status::invalid_argument("content must not be empty"));
+        }
+        let view = self
+            .usecase
+            .edit_message(caller, req.message_id as i32, req.content)
+            .await
+            .map_err(Self::message_status)?;
+
+        Ok(Response::new(EditMessageResponse {
+            message: Some(Self::to_proto_view(view)),
+        }))
+    }
+
+    async fn list_message_revisions(
+        &self,
+        request: Request<ListMessageRevisionsRequest>,
+    ) -> Result<Response<ListMessageRevisionsResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let revisions = self
+            .usecase
+            .list_message_revisions(caller.user_id, req.message_id as i32)
+            .await
+            .map_err(Self::access_status)?;
+
+        Ok(Response::new(ListMessageRevisionsResponse {
+            revisions: revisions.into_iter().map(Self::to_proto_revision).collect(),
+        }))
+    }
+
+    async fn add_reaction(
+        &self,
+        request: Request<AddReactionRequest>,
+    ) -> Result<Response<AddReactionResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let emoji = Self::validate_reaction(req.emoji)?;
+        let reactions = self
+            .usecase
+            .add_reaction(caller.user_id, req.message_id as i32, emoji)
+            .await
+            .map_err(Self::access_status)?;
+
+        Ok(Response::new(AddReactionResponse {
+            reactions: Self::to_proto_reactions(reactions),
+        }))
+    }
+
+    async fn remove_reaction(
+        &self,
+        request: Request<RemoveReactionRequest>,
+    ) -> Result<Response<RemoveReactionResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let emoji = Self::validate_reaction(req.emoji)?;
+        let reactions = self
+            .usecase
+            .remove_reaction(caller.user_id, req.message_id as i32, emoji)
+            .await
+            .map_err(Self::access_status)?;
+
+        Ok(Response::new(RemoveReactionResponse {
+            reactions: Self::to_proto_reactions(reactions),
+        }))
+    }
+
+    async fn subscribe_messages(
+        &self,
+        request: Request<SubscribeMessagesRequest>,
+    ) -> Result<Response<Self::SubscribeMessagesStream>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let subscription = self.usecase.subscribe(caller.resolve(req.user_id)?);
+
+        // 受信が追いつかず取りこぼしたメッセージは読み飛ばして配信を継続する
+        let stream = subscription
+            .into_stream()
+            .filter_map(|result| match result {
+                Ok(MessageEvent::Received {
+                    message,
+                    reply_to,
+                    attachments,
+                }) => Some(Ok(Self::to_proto_view(MessageView::new(
+                    message,
+                    reply_to,
+                    attachments,
+                )))),
+                Ok(_) => None,
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                    tracing::warn!("subscriber lagged, skipped {} events", skipped);
+                    None
+                }
+            });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn chat(
+        &self,
+        request: Request<Streaming<ChatClientFrame>>,
+    ) -> Result<Response<Self::ChatStream>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let mut inbound = request.into_inner();
+
+        // 最初のフレームでセッションのユーザーを確定する
+        let user_id = match inbound.message().await? {
+            Some(ChatClientFrame {
+                frame: Some(ClientFrame::Join(join)),
+                ..
+            }) => caller.resolve(join.user_id)?,
+            _ => return Err(Status::invalid_argument("first frame must be join")),
+        };
+
+        let subscription = self.usecase.subscribe(user_id);
+        let events = subscription
+            .into_stream()
+            .filter_map(|result| match result {
+                Ok(event) => Some(Ok(Self::to_chat_frame(event))),
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                    tracing::warn!("chat session lagged, skipped {} events", skipped);
+                    None
+                }
+            });
+
+        // クライアントからのフレームは別タスクで処理し、応答はチャネル経由で返す
+        let (tx, rx) = mpsc::channel(32);
+        let (done_tx, done_rx) = oneshot::channel::<()>();
+        let usecase = Arc::clone(&self.usecase);
+        tokio::spawn(async move {
+            while let Some(frame) = inbound.next().await {
+                // 通信エラーの場合のみセッションを終了する
+                let frame = match frame {
+                    Ok(frame) => frame,
+                    Err(status) => {
+                        let _ = tx.send(Err(status)).await;
+                        break;
+                    }
+                };
+                let frame_id = frame.frame_id;
+                let reply = match Self::handle_chat_frame(&usecase, user_id, frame).await {
+                    Ok(Some(reply)) => reply,
+                    Ok(None) => continue,
+                    // 処理に失敗したフレームはエラーフレームで知らせ、後続のフレームの処理を続ける
+                    Err(status) => Self::to_chat_error(frame_id, status),
+                };
+                if tx.send(Ok(reply)).await.is_err() {
+                    break;
+                }
+            }
+            let _ = done_tx.send(());
+        });
+
+        // クライアントが送信を終えたらイベントの配信も打ち切り、残りの応答を返してからストリームを閉じる
+        let events = futures::StreamExt::take_until(events, done_rx);
+        let stream = ReceiverStream::new(rx).merge(events);
+        Ok(Response::new(Box::pin(stream)))
+    }
 }
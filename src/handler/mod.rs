@@ -1,3 +1,11 @@
+pub mod admin_handler;
+pub mod attachment_handler;
+pub mod auth_handler;
+pub mod auth_interceptor;
+pub mod comment_handler;
+pub mod conversation_handler;
+pub mod follow_handler;
+pub mod mention_handler;
 pub mod message_handler;
 pub mod post_handler;
 pub mod user_handler;
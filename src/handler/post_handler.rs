@@ -1,9 +1,24 @@
+use crate::domain::entity::post_revisions::Model as PostRevisionModel;
+use crate::domain::entity::sea_orm_active_enums::RepostKind as RepostKindModel;
+use crate::domain::entity::users::Model as UserModel;
+use crate::domain::repository::post::HashtagCount;
+use crate::handler::attachment_handler::to_proto_attachment;
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::infra::cursor::{decode_cursor, encode_cursor};
+use crate::infra::hashtag::normalize_hashtag;
 use crate::post_proto::post_service_server::PostService;
 use crate::post_proto::{
-    CreatePostRequest, CreatePostResponse, DeletePostRequest, DeletePostResponse, GetPostRequest,
-    GetPostResponse, ListPostsRequest, ListPostsResponse, Post,
+    CreatePostRequest, CreatePostResponse, DeletePostRequest, DeletePostResponse,
+    GetHomeTimelineRequest, GetHomeTimelineResponse, GetPostRequest, GetPostResponse,
+    LikePostRequest, LikePostResponse, ListPostLikersRequest, ListPostLikersResponse,
+    ListPostRevisionsRequest, ListPostRevisionsResponse, ListPostsByHashtagRequest,
+    ListPostsByHashtagResponse, ListPostsRequest, ListPostsResponse, Post, PostLiker, PostRevision,
+    RepostKind, RepostRequest, RepostResponse, TrendingHashtag, TrendingHashtagsRequest,
+    TrendingHashtagsResponse, UnlikePostRequest, UnlikePostResponse, UpdatePostRequest,
+    UpdatePostResponse,
 };
-use crate::usecase::post_usecase::PostUseCase;
+use crate::usecase::policy::AccessError;
+use crate::usecase::post_usecase::{PostError, PostUseCase, PostView};
 use sea_orm::DbErr;
 use tonic::{Request, Response, Status};
 
@@ -15,6 +30,95 @@ impl<U: PostUseCase> PostHandler<U> {
     pub fn new(usecase: U) -> Self {
         Self { usecase }
     }
+
+    fn to_proto_post(view: PostView) -> Post {
+        let original_deleted = view.original_deleted();
+        let post = view.post;
+        Post {
+            id: post.id as u64,
+            body: post.body,
+            user_id: post.user_id as u64,
+            created_at: post.created_at,
+            like_count: post.like_count.max(0) as u32,
+            liked_by_viewer: view.liked_by_viewer,
+            comment_count: post.comment_count.max(0) as u32,
+            repost_of_id: post.repost_of_id.unwrap_or_default() as u64,
+            repost_kind: Self::to_proto_repost_kind(post.repost_kind).into(),
+            original: view
+                .original
+                .map(|original| Box::new(Self::to_proto_post(*original))),
+            original_deleted,
+            attachments: view
+                .attachments
+                .into_iter()
+                .map(to_proto_attachment)
+                .collect(),
+        }
+    }
+
+    fn to_proto_repost_kind(kind: Option<RepostKindModel>) -> RepostKind {
+        match kind {
+            None => RepostKind::Unspecified,
+            Some(RepostKindModel::Repost) => RepostKind::Repost,
+            Some(RepostKindModel::Quote) => RepostKind::Quote,
+        }
+    }
+
+    fn to_proto_liker(user: UserModel) -> PostLiker {
+        PostLiker {
+            id: user.id as u64,
+            name: user.name,
+        }
+    }
+
+    fn to_proto_hashtag(hashtag: HashtagCount) -> TrendingHashtag {
+        TrendingHashtag {
+            name: hashtag.name,
+            post_count: hashtag.post_count.max(0) as u64,
+        }
+    }
+
+    fn to_proto_revision(revision: PostRevisionModel) -> PostRevision {
+        PostRevision {
+            id: revision.id as u64,
+            post_id: revision.post_id as u64,
+            body: revision.body,
+            created_at: revision.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+
+    // 空のページトークンは先頭ページとして扱う
+    #[allow(clippy::result_large_err)]
+    fn decode_page_token(page_token: &str) -> Result<Option<i32>, Status> {
+        if page_token.is_empty() {
+            return Ok(None);
+        }
+        decode_cursor(page_token)
+            .map(Some)
+            .ok_or_else(|| Status::invalid_argument("invalid page_token"))
+    }
+
+    fn db_status(e: DbErr) -> Status {
+        match e {
+            DbErr::RecordNotFound(_) => Status::not_found("Post not found"),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+
+    fn access_status(e: AccessError) -> Status {
+        match e {
+            AccessError::PermissionDenied => Status::permission_denied(e.to_string()),
+            AccessError::Database(e) => Self::db_status(e),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+
+    fn post_status(e: PostError) -> Status {
+        match e {
+            PostError::InvalidAttachments => Status::invalid_argument(e.to_string()),
+            PostError::Database(e) => Status::internal(e.to_string()),
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -23,20 +127,40 @@ impl<U: PostUseCase + Send + Sync + 'static> PostService for PostHandler<U> {
         &self,
         request: Request<CreatePostRequest>,
     ) -> Result<Response<CreatePostResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
         let req = request.into_inner();
+        let user_id = caller.resolve(req.user_id)?;
         let post = self
             .usecase
-            .create_post(req.body, req.user_id as i32)
+            .create_post(
+                req.body,
+                user_id,
+                req.attachment_ids.into_iter().map(|id| id as i32).collect(),
+            )
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(Self::post_status)?;
 
         Ok(Response::new(CreatePostResponse {
-            post: Some(Post {
-                id: post.id as u64,
-                body: post.body,
-                user_id: post.user_id as u64,
-                created_at: post.created_at,
-            }),
+            post: Some(Self::to_proto_post(post)),
+        }))
+    }
+
+    async fn repost(
+        &self,
+        request: Request<RepostRequest>,
+    ) -> Result<Response<RepostResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        // 空白のみの本文は単純なリポストとして扱う
+        let body = req.body.trim().to_string();
+        let post = self
+            .usecase
+            .repost(caller.user_id, req.post_id as i32, body)
+            .await
+            .map_err(Self::db_status)?;
+
+        Ok(Response::new(RepostResponse {
+            post: Some(Self::to_proto_post(post)),
         }))
     }
 
@@ -44,48 +168,130 @@ impl<U: PostUseCase + Send + Sync + 'static> PostService for PostHandler<U> {
         &self,
         request: Request<ListPostsRequest>,
     ) -> Result<Response<ListPostsResponse>, Status> {
-        let _req = request.into_inner();
-        let posts = self
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let before_id = Self::decode_page_token(&req.page_token)?;
+        let user_id = (req.user_id > 0).then_some(req.user_id as i32);
+        let (posts, next_before_id) = self
+            .usecase
+            .list_posts(caller.user_id, user_id, before_id, req.per_page)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListPostsResponse {
+            posts: posts.into_iter().map(Self::to_proto_post).collect(),
+            next_page_token: next_before_id.map(encode_cursor).unwrap_or_default(),
+        }))
+    }
+
+    async fn get_home_timeline(
+        &self,
+        request: Request<GetHomeTimelineRequest>,
+    ) -> Result<Response<GetHomeTimelineResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let before_id = Self::decode_page_token(&req.page_token)?;
+        let (posts, next_before_id) = self
             .usecase
-            .list_posts()
+            .home_timeline(caller.user_id, before_id, req.per_page)
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
 
-        let posts = posts
-            .into_iter()
-            .map(|p| Post {
-                id: p.id as u64,
-                body: p.body,
-                user_id: p.user_id as u64,
-                created_at: p.created_at,
-            })
-            .collect();
+        Ok(Response::new(GetHomeTimelineResponse {
+            posts: posts.into_iter().map(Self::to_proto_post).collect(),
+            next_page_token: next_before_id.map(encode_cursor).unwrap_or_default(),
+        }))
+    }
+
+    async fn list_posts_by_hashtag(
+        &self,
+        request: Request<ListPostsByHashtagRequest>,
+    ) -> Result<Response<ListPostsByHashtagResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let tag = normalize_hashtag(req.hashtag.trim())
+            .ok_or_else(|| Status::invalid_argument("invalid hashtag"))?;
+        let before_id = Self::decode_page_token(&req.page_token)?;
+        let (posts, next_before_id) = self
+            .usecase
+            .list_posts_by_hashtag(caller.user_id, &tag, before_id, req.per_page)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListPostsByHashtagResponse {
+            posts: posts.into_iter().map(Self::to_proto_post).collect(),
+            next_page_token: next_before_id.map(encode_cursor).unwrap_or_default(),
+        }))
+    }
+
+    async fn trending_hashtags(
+        &self,
+        request: Request<TrendingHashtagsRequest>,
+    ) -> Result<Response<TrendingHashtagsResponse>, Status> {
+        let req = request.into_inner();
+        let hashtags = self
+            .usecase
+            .trending_hashtags(req.window_hours, req.limit)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
 
-        Ok(Response::new(ListPostsResponse { posts }))
+        Ok(Response::new(TrendingHashtagsResponse {
+            hashtags: hashtags.into_iter().map(Self::to_proto_hashtag).collect(),
+        }))
     }
 
     async fn get_post(
         &self,
         request: Request<GetPostRequest>,
     ) -> Result<Response<GetPostResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
         let req = request.into_inner();
         let post = self
             .usecase
-            .get_post(req.id as i32)
+            .get_post(caller.user_id, req.id as i32)
             .await
-            .map_err(|e| match e {
-                DbErr::RecordNotFound(_) => Status::not_found("Post not found"),
-                _ => Status::internal(e.to_string()),
-            })?
+            .map_err(Self::db_status)?
             .ok_or_else(|| Status::not_found("Post not found"))?;
 
         Ok(Response::new(GetPostResponse {
-            post: Some(Post {
-                id: post.id as u64,
-                body: post.body,
-                user_id: post.user_id as u64,
-                created_at: post.created_at,
-            }),
+            post: Some(Self::to_proto_post(post)),
+        }))
+    }
+
+    async fn update_post(
+        &self,
+        request: Request<UpdatePostRequest>,
+    ) -> Result<Response<UpdatePostResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
+        let req = request.into_inner();
+        if req.body.trim().is_empty() {
+            return Err(Status::invalid_argument("body must not be empty"));
+        }
+
+        let post = self
+            .usecase
+            .update_post(caller, req.id as i32, req.body)
+            .await
+            .map_err(Self::access_status)?;
+
+        Ok(Response::new(UpdatePostResponse {
+            post: Some(Self::to_proto_post(post)),
+        }))
+    }
+
+    async fn list_post_revisions(
+        &self,
+        request: Request<ListPostRevisionsRequest>,
+    ) -> Result<Response<ListPostRevisionsResponse>, Status> {
+        let req = request.into_inner();
+        let revisions = self
+            .usecase
+            .list_post_revisions(req.post_id as i32)
+            .await
+            .map_err(Self::db_status)?;
+
+        Ok(Response::new(ListPostRevisionsResponse {
+            revisions: revisions.into_iter().map(Self::to_proto_revision).collect(),
         }))
     }
 
@@ -93,16 +299,65 @@ impl<U: PostUseCase + Send + Sync + 'static> PostService for PostHandler<U> {
         &self,
         request: Request<DeletePostRequest>,
     ) -> Result<Response<DeletePostResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
         let req = request.into_inner();
 
         self.usecase
-            .delete_post(req.id as i32)
+            .delete_post(caller, req.id as i32)
             .await
-            .map_err(|e| match e {
-                DbErr::RecordNotFound(_) => Status::not_found("Post not found"),
-                _ => Status::internal(e.to_string()),
-            })?;
+            .map_err(Self::access_status)?;
 
         Ok(Response::new(DeletePostResponse { success: true }))
     }
+
+    async fn like_post(
+        &self,
+        request: Request<LikePostRequest>,
+    ) -> Result<Response<LikePostResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let post = self
+            .usecase
+            .like_post(caller.user_id, req.post_id as i32)
+            .await
+            .map_err(Self::db_status)?;
+
+        Ok(Response::new(LikePostResponse {
+            post: Some(Self::to_proto_post(post)),
+        }))
+    }
+
+    async fn unlike_post(
+        &self,
+        request: Request<UnlikePostRequest>,
+    ) -> Result<Response<UnlikePostResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?;
+        let req = request.into_inner();
+        let post = self
+            .usecase
+            .unlike_post(caller.user_id, req.post_id as i32)
+            .await
+            .map_err(Self::db_status)?;
+
+        Ok(Response::new(UnlikePostResponse {
+            post: Some(Self::to_proto_post(post)),
+        }))
+    }
+
+    async fn list_post_likers(
+        &self,
+        request: Request<ListPostLikersRequest>,
+    ) -> Result<Response<ListPostLikersResponse>, Status> {
+        let req = request.into_inner();
+        let (users, total_count) = self
+            .usecase
+            .list_post_likers(req.post_id as i32, req.page, req.per_page)
+            .await
+            .map_err(Self::db_status)?;
+
+        Ok(Response::new(ListPostLikersResponse {
+            users: users.into_iter().map(Self::to_proto_liker).collect(),
+            total_count,
+        }))
+    }
 }
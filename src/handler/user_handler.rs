@@ -1,9 +1,15 @@
-use crate::usecase::user_usecase::UserUseCase;
+use crate::domain::entity::users::Model as UserModel;
+use crate::domain::repository::user::{is_unique_violation, UserFilter};
+use crate::handler::auth_interceptor::CurrentUser;
+use crate::infra::mention::normalize_handle;
+use crate::usecase::auth_usecase::MIN_PASSWORD_LENGTH;
+use crate::usecase::policy::AccessError;
+use crate::usecase::user_usecase::{UserError, UserUseCase};
 use crate::user_proto::user_service_server::UserService;
 use crate::user_proto::{
-    CreateUserRequest, CreateUserResponse, DeleteUserRequest, DeleteUserResponse, GetUserRequest,
-    GetUserResponse, ListUsersRequest, ListUsersResponse, UpdateUserRequest, UpdateUserResponse,
-    User,
+    CreateUserRequest, CreateUserResponse, DeleteUserRequest, DeleteUserResponse,
+    GetUserByHandleRequest, GetUserByHandleResponse, GetUserRequest, GetUserResponse,
+    ListUsersRequest, ListUsersResponse, UpdateUserRequest, UpdateUserResponse, User,
 };
 use tonic::{Request, Response, Status};
 
@@ -15,6 +21,53 @@ impl<U: UserUseCase> UserHandler<U> {
     pub fn new(usecase: U) -> Self {
         Self { usecase }
     }
+
+    // ユーザーエンティティを Proto メッセージに変換するヘルパー関数
+    fn to_proto_user(user: UserModel) -> User {
+        User {
+            id: user.id as u64,
+            name: Some(user.name),
+            email: Some(user.email),
+            gender: user.gender,
+            address: user.address,
+            age: user.age.unwrap_or(0) as u32,
+            description: user.description,
+            followers_count: user.followers_count.max(0) as u32,
+            following_count: user.following_count.max(0) as u32,
+            handle: user.handle,
+        }
+    }
+
+    fn sqlx_status(e: sqlx::Error) -> Status {
+        match e {
+            sqlx::Error::RowNotFound => Status::not_found("User not found"),
+            e if is_unique_violation(&e) => {
+                Status::already_exists("email or handle is already in use")
+            }
+            _ => Status::internal(e.to_string()),
+        }
+    }
+
+    fn user_status(e: UserError) -> Status {
+        match e {
+            UserError::Database(e) => Self::sqlx_status(e),
+            UserError::Hash(_) => Status::internal(e.to_string()),
+        }
+    }
+
+    fn access_status(e: AccessError) -> Status {
+        match e {
+            AccessError::PermissionDenied => Status::permission_denied(e.to_string()),
+            AccessError::Sqlx(e) => Self::sqlx_status(e),
+            _ => Status::internal(e.to_string()),
+        }
+    }
+
+    // 前後の空白を除いてハンドルを正規化するヘルパー関数
+    #[allow(clippy::result_large_err)]
+    fn parse_handle(handle: &str) -> Result<String, Status> {
+        normalize_handle(handle.trim()).ok_or_else(|| Status::invalid_argument("invalid handle"))
+    }
 }
 
 #[tonic::async_trait]
@@ -24,6 +77,15 @@ impl<U: UserUseCase + Send + Sync + 'static> UserService for UserHandler<U> {
         request: Request<CreateUserRequest>,
     ) -> Result<Response<CreateUserResponse>, Status> {
         let req = request.into_inner();
+        if let Some(password) = &req.password {
+            if password.chars().count() < MIN_PASSWORD_LENGTH {
+                return Err(Status::invalid_argument(format!(
+                    "password must be at least {} characters",
+                    MIN_PASSWORD_LENGTH
+                )));
+            }
+        }
+
         let user = self
             .usecase
             .create_user(
@@ -33,20 +95,13 @@ impl<U: UserUseCase + Send + Sync + 'static> UserService for UserHandler<U> {
                 Some(req.age as i32),
                 req.gender,
                 req.address,
+                req.password,
             )
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(Self::user_status)?;
 
         Ok(Response::new(CreateUserResponse {
-            user: Some(User {
-                id: user.id as u64,
-                name: Some(user.name),
-                email: Some(user.email),
-                age: user.age.unwrap_or(0) as u32,
-                address: user.address,
-                description: user.description,
-                gender: user.gender,
-            }),
+            user: Some(Self::to_proto_user(user)),
         }))
     }
 
@@ -54,27 +109,23 @@ impl<U: UserUseCase + Send + Sync + 'static> UserService for UserHandler<U> {
         &self,
         request: Request<ListUsersRequest>,
     ) -> Result<Response<ListUsersResponse>, Status> {
-        let _req = request.into_inner();
-        let users = self
+        let req = request.into_inner();
+        let (users, total_count) = self
             .usecase
-            .list_users()
+            .list_users(UserFilter {
+                page: req.page,
+                per_page: req.per_page,
+                name: req.name,
+                address: req.address,
+                gender: req.gender,
+                include_deleted: false,
+            })
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
 
-        let users = users
-            .into_iter()
-            .map(|u| User {
-                id: u.id as u64,
-                name: Some(u.name),
-                email: Some(u.email),
-                gender: u.gender,
-                address: u.address,
-                age: u.age.unwrap_or(0) as u32,
-                description: u.description,
-            })
-            .collect();
+        let users = users.into_iter().map(Self::to_proto_user).collect();
 
-        Ok(Response::new(ListUsersResponse { users }))
+        Ok(Response::new(ListUsersResponse { users, total_count }))
     }
 
     async fn get_user(
@@ -86,21 +137,27 @@ impl<U: UserUseCase + Send + Sync + 'static> UserService for UserHandler<U> {
             .usecase
             .get_user(req.id as i32)
             .await
-            .map_err(|e| match e {
-                sqlx::Error::RowNotFound => Status::not_found("User not found"),
-                _ => Status::internal(e.to_string()),
-            })?;
+            .map_err(Self::sqlx_status)?;
 
         Ok(Response::new(GetUserResponse {
-            user: Some(User {
-                id: user.id as u64,
-                name: Some(user.name),
-                email: Some(user.email),
-                gender: user.gender,
-                address: user.address,
-                age: user.age.unwrap_or(0) as u32,
-                description: user.description,
-            }),
+            user: Some(Self::to_proto_user(user)),
+        }))
+    }
+
+    async fn get_user_by_handle(
+        &self,
+        request: Request<GetUserByHandleRequest>,
+    ) -> Result<Response<GetUserByHandleResponse>, Status> {
+        let req = request.into_inner();
+        let handle = Self::parse_handle(&req.handle)?;
+        let user = self
+            .usecase
+            .get_user_by_handle(&handle)
+            .await
+            .map_err(Self::sqlx_status)?;
+
+        Ok(Response::new(GetUserByHandleResponse {
+            user: Some(Self::to_proto_user(user)),
         }))
     }
 
@@ -108,12 +165,15 @@ impl<U: UserUseCase + Send + Sync + 'static> UserService for UserHandler<U> {
         &self,
         request: Request<UpdateUserRequest>,
     ) -> Result<Response<UpdateUserResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
         let req = request.into_inner();
+        let handle = req.handle.as_deref().map(Self::parse_handle).transpose()?;
 
         // UpdateUserRequest の各フィールドを usecase の update_user に渡す
         let user = self
             .usecase
             .update_user(
+                caller,
                 req.id as i32,
                 req.name,
                 req.email,
@@ -121,23 +181,13 @@ impl<U: UserUseCase + Send + Sync + 'static> UserService for UserHandler<U> {
                 req.age.map(|a| a as i32),
                 req.gender,
                 req.address,
+                handle,
             )
             .await
-            .map_err(|e| match e {
-                sqlx::Error::RowNotFound => Status::not_found("User not found"),
-                _ => Status::internal(e.to_string()),
-            })?;
+            .map_err(Self::access_status)?;
 
         Ok(Response::new(UpdateUserResponse {
-            user: Some(User {
-                id: user.id as u64,
-                name: Some(user.name),
-                email: Some(user.email),
-                gender: user.gender,
-                address: user.address,
-                age: user.age.unwrap_or(0) as u32,
-                description: user.description,
-            }),
+            user: Some(Self::to_proto_user(user)),
         }))
     }
 
@@ -145,15 +195,13 @@ impl<U: UserUseCase + Send + Sync + 'static> UserService for UserHandler<U> {
         &self,
         request: Request<DeleteUserRequest>,
     ) -> Result<Response<DeleteUserResponse>, Status> {
+        let caller = CurrentUser::from_request(&request)?.caller();
         let req = request.into_inner();
 
         self.usecase
-            .delete_user(req.id as i32)
+            .delete_user(caller, req.id as i32)
             .await
-            .map_err(|e| match e {
-                sqlx::Error::RowNotFound => Status::not_found("User not found"),
-                _ => Status::internal(e.to_string()),
-            })?;
+            .map_err(Self::access_status)?;
 
         Ok(Response::new(DeleteUserResponse { success: true }))
     }
@@ -0,0 +1,38 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+/// カーソルの形式を変更した場合に古いトークンを弾くためのプレフィックス
+const CURSOR_PREFIX: &str = "v1:";
+
+/// キーセットページネーションの位置（直前のページ末尾の ID）を
+/// クライアントから中身が見えないページトークンに変換します。
+pub fn encode_cursor(last_id: i32) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}{}", CURSOR_PREFIX, last_id))
+}
+
+/// ページトークンを復元します。形式が不正な場合は `None` を返します。
+pub fn decode_cursor(token: &str) -> Option<i32> {
+    let bytes = URL_SAFE_NO_PAD.decode(token).ok()?;
+    let decoded = String::from_utf8(bytes).ok()?;
+    decoded.strip_prefix(CURSOR_PREFIX)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let token = encode_cursor(42);
+        assert_ne!(token, "42");
+        assert_eq!(decode_cursor(&token), Some(42));
+    }
+
+    #[test]
+    fn test_decode_invalid_cursor() {
+        assert_eq!(decode_cursor(""), None);
+        assert_eq!(decode_cursor("not a cursor"), None);
+        assert_eq!(decode_cursor(&URL_SAFE_NO_PAD.encode("42")), None);
+        assert_eq!(decode_cursor(&URL_SAFE_NO_PAD.encode("v1:abc")), None);
+    }
+}
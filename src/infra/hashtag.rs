@@ -0,0 +1,106 @@
+/// ハッシュタグとして扱う最大文字数（これを超えるものはタグとして扱わない）
+pub const MAX_HASHTAG_CHARS: usize = 100;
+
+// 英数字（漢字・かな等を含む）とアンダースコアをタグの一部とみなす
+fn is_hashtag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '＿'
+}
+
+// 日本語の文中では直前に空白を置かずにタグを書くため、英数字が直前にある場合のみ区切りとみなさない
+fn continues_word(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn is_hash_mark(c: char) -> bool {
+    c == '#' || c == '＃'
+}
+
+// 全角の英数字・記号を半角にそろえる
+fn fold_width(c: char) -> char {
+    if ('\u{FF01}'..='\u{FF5E}').contains(&c) {
+        char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+/// タグ名を正規化します。先頭の `#` を取り除き、全角英数字を半角に、英字を小文字にそろえます。
+///
+/// タグとして使えない文字列（空、数字のみ、長すぎる等）の場合は `None` を返します。
+pub fn normalize_hashtag(tag: &str) -> Option<String> {
+    let tag = tag.strip_prefix(is_hash_mark).unwrap_or(tag);
+    if tag.is_empty() || !tag.chars().all(is_hashtag_char) {
+        return None;
+    }
+    let normalized: String = tag
+        .chars()
+        .map(fold_width)
+        .flat_map(char::to_lowercase)
+        .collect();
+    if normalized.chars().count() > MAX_HASHTAG_CHARS || normalized.chars().all(char::is_numeric) {
+        return None;
+    }
+    Some(normalized)
+}
+
+/// 本文から `#タグ` を出現順に重複なく取り出し、正規化して返します。
+///
+/// `C#` や `page#section` のように直前が半角英数字の場合はタグとみなしません。
+pub fn extract_hashtags(body: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_hash_mark(c) || prev.is_some_and(continues_word) {
+            prev = Some(c);
+            continue;
+        }
+
+        let mut tag = String::new();
+        while let Some(&next) = chars.peek() {
+            if !is_hashtag_char(next) {
+                break;
+            }
+            tag.push(next);
+            chars.next();
+        }
+        prev = tag.chars().last().or(Some(c));
+        if let Some(tag) = normalize_hashtag(&tag) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_hashtags() {
+        assert_eq!(
+            extract_hashtags("Hello #Rust and #rust_lang! #RUST"),
+            vec!["rust", "rust_lang"]
+        );
+        assert_eq!(
+            extract_hashtags("今日は#東京タワー に行った＃夏休み。#ラーメン好き"),
+            vec!["東京タワー", "夏休み", "ラーメン好き"]
+        );
+        // 全角英数字は半角にそろえる
+        assert_eq!(extract_hashtags("＃ＲＵＳＴ"), vec!["rust"]);
+        assert!(extract_hashtags("C# と abc#def と #123 と # のみ").is_empty());
+        assert_eq!(extract_hashtags("##tag"), vec!["tag"]);
+    }
+
+    #[test]
+    fn test_normalize_hashtag() {
+        assert_eq!(normalize_hashtag("#Rust"), Some("rust".to_string()));
+        assert_eq!(normalize_hashtag("夏休み"), Some("夏休み".to_string()));
+        assert_eq!(normalize_hashtag(""), None);
+        assert_eq!(normalize_hashtag("#"), None);
+        assert_eq!(normalize_hashtag("two words"), None);
+        assert_eq!(normalize_hashtag(&"a".repeat(MAX_HASHTAG_CHARS + 1)), None);
+    }
+}
@@ -0,0 +1,89 @@
+/// ハンドルとして扱う最大文字数
+pub const MAX_HANDLE_CHARS: usize = 30;
+
+// ハンドルに使える文字は半角英数字とアンダースコアのみ
+fn is_handle_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+// メールアドレス（user@example.com）の @ をメンションとみなさないため、直前が英数字・`_`・`.` の場合は区切りとみなさない
+fn continues_word(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+fn is_at_mark(c: char) -> bool {
+    c == '@' || c == '＠'
+}
+
+/// ハンドルを正規化します。先頭の `@` を取り除き、英字を小文字にそろえます。
+///
+/// ハンドルとして使えない文字列（空、使用できない文字を含む、長すぎる）の場合は `None` を返します。
+pub fn normalize_handle(handle: &str) -> Option<String> {
+    let handle = handle.strip_prefix(is_at_mark).unwrap_or(handle);
+    if handle.is_empty()
+        || handle.chars().count() > MAX_HANDLE_CHARS
+        || !handle.chars().all(is_handle_char)
+    {
+        return None;
+    }
+    Some(handle.to_ascii_lowercase())
+}
+
+/// 本文から `@ハンドル` を出現順に重複なく取り出し、正規化して返します。
+///
+/// `user@example.com` のように直前が英数字の場合や、最大文字数を超える場合はメンションとみなしません。
+pub fn extract_mentions(body: &str) -> Vec<String> {
+    let mut handles: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_at_mark(c) || prev.is_some_and(continues_word) {
+            prev = Some(c);
+            continue;
+        }
+
+        let mut handle = String::new();
+        while let Some(&next) = chars.peek() {
+            if !is_handle_char(next) {
+                break;
+            }
+            handle.push(next);
+            chars.next();
+        }
+        prev = handle.chars().last().or(Some(c));
+        if let Some(handle) = normalize_handle(&handle) {
+            if !handles.contains(&handle) {
+                handles.push(handle);
+            }
+        }
+    }
+    handles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_mentions() {
+        assert_eq!(
+            extract_mentions("@Alice と @bob_2 へ。@ALICE もう一度"),
+            vec!["alice", "bob_2"]
+        );
+        assert_eq!(extract_mentions("こんにちは＠carolさん"), vec!["carol"]);
+        assert_eq!(extract_mentions("(@dave) @@eve"), vec!["dave", "eve"]);
+        assert!(extract_mentions("mail: user@example.com, a.b@c と @ のみ").is_empty());
+        assert!(extract_mentions(&format!("@{}", "a".repeat(MAX_HANDLE_CHARS + 1))).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_handle() {
+        assert_eq!(normalize_handle("@Alice_01"), Some("alice_01".to_string()));
+        assert_eq!(normalize_handle("bob"), Some("bob".to_string()));
+        assert_eq!(normalize_handle(""), None);
+        assert_eq!(normalize_handle("@"), None);
+        assert_eq!(normalize_handle("太郎"), None);
+        assert_eq!(normalize_handle("two words"), None);
+        assert_eq!(normalize_handle(&"a".repeat(MAX_HANDLE_CHARS + 1)), None);
+    }
+}
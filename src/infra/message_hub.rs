@@ -0,0 +1,202 @@
+use crate::domain::entity::attachments::Model as Attachment;
+use crate::domain::entity::mentions::Model as Mention;
+use crate::domain::entity::messages::Model as Message;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::Stream;
+
+/// ハブで配信されるイベント
+#[derive(Clone, Debug)]
+pub enum MessageEvent {
+    /// 新着メッセージ。返信の場合は返信元（論理削除済みの場合を含む）を添える
+    Received {
+        message: Message,
+        reply_to: Option<Message>,
+        attachments: Vec<Attachment>,
+    },
+    /// 相手の入力状態の変化
+    Typing { user_id: i32, typing: bool },
+    /// 相手による既読
+    Read {
+        reader_id: i32,
+        message_ids: Vec<i32>,
+    },
+    /// 投稿・メッセージでのメンション
+    Mentioned(Mention),
+}
+
+/// 接続中のクライアントへ新着メッセージやメンションなどのイベントを配信するインプロセスのハブ
+///
+/// ユーザーIDごとに broadcast チャネルを持ち、同一ユーザーの複数接続（複数端末）へ同じイベントを配信します。
+pub struct MessageHub {
+    channels: Arc<Mutex<HashMap<i32, broadcast::Sender<MessageEvent>>>>,
+    capacity: usize,
+}
+
+impl MessageHub {
+    /// `capacity` は購読者ごとに保持できる未配信イベントの上限です。
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            channels: Arc::new(Mutex::new(HashMap::new())),
+            capacity,
+        }
+    }
+
+    /// 指定ユーザー宛てのイベントを購読します。
+    pub fn subscribe(&self, user_id: i32) -> Subscription {
+        let mut channels = self.channels.lock().unwrap();
+        let receiver = channels
+            .entry(user_id)
+            .or_insert_with(|| broadcast::channel(self.capacity).0)
+            .subscribe();
+        Subscription {
+            user_id,
+            receiver,
+            channels: Arc::clone(&self.channels),
+        }
+    }
+
+    /// 指定ユーザーの購読者へイベントを配信します。
+    ///
+    /// 配信できた購読者数を返します。
+    pub fn publish(&self, user_id: i32, event: MessageEvent) -> usize {
+        let channels = self.channels.lock().unwrap();
+        channels
+            .get(&user_id)
+            .and_then(|sender| sender.send(event).ok())
+            .unwrap_or(0)
+    }
+}
+
+/// ハブの購読
+///
+/// 破棄された時点でそのユーザーの購読者がいなくなった場合は、ユーザーのチャネルもハブから破棄します。
+pub struct Subscription {
+    user_id: i32,
+    receiver: broadcast::Receiver<MessageEvent>,
+    channels: Arc<Mutex<HashMap<i32, broadcast::Sender<MessageEvent>>>>,
+}
+
+impl Subscription {
+    /// 受信したイベントを順に返すストリームに変換します。
+    /// 受信が追いつかず取りこぼした場合は、取りこぼした件数を `Lagged` で返して受信を継続します。
+    pub fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<MessageEvent, BroadcastStreamRecvError>> + Send {
+        futures::stream::unfold(self, |mut subscription| async move {
+            let item = match subscription.recv().await {
+                Ok(event) => Ok(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    Err(BroadcastStreamRecvError::Lagged(skipped))
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            };
+            Some((item, subscription))
+        })
+    }
+}
+
+impl Deref for Subscription {
+    type Target = broadcast::Receiver<MessageEvent>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.receiver
+    }
+}
+
+impl DerefMut for Subscription {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.receiver
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // ロック中は新たな購読が増えないため、残る購読者が自分だけならチャネルごと破棄する
+        let mut channels = self.channels.lock().unwrap();
+        if channels
+            .get(&self.user_id)
+            .is_some_and(|sender| sender.receiver_count() <= 1)
+        {
+            channels.remove(&self.user_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn received(id: i32, sender_id: i32, receiver_id: i32) -> MessageEvent {
+        let now = chrono::Utc::now().naive_utc();
+        MessageEvent::Received {
+            message: Message {
+                id,
+                sender_id,
+                receiver_id: Some(receiver_id),
+                content: format!("message {}", id),
+                created_at: now,
+                updated_at: now,
+                deleted_at: None,
+                conversation_id: None,
+                reply_to_message_id: None,
+                edited_at: None,
+            },
+            reply_to: None,
+            attachments: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_to_all_subscribers_of_user() {
+        let hub = MessageHub::new(16);
+        let mut first = hub.subscribe(2);
+        let mut second = hub.subscribe(2);
+        let mut other = hub.subscribe(3);
+
+        let delivered = hub.publish(2, received(1, 1, 2));
+        assert_eq!(delivered, 2);
+
+        for receiver in [&mut first, &mut second] {
+            match receiver.recv().await.unwrap() {
+                MessageEvent::Received { message, .. } => assert_eq!(message.id, 1),
+                event => panic!("unexpected event: {:?}", event),
+            }
+        }
+        assert!(other.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_publish_without_subscribers() {
+        let hub = MessageHub::new(16);
+        assert_eq!(hub.publish(2, received(1, 1, 2)), 0);
+
+        // 購読者が切断した後のチャネルは破棄される
+        drop(hub.subscribe(2));
+        let typing = MessageEvent::Typing {
+            user_id: 1,
+            typing: true,
+        };
+        assert_eq!(hub.publish(2, typing), 0);
+        assert!(hub.channels.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_channel_is_removed_with_last_subscription() {
+        let hub = MessageHub::new(16);
+        let first = hub.subscribe(2);
+        let second = hub.subscribe(2);
+
+        // 他の購読が残っている間はチャネルを残す
+        drop(first);
+        assert!(hub.channels.lock().unwrap().contains_key(&2));
+
+        // イベントを一度も受信せずに切断した場合も破棄される
+        let stream = second.into_stream();
+        drop(stream);
+        assert!(hub.channels.lock().unwrap().is_empty());
+    }
+}
@@ -1 +1,9 @@
+pub mod blob_store;
 pub mod client;
+pub mod cursor;
+pub mod hashtag;
+pub mod mention;
+pub mod message_hub;
+pub mod pagination;
+pub mod password;
+pub mod token;
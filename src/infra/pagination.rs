@@ -0,0 +1,49 @@
+/// per_page が指定されなかった場合の 1 ページあたりの件数
+pub const DEFAULT_PER_PAGE: i32 = 20;
+/// 1 ページあたりの最大件数
+pub const MAX_PER_PAGE: i32 = 100;
+
+/// 1 ページあたりの件数を正規化します。
+/// 未指定（0 以下）の場合は `default`、`max` を超える場合は `max` に丸めます。
+pub fn clamp_per_page(per_page: i32, default: i32, max: i32) -> i32 {
+    if per_page > 0 {
+        per_page.min(max)
+    } else {
+        default
+    }
+}
+
+/// ページ番号と 1 ページあたりの件数を正規化します。
+/// 負のページ番号は先頭ページとし、件数は `clamp_per_page` と同じく丸めます。
+pub fn normalize_page(page: i32, per_page: i32, default: i32, max: i32) -> (i32, i32) {
+    (page.max(0), clamp_per_page(per_page, default, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_per_page() {
+        assert_eq!(clamp_per_page(10, DEFAULT_PER_PAGE, MAX_PER_PAGE), 10);
+        assert_eq!(
+            clamp_per_page(0, DEFAULT_PER_PAGE, MAX_PER_PAGE),
+            DEFAULT_PER_PAGE
+        );
+        assert_eq!(
+            clamp_per_page(-5, DEFAULT_PER_PAGE, MAX_PER_PAGE),
+            DEFAULT_PER_PAGE
+        );
+        assert_eq!(
+            clamp_per_page(1_000_000, DEFAULT_PER_PAGE, MAX_PER_PAGE),
+            MAX_PER_PAGE
+        );
+    }
+
+    #[test]
+    fn test_normalize_page() {
+        assert_eq!(normalize_page(2, 10, 20, 100), (2, 10));
+        assert_eq!(normalize_page(-1, -5, 20, 100), (0, 20));
+        assert_eq!(normalize_page(0, 1000, 20, 100), (0, 100));
+    }
+}
@@ -0,0 +1,55 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use std::sync::OnceLock;
+
+/// パスワードを Argon2id でハッシュ化し、PHC 文字列形式で返します。
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)?
+        .to_string())
+}
+
+/// パスワードがハッシュと一致するかを検証します。
+/// ハッシュの形式が不正な場合も一致しないものとして扱います。
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// ユーザーが存在しない場合やパスワードが未設定の場合に、照合の代わりに検証するハッシュを返します。
+/// 実際の照合と同じだけ時間をかけることで、応答時間から登録済みのメールアドレスを推測されないようにします。
+pub fn dummy_password_hash() -> &'static str {
+    static HASH: OnceLock<String> = OnceLock::new();
+    HASH.get_or_init(|| hash_password("dummy password").expect("Failed to hash dummy password"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hash = hash_password("correct horse").expect("Failed to hash password");
+        assert_ne!(hash, "correct horse");
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("wrong horse", &hash));
+    }
+
+    #[test]
+    fn test_dummy_password_hash() {
+        assert!(dummy_password_hash().starts_with("$argon2id$"));
+        assert!(!verify_password("password", dummy_password_hash()));
+    }
+
+    #[test]
+    fn test_verify_invalid_hash() {
+        assert!(!verify_password("password", "not a hash"));
+    }
+}
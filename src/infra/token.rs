@@ -0,0 +1,155 @@
+use crate::domain::entity::sea_orm_active_enums::UserRole;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// アクセストークンの既定の有効期間（秒）
+const DEFAULT_ACCESS_TOKEN_TTL_SECONDS: i64 = 15 * 60;
+/// リフレッシュトークンの既定の有効期間（日）
+const DEFAULT_REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+/// アクセストークンに含めるクレーム
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Claims {
+    /// ユーザーID
+    pub sub: String,
+    /// 発行時点のユーザーのロール
+    pub role: UserRole,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl Claims {
+    pub fn user_id(&self) -> Option<i32> {
+        self.sub.parse().ok()
+    }
+}
+
+/// アクセストークン（JWT）の発行・検証とリフレッシュトークンの生成を行います。
+pub struct TokenManager {
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
+}
+
+impl TokenManager {
+    pub fn new(secret: &[u8], access_token_ttl: Duration, refresh_token_ttl: Duration) -> Self {
+        Self {
+            encoding_key: EncodingKey::from_secret(secret),
+            decoding_key: DecodingKey::from_secret(secret),
+            access_token_ttl,
+            refresh_token_ttl,
+        }
+    }
+
+    /// 環境変数から設定を読み込みます。
+    /// - `JWT_SECRET`: 署名用の秘密鍵（必須）
+    /// - `ACCESS_TOKEN_TTL_SECONDS`: アクセストークンの有効期間（既定 900 秒）
+    /// - `REFRESH_TOKEN_TTL_DAYS`: リフレッシュトークンの有効期間（既定 30 日）
+    pub fn from_env() -> Self {
+        let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+        let access_ttl = std::env::var("ACCESS_TOKEN_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_ACCESS_TOKEN_TTL_SECONDS);
+        let refresh_ttl = std::env::var("REFRESH_TOKEN_TTL_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_REFRESH_TOKEN_TTL_DAYS);
+        Self::new(
+            secret.as_bytes(),
+            Duration::seconds(access_ttl),
+            Duration::days(refresh_ttl),
+        )
+    }
+
+    pub fn access_token_ttl(&self) -> Duration {
+        self.access_token_ttl
+    }
+
+    pub fn refresh_token_ttl(&self) -> Duration {
+        self.refresh_token_ttl
+    }
+
+    /// ユーザーのアクセストークンを発行します。
+    pub fn issue_access_token(
+        &self,
+        user_id: i32,
+        role: UserRole,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let now = Utc::now();
+        let claims = Claims {
+            sub: user_id.to_string(),
+            role,
+            iat: now.timestamp(),
+            exp: (now + self.access_token_ttl).timestamp(),
+        };
+        encode(&Header::default(), &claims, &self.encoding_key)
+    }
+
+    /// アクセストークンの署名と有効期限を検証し、クレームを返します。
+    pub fn verify_access_token(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+        let mut validation = Validation::default();
+        validation.leeway = 0;
+        decode::<Claims>(token, &self.decoding_key, &validation).map(|data| data.claims)
+    }
+}
+
+/// 推測不可能なリフレッシュトークンを生成します。
+pub fn generate_refresh_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// 保存用にリフレッシュトークンの SHA-256 ハッシュ（16進数）を計算します。
+pub fn hash_refresh_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> TokenManager {
+        TokenManager::new(b"test-secret", Duration::minutes(15), Duration::days(30))
+    }
+
+    #[test]
+    fn test_issue_and_verify_access_token() {
+        let manager = manager();
+        let token = manager
+            .issue_access_token(42, UserRole::Moderator)
+            .expect("Failed to issue");
+        let claims = manager
+            .verify_access_token(&token)
+            .expect("Failed to verify");
+        assert_eq!(claims.user_id(), Some(42));
+        assert_eq!(claims.role, UserRole::Moderator);
+
+        let other = TokenManager::new(b"other-secret", Duration::minutes(15), Duration::days(30));
+        assert!(other.verify_access_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_expired_access_token_is_rejected() {
+        let manager = TokenManager::new(b"test-secret", Duration::seconds(-1), Duration::days(30));
+        let token = manager
+            .issue_access_token(42, UserRole::User)
+            .expect("Failed to issue");
+        assert!(manager.verify_access_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_refresh_token_hash() {
+        let token = generate_refresh_token();
+        assert_ne!(token, generate_refresh_token());
+        assert_eq!(hash_refresh_token(&token), hash_refresh_token(&token));
+        assert_eq!(hash_refresh_token(&token).len(), 64);
+    }
+}
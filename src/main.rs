@@ -4,16 +4,41 @@ mod infra;
 mod repository;
 mod usecase;
 
+use crate::handler::admin_handler::AdminHandler;
+use crate::handler::attachment_handler::AttachmentHandler;
+use crate::handler::auth_handler::AuthHandler;
+use crate::handler::auth_interceptor::AuthInterceptor;
+use crate::handler::comment_handler::CommentHandler;
+use crate::handler::conversation_handler::ConversationHandler;
+use crate::handler::follow_handler::FollowHandler;
+use crate::handler::mention_handler::MentionHandler;
 use crate::handler::message_handler::MessageHandler;
 use crate::handler::post_handler::PostHandler;
 use crate::handler::user_handler::UserHandler;
+use crate::infra::blob_store::blob_store_from_env;
+use crate::infra::message_hub::MessageHub;
+use crate::infra::token::TokenManager;
+use crate::repository::attachment_repository::PgAttachmentRepository;
+use crate::repository::comment_repository::PgCommentRepository;
+use crate::repository::conversation_repository::PgConversationRepository;
+use crate::repository::follow_repository::PgFollowRepository;
+use crate::repository::mention_repository::PgMentionRepository;
 use crate::repository::message_repository::PgMessageRepository;
 use crate::repository::post_repository::PgPostRepository;
+use crate::repository::refresh_token_repository::PgRefreshTokenRepository;
 use crate::repository::user_repository::PgUserRepository;
-use crate::usecase::message_usecase::MessageUseCaseImpl;
-use crate::usecase::post_usecase::PostUseCaseImpl;
+use crate::usecase::admin_usecase::AdminUseCaseImpl;
+use crate::usecase::attachment_usecase::{AttachmentUseCaseImpl, DEFAULT_MAX_ATTACHMENT_BYTES};
+use crate::usecase::auth_usecase::AuthUseCaseImpl;
+use crate::usecase::comment_usecase::CommentUseCaseImpl;
+use crate::usecase::conversation_usecase::ConversationUseCaseImpl;
+use crate::usecase::follow_usecase::FollowUseCaseImpl;
+use crate::usecase::mention_usecase::{MentionNotifier, MentionUseCaseImpl};
+use crate::usecase::message_usecase::{MessageUseCaseImpl, DEFAULT_MESSAGE_EDIT_WINDOW_SECONDS};
+use crate::usecase::post_usecase::{PostUseCaseImpl, DEFAULT_FAN_OUT_MAX_FOLLOWERS};
 use crate::usecase::user_usecase::UserUseCaseImpl;
 use dotenv::dotenv;
+use std::sync::Arc;
 use tonic::transport::Server;
 
 mod user_proto {
@@ -28,6 +53,34 @@ mod message_proto {
     tonic::include_proto!("message");
 }
 
+mod conversation_proto {
+    tonic::include_proto!("conversation");
+}
+
+mod auth_proto {
+    tonic::include_proto!("auth");
+}
+
+mod admin_proto {
+    tonic::include_proto!("admin");
+}
+
+mod follow_proto {
+    tonic::include_proto!("follow");
+}
+
+mod comment_proto {
+    tonic::include_proto!("comment");
+}
+
+mod mention_proto {
+    tonic::include_proto!("mention");
+}
+
+mod attachment_proto {
+    tonic::include_proto!("attachment");
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
@@ -43,28 +96,152 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let user_usecase = UserUseCaseImpl::new(user_repository);
     let user_handler = UserHandler::new(user_usecase);
 
+    // 新着メッセージやメンションをストリーム購読者へ配信するハブ
+    let message_hub = Arc::new(MessageHub::new(256));
+
     let post_repository = PgPostRepository::new(pool.clone());
-    let post_usecase = PostUseCaseImpl::new(post_repository);
+    // フォロワー数が TIMELINE_FAN_OUT_MAX_FOLLOWERS 以下の投稿者は書き込み時にタイムラインへ配信する
+    let fan_out_max_followers = std::env::var("TIMELINE_FAN_OUT_MAX_FOLLOWERS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_FAN_OUT_MAX_FOLLOWERS);
+    let post_usecase = PostUseCaseImpl::new(
+        post_repository,
+        MentionNotifier::new(
+            PgMentionRepository::new(pool.clone()),
+            Arc::clone(&message_hub),
+        ),
+        fan_out_max_followers,
+    );
     let post_handler = PostHandler::new(post_usecase);
 
     let message_repository = PgMessageRepository::new(pool.clone());
-    let message_usecase = MessageUseCaseImpl::new(message_repository);
+    // 送信後 MESSAGE_EDIT_WINDOW_SECONDS 秒まで送信者はメッセージを編集できる
+    let message_edit_window = std::env::var("MESSAGE_EDIT_WINDOW_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MESSAGE_EDIT_WINDOW_SECONDS);
+    let message_usecase = MessageUseCaseImpl::new(
+        message_repository,
+        PgConversationRepository::new(pool.clone()),
+        MentionNotifier::new(
+            PgMentionRepository::new(pool.clone()),
+            Arc::clone(&message_hub),
+        ),
+        message_hub,
+        chrono::Duration::seconds(message_edit_window),
+    );
     let message_handler = MessageHandler::new(message_usecase);
 
+    let conversation_repository = PgConversationRepository::new(pool.clone());
+    let conversation_usecase = ConversationUseCaseImpl::new(conversation_repository);
+    let conversation_handler = ConversationHandler::new(conversation_usecase);
+
+    let follow_usecase = FollowUseCaseImpl::new(
+        PgFollowRepository::new(pool.clone()),
+        PgUserRepository::new(pool.clone()),
+    );
+    let follow_handler = FollowHandler::new(follow_usecase);
+
+    let comment_usecase = CommentUseCaseImpl::new(PgCommentRepository::new(pool.clone()));
+    let comment_handler = CommentHandler::new(comment_usecase);
+
+    // 添付ファイルの本体は ATTACHMENT_STORE（local または s3）の BlobStore に保存する
+    let max_attachment_bytes = std::env::var("MAX_ATTACHMENT_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_ATTACHMENT_BYTES);
+    let attachment_usecase = AttachmentUseCaseImpl::new(
+        PgAttachmentRepository::new(pool.clone()),
+        blob_store_from_env()?,
+        max_attachment_bytes,
+    );
+    let attachment_handler = AttachmentHandler::new(attachment_usecase);
+
+    let mention_usecase = MentionUseCaseImpl::new(PgMentionRepository::new(pool.clone()));
+    let mention_handler = MentionHandler::new(mention_usecase);
+
+    // JWT_SECRET 環境変数からアクセストークンの署名鍵を読み込む
+    let token_manager = Arc::new(TokenManager::from_env());
+    let admin_usecase = AdminUseCaseImpl::new(
+        PgUserRepository::new(pool.clone()),
+        PgPostRepository::new(pool.clone()),
+        PgMessageRepository::new(pool.clone()),
+    );
+    let admin_handler = AdminHandler::new(admin_usecase);
+
+    let auth_usecase = AuthUseCaseImpl::new(
+        PgUserRepository::new(pool.clone()),
+        PgRefreshTokenRepository::new(pool.clone()),
+        Arc::clone(&token_manager),
+    );
+    let auth_handler = AuthHandler::new(auth_usecase);
+
+    // AuthService 以外はアクセストークンで呼び出し元を認証する
+    let auth_interceptor = AuthInterceptor::new(token_manager);
+
     let addr = "[::1]:50051".parse()?;
     println!("Server listening on {}", addr);
 
     // Tonic サーバーにハンドラを登録して起動
     Server::builder()
-        .add_service(user_proto::user_service_server::UserServiceServer::new(
-            user_handler,
-        ))
-        .add_service(post_proto::post_service_server::PostServiceServer::new(
-            post_handler,
-        ))
         .add_service(
-            message_proto::message_service_server::MessageServiceServer::new(message_handler),
+            user_proto::user_service_server::UserServiceServer::with_interceptor(
+                user_handler,
+                auth_interceptor.clone(),
+            ),
+        )
+        .add_service(
+            post_proto::post_service_server::PostServiceServer::with_interceptor(
+                post_handler,
+                auth_interceptor.clone(),
+            ),
         )
+        .add_service(
+            message_proto::message_service_server::MessageServiceServer::with_interceptor(
+                message_handler,
+                auth_interceptor.clone(),
+            ),
+        )
+        .add_service(
+            conversation_proto::conversation_service_server::ConversationServiceServer::with_interceptor(
+                conversation_handler,
+                auth_interceptor.clone(),
+            ),
+        )
+        .add_service(
+            follow_proto::follow_service_server::FollowServiceServer::with_interceptor(
+                follow_handler,
+                auth_interceptor.clone(),
+            ),
+        )
+        .add_service(
+            comment_proto::comment_service_server::CommentServiceServer::with_interceptor(
+                comment_handler,
+                auth_interceptor.clone(),
+            ),
+        )
+        .add_service(
+            mention_proto::mention_service_server::MentionServiceServer::with_interceptor(
+                mention_handler,
+                auth_interceptor.clone(),
+            ),
+        )
+        .add_service(
+            attachment_proto::attachment_service_server::AttachmentServiceServer::with_interceptor(
+                attachment_handler,
+                auth_interceptor.clone(),
+            ),
+        )
+        .add_service(
+            admin_proto::admin_service_server::AdminServiceServer::with_interceptor(
+                admin_handler,
+                auth_interceptor,
+            ),
+        )
+        .add_service(auth_proto::auth_service_server::AuthServiceServer::new(
+            auth_handler,
+        ))
         .serve(addr)
         .await?;
 
@@ -0,0 +1,316 @@
+use crate::domain::entity::attachments::{
+    self, Column, Entity as Attachments, Model as Attachment,
+};
+use crate::domain::entity::{conversation_members, messages};
+use crate::domain::repository::attachment::{
+    AttachmentRepository, AttachmentTarget, NewAttachment,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Query;
+use sea_orm::{Condition, DatabaseConnection, DbErr, NotSet, QueryOrder, Set};
+use std::collections::BTreeSet;
+
+pub struct PgAttachmentRepository {
+    db: DatabaseConnection,
+}
+
+impl PgAttachmentRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+/// `uploader_id` のユーザーがアップロードし、まだどこにも紐付いていない添付ファイルを `target` に紐付けます。
+///
+/// 該当しない ID が含まれる場合は何も紐付けずに `RecordNotUpdated` を返すため、
+/// メッセージ・投稿の作成と同じトランザクションで呼び出します。
+pub(crate) async fn link_attachments<C: ConnectionTrait>(
+    db: &C,
+    uploader_id: i32,
+    target: AttachmentTarget,
+    attachment_ids: &[i32],
+) -> Result<(), DbErr> {
+    let ids: BTreeSet<i32> = attachment_ids.iter().copied().collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let (message_id, post_id) = match target {
+        AttachmentTarget::Message(id) => (Some(id), None),
+        AttachmentTarget::Post(id) => (None, Some(id)),
+    };
+    let result = Attachments::update_many()
+        .col_expr(Column::MessageId, Expr::value(message_id))
+        .col_expr(Column::PostId, Expr::value(post_id))
+        .filter(Column::Id.is_in(ids.iter().copied()))
+        .filter(Column::UploaderId.eq(uploader_id))
+        .filter(Column::MessageId.is_null())
+        .filter(Column::PostId.is_null())
+        .exec(db)
+        .await?;
+    if result.rows_affected != ids.len() as u64 {
+        return Err(DbErr::RecordNotUpdated);
+    }
+    Ok(())
+}
+
+/// 指定メッセージの添付ファイルをアップロード順に取得します。
+pub(crate) async fn find_by_message_ids<C: ConnectionTrait>(
+    db: &C,
+    message_ids: &[i32],
+) -> Result<Vec<Attachment>, DbErr> {
+    if message_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    Attachments::find()
+        .filter(Column::MessageId.is_in(message_ids.iter().copied()))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await
+}
+
+/// 指定投稿の添付ファイルをアップロード順に取得します。
+pub(crate) async fn find_by_post_ids<C: ConnectionTrait>(
+    db: &C,
+    post_ids: &[i32],
+) -> Result<Vec<Attachment>, DbErr> {
+    if post_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    Attachments::find()
+        .filter(Column::PostId.is_in(post_ids.iter().copied()))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await
+}
+
+#[async_trait]
+impl AttachmentRepository for PgAttachmentRepository {
+    async fn insert(&self, attachment: NewAttachment) -> Result<Attachment, DbErr> {
+        attachments::ActiveModel {
+            id: NotSet,
+            uploader_id: Set(attachment.uploader_id),
+            storage_key: Set(attachment.storage_key),
+            filename: Set(attachment.filename),
+            mime_type: Set(attachment.mime_type),
+            size_bytes: Set(attachment.size_bytes),
+            checksum: Set(attachment.checksum),
+            message_id: Set(None),
+            post_id: Set(None),
+            created_at: Set(Utc::now().naive_utc()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    async fn find_visible(&self, user_id: i32, id: i32) -> Result<Option<Attachment>, DbErr> {
+        let visible_message_ids = Query::select()
+            .column((messages::Entity, messages::Column::Id))
+            .from(messages::Entity)
+            .and_where(messages::Column::DeletedAt.is_null())
+            .cond_where(
+                Condition::any()
+                    .add(messages::Column::SenderId.eq(user_id))
+                    .add(messages::Column::ReceiverId.eq(user_id))
+                    .add(
+                        messages::Column::ConversationId.in_subquery(
+                            Query::select()
+                                .column(conversation_members::Column::ConversationId)
+                                .from(conversation_members::Entity)
+                                .and_where(conversation_members::Column::UserId.eq(user_id))
+                                .to_owned(),
+                        ),
+                    ),
+            )
+            .to_owned();
+        Attachments::find_by_id(id)
+            .filter(
+                Condition::any()
+                    .add(Column::UploaderId.eq(user_id))
+                    .add(Column::PostId.is_not_null())
+                    .add(Column::MessageId.in_subquery(visible_message_ids)),
+            )
+            .one(&self.db)
+            .await
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 記録した添付ファイルを保持します。紐付け先は扱わず、アップロードした本人のみ閲覧できます。
+    pub struct MockAttachmentRepository {
+        pub attachments: Mutex<Vec<Attachment>>,
+    }
+
+    impl MockAttachmentRepository {
+        pub fn new() -> Self {
+            Self {
+                attachments: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AttachmentRepository for MockAttachmentRepository {
+        async fn insert(&self, attachment: NewAttachment) -> Result<Attachment, DbErr> {
+            let mut attachments = self.attachments.lock().unwrap();
+            let inserted = Attachment {
+                id: attachments.len() as i32 + 1,
+                uploader_id: attachment.uploader_id,
+                storage_key: attachment.storage_key,
+                filename: attachment.filename,
+                mime_type: attachment.mime_type,
+                size_bytes: attachment.size_bytes,
+                checksum: attachment.checksum,
+                message_id: None,
+                post_id: None,
+                created_at: Utc::now().naive_utc(),
+            };
+            attachments.push(inserted.clone());
+            Ok(inserted)
+        }
+
+        async fn find_visible(&self, user_id: i32, id: i32) -> Result<Option<Attachment>, DbErr> {
+            let attachments = self.attachments.lock().unwrap();
+            Ok(attachments
+                .iter()
+                .find(|a| a.id == id && a.uploader_id == user_id)
+                .cloned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repository::message::MessageRepository;
+    use crate::domain::repository::post::PostRepository;
+    use crate::repository::message_repository::PgMessageRepository;
+    use crate::repository::post_repository::PgPostRepository;
+    use crate::repository::test_support::insert_user;
+    use dotenv::dotenv;
+    use sea_orm::Database;
+    use std::env;
+
+    async fn setup_test_db() -> DatabaseConnection {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        Database::connect(&database_url)
+            .await
+            .expect("Failed to connect to database")
+    }
+
+    async fn upload(repo: &PgAttachmentRepository, uploader_id: i32) -> Attachment {
+        repo.insert(NewAttachment {
+            uploader_id,
+            storage_key: "2c/f24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+                .to_string(),
+            filename: "hello.txt".to_string(),
+            mime_type: "text/plain".to_string(),
+            size_bytes: 5,
+            checksum: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+                .to_string(),
+        })
+        .await
+        .expect("Insert attachment failed")
+    }
+
+    #[tokio::test]
+    async fn test_attachments_linked_to_message_and_post() {
+        let db = setup_test_db().await;
+        let sender_id = insert_user(&db).await;
+        let receiver_id = insert_user(&db).await;
+        let stranger_id = insert_user(&db).await;
+        let repo = PgAttachmentRepository::new(db.clone());
+        let messages = PgMessageRepository::new(db.clone());
+        let posts = PgPostRepository::new(db.clone());
+
+        let first = upload(&repo, sender_id).await;
+        let second = upload(&repo, sender_id).await;
+        // 紐付け前はアップロードした本人のみ閲覧できる
+        assert!(repo
+            .find_visible(sender_id, first.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repo
+            .find_visible(receiver_id, first.id)
+            .await
+            .unwrap()
+            .is_none());
+
+        // 他人のアップロードは添付できず、メッセージも作成されない
+        let others = upload(&repo, receiver_id).await;
+        let result = messages
+            .send_message(
+                sender_id,
+                Some(receiver_id),
+                None,
+                "with attachment".to_string(),
+                None,
+                &[first.id, others.id],
+            )
+            .await;
+        assert!(matches!(result, Err(DbErr::RecordNotUpdated)));
+        let (_, total_count, _) = messages
+            .get_conversation(sender_id, Some(receiver_id), None, 0, 10)
+            .await
+            .expect("Get conversation failed");
+        assert_eq!(total_count, 0);
+
+        let message = messages
+            .send_message(
+                sender_id,
+                Some(receiver_id),
+                None,
+                "with attachment".to_string(),
+                None,
+                &[first.id],
+            )
+            .await
+            .expect("Send message failed");
+        let found = messages
+            .find_attachments(&[message.id])
+            .await
+            .expect("Find attachments failed");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].message_id, Some(message.id));
+        assert!(repo
+            .find_visible(receiver_id, first.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repo
+            .find_visible(stranger_id, first.id)
+            .await
+            .unwrap()
+            .is_none());
+
+        // 紐付け済みの添付ファイルは別の投稿に使えない
+        let result = posts
+            .insert("with attachment".to_string(), sender_id, &[first.id], &[])
+            .await;
+        assert!(matches!(result, Err(DbErr::RecordNotUpdated)));
+
+        let post = posts
+            .insert("with attachment".to_string(), sender_id, &[second.id], &[])
+            .await
+            .expect("Insert post failed");
+        let found = posts
+            .find_attachments(&[post.id])
+            .await
+            .expect("Find attachments failed");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].post_id, Some(post.id));
+        // 投稿の添付ファイルは誰でも閲覧できる
+        assert!(repo
+            .find_visible(stranger_id, second.id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
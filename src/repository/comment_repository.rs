@@ -0,0 +1,454 @@
+use crate::domain::entity::comments::{self, Column, Entity as Comments, Model as Comment};
+use crate::domain::entity::post;
+use crate::domain::repository::comment::{CommentRepository, NewComment};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Alias, Expr, Query};
+use sea_orm::{
+    DatabaseConnection, DbErr, NotSet, PaginatorTrait, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+use std::collections::HashMap;
+
+pub struct PgCommentRepository {
+    db: DatabaseConnection,
+}
+
+impl PgCommentRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+fn comment_not_found(id: i32) -> DbErr {
+    DbErr::RecordNotFound(format!("Comment with id {} not found", id))
+}
+
+// コメント数の非正規化カラムを増減する
+async fn adjust_comment_count<C: ConnectionTrait>(
+    db: &C,
+    post_id: i32,
+    delta: i32,
+) -> Result<(), DbErr> {
+    post::Entity::update_many()
+        .col_expr(
+            post::Column::CommentCount,
+            Expr::col(post::Column::CommentCount).add(delta),
+        )
+        .filter(post::Column::Id.eq(post_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl CommentRepository for PgCommentRepository {
+    async fn post_exists(&self, post_id: i32) -> Result<bool, DbErr> {
+        let count = post::Entity::find_by_id(post_id).count(&self.db).await?;
+        Ok(count > 0)
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<Option<Comment>, DbErr> {
+        Comments::find_by_id(id).one(&self.db).await
+    }
+
+    async fn insert(&self, comment: NewComment) -> Result<Comment, DbErr> {
+        let now = Utc::now().naive_utc();
+        let txn = self.db.begin().await?;
+        let inserted = comments::ActiveModel {
+            id: NotSet,
+            post_id: Set(comment.post_id),
+            user_id: Set(comment.user_id),
+            parent_comment_id: Set(comment.parent_comment_id),
+            root_comment_id: Set(comment.root_comment_id),
+            body: Set(comment.body),
+            created_at: Set(now),
+            updated_at: Set(now),
+            deleted_at: NotSet,
+        }
+        .insert(&txn)
+        .await?;
+        adjust_comment_count(&txn, inserted.post_id, 1).await?;
+        txn.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn find_threads(
+        &self,
+        post_id: i32,
+        after_id: Option<i32>,
+        limit: u64,
+    ) -> Result<Vec<Comment>, DbErr> {
+        let mut query = Comments::find()
+            .filter(Column::PostId.eq(post_id))
+            .filter(Column::RootCommentId.is_null())
+            .order_by_asc(Column::Id)
+            .limit(limit);
+        if let Some(after_id) = after_id {
+            query = query.filter(Column::Id.gt(after_id));
+        }
+        query.all(&self.db).await
+    }
+
+    async fn find_replies(
+        &self,
+        root_ids: &[i32],
+        limit_per_thread: u64,
+    ) -> Result<Vec<Comment>, DbErr> {
+        if root_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        // スレッドごとに古い順の連番を振り、先頭の limit_per_thread 件だけを取り出す
+        let ranked = Query::select()
+            .column(Column::Id)
+            .expr_as(
+                Expr::cust("ROW_NUMBER() OVER (PARTITION BY root_comment_id ORDER BY id)"),
+                Alias::new("rn"),
+            )
+            .from(Comments)
+            .and_where(Column::RootCommentId.is_in(root_ids.iter().copied()))
+            .to_owned();
+        let first_ids = Query::select()
+            .column(Alias::new("id"))
+            .from_subquery(ranked, Alias::new("ranked"))
+            .and_where(Expr::col(Alias::new("rn")).lte(limit_per_thread as i64))
+            .to_owned();
+        Comments::find()
+            .filter(Column::Id.in_subquery(first_ids))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_replies(&self, root_ids: &[i32]) -> Result<HashMap<i32, i64>, DbErr> {
+        if root_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let counts: Vec<(i32, i64)> = Comments::find()
+            .select_only()
+            .column(Column::RootCommentId)
+            .column_as(Expr::col(Column::Id).count(), "reply_count")
+            .filter(Column::RootCommentId.is_in(root_ids.iter().copied()))
+            .group_by(Column::RootCommentId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        Ok(counts.into_iter().collect())
+    }
+
+    async fn find_thread_replies(
+        &self,
+        root_id: i32,
+        after_id: Option<i32>,
+        limit: u64,
+    ) -> Result<Vec<Comment>, DbErr> {
+        let mut query = Comments::find()
+            .filter(Column::RootCommentId.eq(root_id))
+            .order_by_asc(Column::Id)
+            .limit(limit);
+        if let Some(after_id) = after_id {
+            query = query.filter(Column::Id.gt(after_id));
+        }
+        query.all(&self.db).await
+    }
+
+    async fn update_body(&self, id: i32, body: String) -> Result<Comment, DbErr> {
+        let existing = Comments::find_by_id(id)
+            .filter(Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| comment_not_found(id))?;
+
+        let mut active: comments::ActiveModel = existing.into();
+        active.body = Set(body);
+        active.updated_at = Set(Utc::now().naive_utc());
+        active.update(&self.db).await
+    }
+
+    async fn soft_delete(&self, id: i32) -> Result<bool, DbErr> {
+        let txn = self.db.begin().await?;
+        let existing = Comments::find_by_id(id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| comment_not_found(id))?;
+
+        // 同時に削除されてもコメント数を減らすのは 1 回だけになるよう、未削除の行のみを更新する
+        let result = Comments::update_many()
+            .col_expr(Column::DeletedAt, Expr::value(Utc::now().naive_utc()))
+            .filter(Column::Id.eq(id))
+            .filter(Column::DeletedAt.is_null())
+            .exec(&txn)
+            .await?;
+        if result.rows_affected > 0 {
+            adjust_comment_count(&txn, existing.post_id, -1).await?;
+        }
+        txn.commit().await?;
+        Ok(result.rows_affected > 0)
+    }
+}
+
+/// モック実装（テスト用）
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    /// 投稿 ID の集合とコメントを保持します。
+    pub struct MockCommentRepository {
+        pub posts: Mutex<HashSet<i32>>,
+        pub comments: Mutex<Vec<Comment>>,
+    }
+
+    impl MockCommentRepository {
+        pub fn new(post_ids: &[i32]) -> Self {
+            Self {
+                posts: Mutex::new(post_ids.iter().copied().collect()),
+                comments: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CommentRepository for MockCommentRepository {
+        async fn post_exists(&self, post_id: i32) -> Result<bool, DbErr> {
+            Ok(self.posts.lock().unwrap().contains(&post_id))
+        }
+
+        async fn get_by_id(&self, id: i32) -> Result<Option<Comment>, DbErr> {
+            let comments = self.comments.lock().unwrap();
+            Ok(comments.iter().find(|c| c.id == id).cloned())
+        }
+
+        async fn insert(&self, comment: NewComment) -> Result<Comment, DbErr> {
+            let mut comments = self.comments.lock().unwrap();
+            let now = Utc::now().naive_utc();
+            let inserted = Comment {
+                id: comments.len() as i32 + 1,
+                post_id: comment.post_id,
+                user_id: comment.user_id,
+                parent_comment_id: comment.parent_comment_id,
+                root_comment_id: comment.root_comment_id,
+                body: comment.body,
+                created_at: now,
+                updated_at: now,
+                deleted_at: None,
+            };
+            comments.push(inserted.clone());
+            Ok(inserted)
+        }
+
+        async fn find_threads(
+            &self,
+            post_id: i32,
+            after_id: Option<i32>,
+            limit: u64,
+        ) -> Result<Vec<Comment>, DbErr> {
+            let comments = self.comments.lock().unwrap();
+            Ok(comments
+                .iter()
+                .filter(|c| c.post_id == post_id && c.root_comment_id.is_none())
+                .filter(|c| after_id.is_none_or(|after_id| c.id > after_id))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_replies(
+            &self,
+            root_ids: &[i32],
+            limit_per_thread: u64,
+        ) -> Result<Vec<Comment>, DbErr> {
+            let comments = self.comments.lock().unwrap();
+            let mut taken: HashMap<i32, u64> = HashMap::new();
+            Ok(comments
+                .iter()
+                .filter(|c| c.root_comment_id.is_some_and(|id| root_ids.contains(&id)))
+                .filter(|c| {
+                    let taken = taken
+                        .entry(c.root_comment_id.unwrap_or_default())
+                        .or_default();
+                    *taken += 1;
+                    *taken <= limit_per_thread
+                })
+                .cloned()
+                .collect())
+        }
+
+        async fn count_replies(&self, root_ids: &[i32]) -> Result<HashMap<i32, i64>, DbErr> {
+            let comments = self.comments.lock().unwrap();
+            let mut counts = HashMap::new();
+            for root_id in comments.iter().filter_map(|c| c.root_comment_id) {
+                if root_ids.contains(&root_id) {
+                    *counts.entry(root_id).or_default() += 1;
+                }
+            }
+            Ok(counts)
+        }
+
+        async fn find_thread_replies(
+            &self,
+            root_id: i32,
+            after_id: Option<i32>,
+            limit: u64,
+        ) -> Result<Vec<Comment>, DbErr> {
+            let comments = self.comments.lock().unwrap();
+            Ok(comments
+                .iter()
+                .filter(|c| c.root_comment_id == Some(root_id))
+                .filter(|c| after_id.is_none_or(|after_id| c.id > after_id))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn update_body(&self, id: i32, body: String) -> Result<Comment, DbErr> {
+            let mut comments = self.comments.lock().unwrap();
+            let comment = comments
+                .iter_mut()
+                .find(|c| c.id == id && c.deleted_at.is_none())
+                .ok_or_else(|| comment_not_found(id))?;
+            comment.body = body;
+            comment.updated_at = Utc::now().naive_utc();
+            Ok(comment.clone())
+        }
+
+        async fn soft_delete(&self, id: i32) -> Result<bool, DbErr> {
+            let mut comments = self.comments.lock().unwrap();
+            let comment = comments
+                .iter_mut()
+                .find(|c| c.id == id)
+                .ok_or_else(|| comment_not_found(id))?;
+            if comment.deleted_at.is_some() {
+                return Ok(false);
+            }
+            comment.deleted_at = Some(Utc::now().naive_utc());
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_support::insert_user;
+    use dotenv::dotenv;
+    use sea_orm::Database;
+    use std::env;
+
+    async fn setup_test_db() -> DatabaseConnection {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        Database::connect(&database_url)
+            .await
+            .expect("Failed to connect to database")
+    }
+
+    async fn insert_dummy_post(db: &DatabaseConnection, user_id: i32) -> i32 {
+        let inserted = post::ActiveModel {
+            id: NotSet,
+            body: Set("dummy post".to_string()),
+            user_id: Set(user_id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .expect("Insert dummy post failed");
+        inserted.id
+    }
+
+    async fn comment_count(db: &DatabaseConnection, post_id: i32) -> i32 {
+        post::Entity::find_by_id(post_id)
+            .one(db)
+            .await
+            .expect("Find post failed")
+            .expect("Post not found")
+            .comment_count
+    }
+
+    #[tokio::test]
+    async fn test_threads_replies_and_soft_delete() {
+        let db = setup_test_db().await;
+        let user_id = insert_user(&db).await;
+        let post_id = insert_dummy_post(&db, user_id).await;
+        let repo = PgCommentRepository::new(db.clone());
+
+        let root = repo
+            .insert(NewComment {
+                post_id,
+                user_id,
+                parent_comment_id: None,
+                root_comment_id: None,
+                body: "root".to_string(),
+            })
+            .await
+            .expect("Insert root failed");
+        let reply = repo
+            .insert(NewComment {
+                post_id,
+                user_id,
+                parent_comment_id: Some(root.id),
+                root_comment_id: Some(root.id),
+                body: "reply".to_string(),
+            })
+            .await
+            .expect("Insert reply failed");
+        assert_eq!(comment_count(&db, post_id).await, 2);
+
+        let threads = repo
+            .find_threads(post_id, None, 10)
+            .await
+            .expect("Find threads failed");
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, root.id);
+        let second_reply = repo
+            .insert(NewComment {
+                post_id,
+                user_id,
+                parent_comment_id: Some(reply.id),
+                root_comment_id: Some(root.id),
+                body: "second reply".to_string(),
+            })
+            .await
+            .expect("Insert reply failed");
+        assert_eq!(comment_count(&db, post_id).await, 3);
+
+        // スレッドごとの件数で打ち切り、残りは返信数とカーソルで辿る
+        let replies = repo
+            .find_replies(&[root.id], 1)
+            .await
+            .expect("Find replies failed");
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0].id, reply.id);
+        let counts = repo
+            .count_replies(&[root.id])
+            .await
+            .expect("Count replies failed");
+        assert_eq!(counts.get(&root.id), Some(&2));
+        let rest = repo
+            .find_thread_replies(root.id, Some(reply.id), 10)
+            .await
+            .expect("Find thread replies failed");
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].id, second_reply.id);
+
+        let updated = repo
+            .update_body(reply.id, "edited".to_string())
+            .await
+            .expect("Update failed");
+        assert_eq!(updated.body, "edited");
+
+        // 二重に削除してもコメント数は 1 回だけ減る
+        assert!(repo.soft_delete(root.id).await.expect("Delete failed"));
+        assert!(!repo.soft_delete(root.id).await.expect("Delete failed"));
+        assert_eq!(comment_count(&db, post_id).await, 2);
+        assert!(repo
+            .get_by_id(root.id)
+            .await
+            .expect("Get failed")
+            .expect("Comment not found")
+            .deleted_at
+            .is_some());
+    }
+}
@@ -0,0 +1,680 @@
+use crate::domain::entity::{conversation_members, conversations, messages};
+use crate::domain::repository::conversation::{ConversationRepository, ConversationSummary};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    DatabaseConnection, DbBackend, DbErr, FromQueryResult, NotSet, QuerySelect, QueryTrait, Set,
+    Statement, TransactionTrait,
+};
+
+// 既読位置までの他のメンバーのメッセージのうち、まだ既読にしていないものに既読を記録する。
+// 対象のメッセージを読み込まずに1文で登録し、配達済みのみの記録は既読に更新する。
+const RECORD_GROUP_READ_SQL: &str = r#"
+INSERT INTO message_receipts (message_id, user_id, delivered_at, read_at)
+SELECT m.id, $2, $4, $4
+FROM messages m
+WHERE m.conversation_id = $1
+  AND m.sender_id <> $2
+  AND m.id <= $3
+  AND m.deleted_at IS NULL
+  AND NOT EXISTS (
+      SELECT 1 FROM message_receipts r
+      WHERE r.message_id = m.id AND r.user_id = $2 AND r.read_at IS NOT NULL
+  )
+ON CONFLICT (message_id, user_id) DO UPDATE
+SET read_at = COALESCE(message_receipts.read_at, EXCLUDED.read_at),
+    delivered_at = COALESCE(message_receipts.delivered_at, EXCLUDED.delivered_at)
+"#;
+
+// 1対1の会話は相手ごとに最新メッセージを1件、グループは参加中の会話ごとに最新メッセージを1件取得し、
+// 最新の会話から順に並べる。1対1の検索には idx_conversation (sender_id, receiver_id) が使われる。
+const LIST_CONVERSATIONS_SQL: &str = r#"
+SELECT * FROM (
+    SELECT DISTINCT ON (peer_id)
+        CASE WHEN m.sender_id = $1 THEN m.receiver_id ELSE m.sender_id END AS peer_id,
+        NULL::integer AS conversation_id,
+        NULL::varchar AS name,
+        m.id AS last_message_id,
+        m.sender_id AS last_sender_id,
+        m.content AS last_message_content,
+        m.created_at AS last_activity_at,
+        (
+            SELECT COUNT(*) FROM messages u
+            WHERE u.sender_id = CASE WHEN m.sender_id = $1 THEN m.receiver_id ELSE m.sender_id END
+              AND u.receiver_id = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM message_receipts r
+                  WHERE r.message_id = u.id AND r.user_id = $1 AND r.read_at IS NOT NULL
+              )
+              AND u.deleted_at IS NULL
+        ) AS unread_count
+    FROM messages m
+    WHERE m.conversation_id IS NULL
+      AND m.deleted_at IS NULL
+      AND (m.sender_id = $1 OR m.receiver_id = $1)
+    ORDER BY peer_id, m.created_at DESC, m.id DESC
+) AS direct_conversations
+UNION ALL
+SELECT
+    NULL::integer AS peer_id,
+    c.id AS conversation_id,
+    c.name AS name,
+    lm.id AS last_message_id,
+    lm.sender_id AS last_sender_id,
+    lm.content AS last_message_content,
+    COALESCE(lm.created_at, cm.joined_at) AS last_activity_at,
+    (
+        SELECT COUNT(*) FROM messages u
+        WHERE u.conversation_id = c.id
+          AND u.sender_id <> $1
+          AND u.id > COALESCE(cm.last_read_message_id, 0)
+          AND u.deleted_at IS NULL
+    ) AS unread_count
+FROM conversation_members cm
+JOIN conversations c ON c.id = cm.conversation_id
+LEFT JOIN LATERAL (
+    SELECT id, sender_id, content, created_at FROM messages
+    WHERE conversation_id = c.id AND deleted_at IS NULL
+    ORDER BY created_at DESC, id DESC
+    LIMIT 1
+) AS lm ON true
+WHERE cm.user_id = $1
+ORDER BY last_activity_at DESC
+LIMIT $2 OFFSET $3
+"#;
+
+const COUNT_CONVERSATIONS_SQL: &str = r#"
+SELECT (
+    SELECT COUNT(DISTINCT CASE WHEN sender_id = $1 THEN receiver_id ELSE sender_id END)
+    FROM messages
+    WHERE conversation_id IS NULL
+      AND deleted_at IS NULL
+      AND (sender_id = $1 OR receiver_id = $1)
+) + (
+    SELECT COUNT(*) FROM conversation_members WHERE user_id = $1
+) AS total_count
+"#;
+
+pub struct PgConversationRepository {
+    db: DatabaseConnection,
+}
+
+impl PgConversationRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+// メンバー追加用の ActiveModel 一覧を作成するヘルパー関数
+fn member_models(id: i32, user_ids: Vec<i32>) -> Vec<conversation_members::ActiveModel> {
+    let now = Utc::now().naive_utc();
+    user_ids
+        .into_iter()
+        .map(|user_id| conversation_members::ActiveModel {
+            conversation_id: Set(id),
+            user_id: Set(user_id),
+            last_read_message_id: Set(None),
+            joined_at: Set(now),
+        })
+        .collect()
+}
+
+// 既にメンバーのユーザーを無視してメンバーを追加する
+async fn insert_members<C: ConnectionTrait>(
+    db: &C,
+    id: i32,
+    user_ids: Vec<i32>,
+) -> Result<u64, DbErr> {
+    if user_ids.is_empty() {
+        return Ok(0);
+    }
+    conversation_members::Entity::insert_many(member_models(id, user_ids))
+        .on_conflict(
+            OnConflict::columns([
+                conversation_members::Column::ConversationId,
+                conversation_members::Column::UserId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await
+}
+
+#[async_trait]
+impl ConversationRepository for PgConversationRepository {
+    async fn create_group(
+        &self,
+        created_by: i32,
+        name: String,
+        member_ids: Vec<i32>,
+    ) -> Result<conversations::Model, DbErr> {
+        let now = Utc::now().naive_utc();
+        let txn = self.db.begin().await?;
+
+        let conversation = conversations::ActiveModel {
+            id: NotSet,
+            name: Set(name),
+            created_by: Set(Some(created_by)),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&txn)
+        .await?;
+
+        // 作成者を含めた初期メンバーを登録
+        let mut user_ids = vec![created_by];
+        user_ids.extend(member_ids.into_iter().filter(|&id| id != created_by));
+        insert_members(&txn, conversation.id, user_ids).await?;
+
+        txn.commit().await?;
+        Ok(conversation)
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<Option<conversations::Model>, DbErr> {
+        conversations::Entity::find_by_id(id).one(&self.db).await
+    }
+
+    async fn rename(&self, id: i32, name: String) -> Result<conversations::Model, DbErr> {
+        let conversation = conversations::Entity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound(format!(
+                "Conversation with id {} not found",
+                id
+            )))?;
+
+        let mut conversation: conversations::ActiveModel = conversation.into();
+        conversation.name = Set(name);
+        conversation.updated_at = Set(Utc::now().naive_utc());
+        conversation.update(&self.db).await
+    }
+
+    async fn add_members(&self, id: i32, user_ids: Vec<i32>) -> Result<i32, DbErr> {
+        let inserted = insert_members(&self.db, id, user_ids).await?;
+        Ok(inserted as i32)
+    }
+
+    async fn remove_member(&self, id: i32, user_id: i32) -> Result<bool, DbErr> {
+        let result = conversation_members::Entity::delete_by_id((id, user_id))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn list_member_ids(&self, id: i32) -> Result<Vec<i32>, DbErr> {
+        conversation_members::Entity::find()
+            .select_only()
+            .column(conversation_members::Column::UserId)
+            .filter(conversation_members::Column::ConversationId.eq(id))
+            .into_tuple()
+            .all(&self.db)
+            .await
+    }
+
+    async fn is_member(&self, id: i32, user_id: i32) -> Result<bool, DbErr> {
+        let member = conversation_members::Entity::find_by_id((id, user_id))
+            .one(&self.db)
+            .await?;
+        Ok(member.is_some())
+    }
+
+    async fn shares_conversation(&self, user_id: i32, peer_id: i32) -> Result<bool, DbErr> {
+        let peer_conversations = conversation_members::Entity::find()
+            .select_only()
+            .column(conversation_members::Column::ConversationId)
+            .filter(conversation_members::Column::UserId.eq(peer_id))
+            .into_query();
+        let member = conversation_members::Entity::find()
+            .filter(conversation_members::Column::UserId.eq(user_id))
+            .filter(conversation_members::Column::ConversationId.in_subquery(peer_conversations))
+            .one(&self.db)
+            .await?;
+        Ok(member.is_some())
+    }
+
+    async fn mark_as_read(
+        &self,
+        id: i32,
+        user_id: i32,
+        message_id: Option<i32>,
+    ) -> Result<Option<i32>, DbErr> {
+        // 指定がなければ会話内の最新メッセージまで既読にする
+        let message_id = match message_id {
+            Some(message_id) => Some(message_id),
+            None => messages::Entity::find()
+                .select_only()
+                .column_as(messages::Column::Id.max(), "max_id")
+                .filter(messages::Column::ConversationId.eq(id))
+                .into_tuple::<Option<i32>>()
+                .one(&self.db)
+                .await?
+                .flatten(),
+        };
+        let Some(message_id) = message_id else {
+            return Ok(None);
+        };
+
+        // 既読位置は後退させない
+        conversation_members::Entity::update_many()
+            .col_expr(
+                conversation_members::Column::LastReadMessageId,
+                Expr::cust_with_values(
+                    "GREATEST(COALESCE(last_read_message_id, 0), $1)",
+                    [message_id],
+                ),
+            )
+            .filter(conversation_members::Column::ConversationId.eq(id))
+            .filter(conversation_members::Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+
+        let member = conversation_members::Entity::find_by_id((id, user_id))
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound(format!(
+                "User {} is not a member of conversation {}",
+                user_id, id
+            )))?;
+
+        // 既読位置までの他のメンバーのメッセージに、メッセージごとの既読も記録する
+        if let Some(last_read) = member.last_read_message_id {
+            self.db
+                .execute(Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    RECORD_GROUP_READ_SQL,
+                    [
+                        id.into(),
+                        user_id.into(),
+                        last_read.into(),
+                        Utc::now().naive_utc().into(),
+                    ],
+                ))
+                .await?;
+        }
+        Ok(member.last_read_message_id)
+    }
+
+    async fn list_conversations(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<ConversationSummary>, i32), DbErr> {
+        let limit = per_page as i64;
+        let offset = page as i64 * limit;
+        let conversations = ConversationSummary::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            LIST_CONVERSATIONS_SQL,
+            [user_id.into(), limit.into(), offset.into()],
+        ))
+        .all(&self.db)
+        .await?;
+
+        let total_count = self
+            .db
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                COUNT_CONVERSATIONS_SQL,
+                [user_id.into()],
+            ))
+            .await?
+            .map(|row| row.try_get::<i64>("", "total_count"))
+            .transpose()?
+            .unwrap_or(0);
+
+        Ok((conversations, total_count as i32))
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// グループと（グループID, ユーザーID）のメンバー一覧のみを保持します。
+    /// 既読位置は記録せず、会話一覧は空の一覧と件数 0 を返します。
+    pub struct MockConversationRepository {
+        pub conversations: Mutex<Vec<conversations::Model>>,
+        pub members: Mutex<Vec<(i32, i32)>>,
+    }
+
+    impl MockConversationRepository {
+        pub fn new() -> Self {
+            Self {
+                conversations: Mutex::new(Vec::new()),
+                members: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ConversationRepository for MockConversationRepository {
+        async fn create_group(
+            &self,
+            created_by: i32,
+            name: String,
+            member_ids: Vec<i32>,
+        ) -> Result<conversations::Model, DbErr> {
+            let now = Utc::now().naive_utc();
+            let conversation = {
+                let mut conversations = self.conversations.lock().unwrap();
+                let conversation = conversations::Model {
+                    id: conversations.len() as i32 + 1,
+                    name,
+                    created_by: Some(created_by),
+                    created_at: now,
+                    updated_at: now,
+                };
+                conversations.push(conversation.clone());
+                conversation
+            };
+            // 作成者は自動的にメンバーに含まれる
+            self.add_members(
+                conversation.id,
+                std::iter::once(created_by).chain(member_ids).collect(),
+            )
+            .await?;
+            Ok(conversation)
+        }
+
+        async fn get_by_id(&self, id: i32) -> Result<Option<conversations::Model>, DbErr> {
+            let conversations = self.conversations.lock().unwrap();
+            Ok(conversations.iter().find(|c| c.id == id).cloned())
+        }
+
+        async fn rename(&self, id: i32, name: String) -> Result<conversations::Model, DbErr> {
+            let mut conversations = self.conversations.lock().unwrap();
+            let conversation =
+                conversations
+                    .iter_mut()
+                    .find(|c| c.id == id)
+                    .ok_or(DbErr::RecordNotFound(format!(
+                        "Conversation with id {} not found",
+                        id
+                    )))?;
+            conversation.name = name;
+            Ok(conversation.clone())
+        }
+
+        async fn add_members(&self, id: i32, user_ids: Vec<i32>) -> Result<i32, DbErr> {
+            let mut members = self.members.lock().unwrap();
+            let mut added_count = 0;
+            for user_id in user_ids {
+                if !members.contains(&(id, user_id)) {
+                    members.push((id, user_id));
+                    added_count += 1;
+                }
+            }
+            Ok(added_count)
+        }
+
+        async fn remove_member(&self, id: i32, user_id: i32) -> Result<bool, DbErr> {
+            let mut members = self.members.lock().unwrap();
+            let count = members.len();
+            members.retain(|&member| member != (id, user_id));
+            Ok(members.len() < count)
+        }
+
+        async fn list_member_ids(&self, id: i32) -> Result<Vec<i32>, DbErr> {
+            let members = self.members.lock().unwrap();
+            Ok(members
+                .iter()
+                .filter(|(c, _)| *c == id)
+                .map(|&(_, user_id)| user_id)
+                .collect())
+        }
+
+        async fn is_member(&self, id: i32, user_id: i32) -> Result<bool, DbErr> {
+            Ok(self.members.lock().unwrap().contains(&(id, user_id)))
+        }
+
+        async fn shares_conversation(&self, user_id: i32, peer_id: i32) -> Result<bool, DbErr> {
+            let members = self.members.lock().unwrap();
+            Ok(members
+                .iter()
+                .any(|&(id, member)| member == user_id && members.contains(&(id, peer_id))))
+        }
+
+        async fn mark_as_read(
+            &self,
+            _id: i32,
+            _user_id: i32,
+            message_id: Option<i32>,
+        ) -> Result<Option<i32>, DbErr> {
+            Ok(message_id)
+        }
+
+        async fn list_conversations(
+            &self,
+            _user_id: i32,
+            _page: i32,
+            _per_page: i32,
+        ) -> Result<(Vec<ConversationSummary>, i32), DbErr> {
+            Ok((Vec::new(), 0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entity::message_receipts;
+    use crate::repository::test_support::insert_user;
+    use dotenv::dotenv;
+    use sea_orm::Database;
+    use std::env;
+
+    async fn setup_test_db() -> DatabaseConnection {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        Database::connect(&database_url)
+            .await
+            .expect("Failed to connect to database")
+    }
+
+    #[tokio::test]
+    async fn test_create_group_and_members() {
+        let db = setup_test_db().await;
+        let owner = insert_user(&db).await;
+        let member = insert_user(&db).await;
+        let repo = PgConversationRepository::new(db);
+
+        let group = repo
+            .create_group(owner, "Test group".to_string(), vec![member, owner])
+            .await
+            .expect("Create group failed");
+        assert_eq!(group.name, "Test group");
+        assert_eq!(group.created_by, Some(owner));
+
+        let mut member_ids = repo
+            .list_member_ids(group.id)
+            .await
+            .expect("List members failed");
+        member_ids.sort();
+        assert_eq!(member_ids, vec![owner, member]);
+    }
+
+    #[tokio::test]
+    async fn test_shares_conversation() {
+        let db = setup_test_db().await;
+        let owner = insert_user(&db).await;
+        let member = insert_user(&db).await;
+        let stranger = insert_user(&db).await;
+        let repo = PgConversationRepository::new(db);
+
+        repo.create_group(owner, "Test group".to_string(), vec![member])
+            .await
+            .expect("Create group failed");
+        repo.create_group(stranger, "Other group".to_string(), vec![])
+            .await
+            .expect("Create group failed");
+
+        assert!(repo.shares_conversation(owner, member).await.unwrap());
+        assert!(repo.shares_conversation(member, owner).await.unwrap());
+        assert!(!repo.shares_conversation(owner, stranger).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_members() {
+        let db = setup_test_db().await;
+        let owner = insert_user(&db).await;
+        let member = insert_user(&db).await;
+        let repo = PgConversationRepository::new(db);
+
+        let group = repo
+            .create_group(owner, "Test group".to_string(), vec![])
+            .await
+            .expect("Create group failed");
+
+        // 既存メンバーは重複して追加されない
+        let added = repo
+            .add_members(group.id, vec![owner, member])
+            .await
+            .expect("Add members failed");
+        assert_eq!(added, 1);
+        assert!(repo.is_member(group.id, member).await.unwrap());
+
+        let removed = repo
+            .remove_member(group.id, member)
+            .await
+            .expect("Remove member failed");
+        assert!(removed);
+        assert!(!repo.is_member(group.id, member).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rename_group() {
+        let db = setup_test_db().await;
+        let owner = insert_user(&db).await;
+        let repo = PgConversationRepository::new(db);
+
+        let group = repo
+            .create_group(owner, "Before".to_string(), vec![])
+            .await
+            .expect("Create group failed");
+        let renamed = repo
+            .rename(group.id, "After".to_string())
+            .await
+            .expect("Rename failed");
+        assert_eq!(renamed.name, "After");
+    }
+
+    #[tokio::test]
+    async fn test_mark_as_read_does_not_move_backwards() {
+        let db = setup_test_db().await;
+        let owner = insert_user(&db).await;
+        let member = insert_user(&db).await;
+        let repo = PgConversationRepository::new(db.clone());
+
+        let group = repo
+            .create_group(owner, "Test group".to_string(), vec![member])
+            .await
+            .expect("Create group failed");
+        let now = Utc::now().naive_utc();
+        let message = messages::ActiveModel {
+            id: NotSet,
+            sender_id: Set(owner),
+            receiver_id: Set(None),
+            content: Set("hello".to_string()),
+            created_at: Set(now),
+            updated_at: Set(now),
+            deleted_at: NotSet,
+            conversation_id: Set(Some(group.id)),
+            reply_to_message_id: NotSet,
+            edited_at: NotSet,
+        }
+        .insert(&db)
+        .await
+        .expect("Insert message failed");
+        // 配達済みの記録があれば既読に更新され、配達日時は保持される
+        let delivered = message_receipts::ActiveModel {
+            message_id: Set(message.id),
+            user_id: Set(member),
+            delivered_at: Set(Some(now)),
+            read_at: Set(None),
+        }
+        .insert(&db)
+        .await
+        .expect("Insert receipt failed");
+
+        // 指定がなければ最新メッセージまで既読になる
+        let last_read = repo
+            .mark_as_read(group.id, member, None)
+            .await
+            .expect("Mark as read failed");
+        assert_eq!(last_read, Some(message.id));
+
+        let receipts = message_receipts::Entity::find()
+            .filter(message_receipts::Column::MessageId.eq(message.id))
+            .all(&db)
+            .await
+            .expect("Find receipts failed");
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].user_id, member);
+        assert!(receipts[0].read_at.is_some());
+        assert_eq!(receipts[0].delivered_at, delivered.delivered_at);
+
+        let last_read = repo
+            .mark_as_read(group.id, member, Some(message.id - 1))
+            .await
+            .expect("Mark as read failed");
+        assert_eq!(last_read, Some(message.id));
+    }
+
+    #[tokio::test]
+    async fn test_list_conversations() {
+        let db = setup_test_db().await;
+        let user = insert_user(&db).await;
+        let peer = insert_user(&db).await;
+        let member = insert_user(&db).await;
+        let repo = PgConversationRepository::new(db.clone());
+
+        let insert_message = |sender_id: i32, receiver_id: Option<i32>, conversation_id| {
+            let now = Utc::now().naive_utc();
+            messages::ActiveModel {
+                id: NotSet,
+                sender_id: Set(sender_id),
+                receiver_id: Set(receiver_id),
+                content: Set(format!("from {}", sender_id)),
+                created_at: Set(now),
+                updated_at: Set(now),
+                deleted_at: NotSet,
+                conversation_id: Set(conversation_id),
+                reply_to_message_id: NotSet,
+                edited_at: NotSet,
+            }
+            .insert(&db)
+        };
+
+        // 1対1の会話（相手から2件受信、1件送信）
+        insert_message(peer, Some(user), None).await.unwrap();
+        insert_message(peer, Some(user), None).await.unwrap();
+        let last_direct = insert_message(user, Some(peer), None).await.unwrap();
+
+        // グループの会話（後から送信されたため先頭に並ぶ）
+        let group = repo
+            .create_group(member, "Test group".to_string(), vec![user])
+            .await
+            .expect("Create group failed");
+        let last_group = insert_message(member, None, Some(group.id)).await.unwrap();
+
+        let (conversations, total_count) = repo
+            .list_conversations(user, 0, 10)
+            .await
+            .expect("List conversations failed");
+        assert_eq!(total_count, 2);
+        assert_eq!(conversations.len(), 2);
+
+        assert_eq!(conversations[0].conversation_id, Some(group.id));
+        assert_eq!(conversations[0].name, Some("Test group".to_string()));
+        assert_eq!(conversations[0].last_message_id, Some(last_group.id));
+        assert_eq!(conversations[0].unread_count, 1);
+
+        assert_eq!(conversations[1].peer_id, Some(peer));
+        assert_eq!(conversations[1].last_message_id, Some(last_direct.id));
+        assert_eq!(conversations[1].unread_count, 2);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::domain::entity::users::Model as User;
+use crate::domain::entity::{follows, timeline_entries, users};
+use crate::domain::repository::follow::FollowRepository;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    DatabaseConnection, DbBackend, DbErr, JoinType, PaginatorTrait, QueryOrder, QuerySelect, Set,
+    Statement, TransactionTrait,
+};
+
+/// フォロー時にタイムラインへ取り込む、相手の配信済み投稿の最大件数
+const TIMELINE_BACKFILL_LIMIT: i64 = 50;
+
+// フォローした相手の配信済みの最近の投稿をタイムラインに取り込む
+// （未配信の投稿は読み込み時にフォロー関係から集められる）
+const BACKFILL_TIMELINE_SQL: &str = r#"
+INSERT INTO timeline_entries (user_id, post_id, author_id)
+SELECT $1, id, user_id FROM (
+    SELECT id, user_id FROM post
+    WHERE user_id = $2 AND fanned_out = true
+    ORDER BY id DESC
+    LIMIT $3
+) AS recent
+ON CONFLICT DO NOTHING
+"#;
+
+pub struct PgFollowRepository {
+    db: DatabaseConnection,
+}
+
+impl PgFollowRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    // フォロー関係の片側で絞り込んだユーザー一覧を、フォロー日時の新しい順に取得する
+    async fn list_users(
+        &self,
+        relation: follows::Relation,
+        filter_column: follows::Column,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr> {
+        let query = users::Entity::find()
+            .join_rev(JoinType::InnerJoin, relation.def())
+            .filter(filter_column.eq(user_id))
+            .filter(users::Column::DeletedAt.is_null());
+        let total_count = query.clone().count(&self.db).await?;
+        let users = query
+            .order_by_desc(follows::Column::CreatedAt)
+            .limit(per_page as u64)
+            .offset((page as i64 * per_page as i64) as u64)
+            .all(&self.db)
+            .await?;
+        Ok((users, total_count as i32))
+    }
+}
+
+// フォロー数・フォロワー数の非正規化カラムを増減する
+async fn adjust_counts<C: ConnectionTrait>(
+    db: &C,
+    follower_id: i32,
+    followee_id: i32,
+    delta: i32,
+) -> Result<(), DbErr> {
+    users::Entity::update_many()
+        .col_expr(
+            users::Column::FollowingCount,
+            Expr::col(users::Column::FollowingCount).add(delta),
+        )
+        .filter(users::Column::Id.eq(follower_id))
+        .exec(db)
+        .await?;
+    users::Entity::update_many()
+        .col_expr(
+            users::Column::FollowersCount,
+            Expr::col(users::Column::FollowersCount).add(delta),
+        )
+        .filter(users::Column::Id.eq(followee_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl FollowRepository for PgFollowRepository {
+    async fn follow(&self, follower_id: i32, followee_id: i32) -> Result<bool, DbErr> {
+        let txn = self.db.begin().await?;
+        let inserted = follows::Entity::insert(follows::ActiveModel {
+            follower_id: Set(follower_id),
+            followee_id: Set(followee_id),
+            created_at: Set(Utc::now().naive_utc()),
+        })
+        .on_conflict(
+            OnConflict::columns([follows::Column::FollowerId, follows::Column::FolloweeId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&txn)
+        .await?;
+
+        // 既にフォロー済みの場合はカウントを変えない
+        if inserted > 0 {
+            adjust_counts(&txn, follower_id, followee_id, 1).await?;
+            txn.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                BACKFILL_TIMELINE_SQL,
+                [
+                    follower_id.into(),
+                    followee_id.into(),
+                    TIMELINE_BACKFILL_LIMIT.into(),
+                ],
+            ))
+            .await?;
+        }
+        txn.commit().await?;
+        Ok(inserted > 0)
+    }
+
+    async fn unfollow(&self, follower_id: i32, followee_id: i32) -> Result<bool, DbErr> {
+        let txn = self.db.begin().await?;
+        let result = follows::Entity::delete_by_id((follower_id, followee_id))
+            .exec(&txn)
+            .await?;
+
+        if result.rows_affected > 0 {
+            adjust_counts(&txn, follower_id, followee_id, -1).await?;
+            timeline_entries::Entity::delete_many()
+                .filter(timeline_entries::Column::UserId.eq(follower_id))
+                .filter(timeline_entries::Column::AuthorId.eq(followee_id))
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn list_followers(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr> {
+        self.list_users(
+            follows::Relation::Follower,
+            follows::Column::FolloweeId,
+            user_id,
+            page,
+            per_page,
+        )
+        .await
+    }
+
+    async fn list_following(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr> {
+        self.list_users(
+            follows::Relation::Followee,
+            follows::Column::FollowerId,
+            user_id,
+            page,
+            per_page,
+        )
+        .await
+    }
+}
+
+/// モック実装（テスト用）
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    /// フォロー関係（フォローする側, される側）のみを保持します。
+    /// 一覧系はユーザー情報を持たないため空の一覧と件数を返します。
+    pub struct MockFollowRepository {
+        pub follows: Mutex<HashSet<(i32, i32)>>,
+    }
+
+    impl MockFollowRepository {
+        pub fn new() -> Self {
+            Self {
+                follows: Mutex::new(HashSet::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl FollowRepository for MockFollowRepository {
+        async fn follow(&self, follower_id: i32, followee_id: i32) -> Result<bool, DbErr> {
+            Ok(self
+                .follows
+                .lock()
+                .unwrap()
+                .insert((follower_id, followee_id)))
+        }
+
+        async fn unfollow(&self, follower_id: i32, followee_id: i32) -> Result<bool, DbErr> {
+            Ok(self
+                .follows
+                .lock()
+                .unwrap()
+                .remove(&(follower_id, followee_id)))
+        }
+
+        async fn list_followers(
+            &self,
+            user_id: i32,
+            _page: i32,
+            _per_page: i32,
+        ) -> Result<(Vec<User>, i32), DbErr> {
+            let follows = self.follows.lock().unwrap();
+            let count = follows.iter().filter(|(_, f)| *f == user_id).count();
+            Ok((Vec::new(), count as i32))
+        }
+
+        async fn list_following(
+            &self,
+            user_id: i32,
+            _page: i32,
+            _per_page: i32,
+        ) -> Result<(Vec<User>, i32), DbErr> {
+            let follows = self.follows.lock().unwrap();
+            let count = follows.iter().filter(|(f, _)| *f == user_id).count();
+            Ok((Vec::new(), count as i32))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_support::insert_user;
+    use dotenv::dotenv;
+    use sea_orm::Database;
+    use std::env;
+
+    async fn setup_test_db() -> DatabaseConnection {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        Database::connect(&database_url)
+            .await
+            .expect("Failed to connect to database")
+    }
+
+    async fn get_user(db: &DatabaseConnection, id: i32) -> User {
+        users::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .expect("Find user failed")
+            .expect("User not found")
+    }
+
+    #[tokio::test]
+    async fn test_follow_is_idempotent_and_updates_counts() {
+        let db = setup_test_db().await;
+        let alice = insert_user(&db).await;
+        let bob = insert_user(&db).await;
+        let repo = PgFollowRepository::new(db.clone());
+
+        assert!(repo.follow(alice, bob).await.expect("Follow failed"));
+        assert!(!repo.follow(alice, bob).await.expect("Follow failed"));
+
+        assert_eq!(get_user(&db, alice).await.following_count, 1);
+        assert_eq!(get_user(&db, bob).await.followers_count, 1);
+
+        let (followers, total_count) = repo
+            .list_followers(bob, 0, 10)
+            .await
+            .expect("List followers failed");
+        assert_eq!(total_count, 1);
+        assert_eq!(followers[0].id, alice);
+
+        let (following, total_count) = repo
+            .list_following(alice, 0, 10)
+            .await
+            .expect("List following failed");
+        assert_eq!(total_count, 1);
+        assert_eq!(following[0].id, bob);
+
+        assert!(repo.unfollow(alice, bob).await.expect("Unfollow failed"));
+        assert!(!repo.unfollow(alice, bob).await.expect("Unfollow failed"));
+        assert_eq!(get_user(&db, alice).await.following_count, 0);
+        assert_eq!(get_user(&db, bob).await.followers_count, 0);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::domain::entity::mentions::{self, Column, Entity as Mentions, Model as Mention};
+use crate::domain::entity::users;
+use crate::domain::repository::mention::{MentionRepository, MentionSource};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{DatabaseConnection, DbErr, NotSet, QueryOrder, QuerySelect, Set};
+
+pub struct PgMentionRepository {
+    db: DatabaseConnection,
+}
+
+impl PgMentionRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl MentionRepository for PgMentionRepository {
+    async fn resolve_handles(&self, handles: &[String]) -> Result<Vec<i32>, DbErr> {
+        if handles.is_empty() {
+            return Ok(Vec::new());
+        }
+        users::Entity::find()
+            .select_only()
+            .column(users::Column::Id)
+            .filter(users::Column::Handle.is_in(handles.iter().cloned()))
+            .filter(users::Column::DeletedAt.is_null())
+            .into_tuple()
+            .all(&self.db)
+            .await
+    }
+
+    async fn insert(
+        &self,
+        author_id: i32,
+        source: MentionSource,
+        user_ids: &[i32],
+    ) -> Result<Vec<Mention>, DbErr> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (post_id, message_id) = match source {
+            MentionSource::Post(id) => (Some(id), None),
+            MentionSource::Message(id) => (None, Some(id)),
+        };
+        let now = Utc::now().naive_utc();
+        // 記録済みのメンションは一意インデックスにより挿入されず、RETURNING にも含まれない
+        Mentions::insert_many(user_ids.iter().map(|&user_id| mentions::ActiveModel {
+            id: NotSet,
+            user_id: Set(user_id),
+            author_id: Set(author_id),
+            post_id: Set(post_id),
+            message_id: Set(message_id),
+            created_at: Set(now),
+        }))
+        .on_conflict(OnConflict::new().do_nothing().to_owned())
+        .exec_with_returning_many(&self.db)
+        .await
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        before_id: Option<i32>,
+        limit: u64,
+    ) -> Result<Vec<Mention>, DbErr> {
+        let mut query = Mentions::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_desc(Column::Id)
+            .limit(limit);
+        if let Some(before_id) = before_id {
+            query = query.filter(Column::Id.lt(before_id));
+        }
+        query.all(&self.db).await
+    }
+}
+
+/// モック実装（テスト用）
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// ハンドルとユーザー ID の対応と、記録したメンションを保持します。
+    pub struct MockMentionRepository {
+        pub handles: Mutex<HashMap<String, i32>>,
+        pub mentions: Mutex<Vec<Mention>>,
+    }
+
+    impl MockMentionRepository {
+        pub fn new(handles: &[(&str, i32)]) -> Self {
+            Self {
+                handles: Mutex::new(
+                    handles
+                        .iter()
+                        .map(|&(handle, id)| (handle.to_string(), id))
+                        .collect(),
+                ),
+                mentions: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MentionRepository for MockMentionRepository {
+        async fn resolve_handles(&self, handles: &[String]) -> Result<Vec<i32>, DbErr> {
+            let known = self.handles.lock().unwrap();
+            Ok(handles
+                .iter()
+                .filter_map(|h| known.get(h).copied())
+                .collect())
+        }
+
+        async fn insert(
+            &self,
+            author_id: i32,
+            source: MentionSource,
+            user_ids: &[i32],
+        ) -> Result<Vec<Mention>, DbErr> {
+            let (post_id, message_id) = match source {
+                MentionSource::Post(id) => (Some(id), None),
+                MentionSource::Message(id) => (None, Some(id)),
+            };
+            let mut mentions = self.mentions.lock().unwrap();
+            let mut inserted = Vec::new();
+            for &user_id in user_ids {
+                let exists = mentions.iter().any(|m| {
+                    m.user_id == user_id && m.post_id == post_id && m.message_id == message_id
+                });
+                if exists {
+                    continue;
+                }
+                let mention = Mention {
+                    id: mentions.len() as i32 + 1,
+                    user_id,
+                    author_id,
+                    post_id,
+                    message_id,
+                    created_at: Utc::now().naive_utc(),
+                };
+                mentions.push(mention.clone());
+                inserted.push(mention);
+            }
+            Ok(inserted)
+        }
+
+        async fn find_by_user_id(
+            &self,
+            user_id: i32,
+            before_id: Option<i32>,
+            limit: u64,
+        ) -> Result<Vec<Mention>, DbErr> {
+            let mentions = self.mentions.lock().unwrap();
+            Ok(mentions
+                .iter()
+                .rev()
+                .filter(|m| m.user_id == user_id)
+                .filter(|m| before_id.is_none_or(|before_id| m.id < before_id))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entity::post;
+    use crate::repository::test_support::{insert_user, insert_user_with_handle};
+    use dotenv::dotenv;
+    use sea_orm::Database;
+    use std::env;
+
+    async fn setup_test_db() -> DatabaseConnection {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        Database::connect(&database_url)
+            .await
+            .expect("Failed to connect to database")
+    }
+
+    #[tokio::test]
+    async fn test_resolve_insert_and_list() {
+        let db = setup_test_db().await;
+        // ハンドルは一意のため、実行ごとに異なる値を使う
+        let handle = format!("m{}", Utc::now().timestamp_micros());
+        let author_id = insert_user(&db).await;
+        let user_id = insert_user_with_handle(&db, Some(handle.clone())).await;
+        let post_id = post::ActiveModel {
+            id: NotSet,
+            body: Set(format!("hello @{}", handle)),
+            user_id: Set(author_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .expect("Insert dummy post failed")
+        .id;
+        let repo = PgMentionRepository::new(db);
+
+        let resolved = repo
+            .resolve_handles(&[handle.clone(), "no_such_handle_x".to_string()])
+            .await
+            .expect("Resolve failed");
+        assert_eq!(resolved, vec![user_id]);
+
+        let inserted = repo
+            .insert(author_id, MentionSource::Post(post_id), &[user_id])
+            .await
+            .expect("Insert failed");
+        assert_eq!(inserted.len(), 1);
+        assert_eq!(inserted[0].post_id, Some(post_id));
+        // 同じ投稿で同じユーザーへのメンションは 1 件のみ
+        let duplicated = repo
+            .insert(author_id, MentionSource::Post(post_id), &[user_id])
+            .await
+            .expect("Insert failed");
+        assert!(duplicated.is_empty());
+
+        let listed = repo
+            .find_by_user_id(user_id, None, 10)
+            .await
+            .expect("List failed");
+        assert_eq!(listed, inserted);
+        let older = repo
+            .find_by_user_id(user_id, Some(inserted[0].id), 10)
+            .await
+            .expect("List failed");
+        assert!(older.is_empty());
+    }
+}
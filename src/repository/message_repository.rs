@@ -1,11 +1,17 @@
-use crate::domain::entity::messages;
-use crate::domain::repository::message::MessageRepository;
+use crate::domain::entity::attachments::Model as Attachment;
+use crate::domain::entity::message_receipts::{self, Model as MessageReceipt};
+use crate::domain::entity::message_revisions::{self, Model as MessageRevision};
+use crate::domain::entity::{conversation_members, message_reactions, messages};
+use crate::domain::repository::attachment::AttachmentTarget;
+use crate::domain::repository::message::{MessageRepository, ReactionCount};
+use crate::repository::attachment_repository::{find_by_message_ids, link_attachments};
 use async_trait::async_trait;
 use chrono::Utc;
 use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Alias, Func, OnConflict, Query, SelectStatement};
 use sea_orm::Condition;
-use sea_orm::QueryOrder;
-use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, NotSet, Set};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, NotSet, Set, TransactionTrait};
+use sea_orm::{Order, QueryOrder, QuerySelect};
 
 pub struct PgMessageRepository {
     db: DatabaseConnection,
@@ -17,31 +23,114 @@ impl PgMessageRepository {
     }
 }
 
+/// 配達・既読の記録を一度に登録する最大件数（1 件あたり 4 つのバインド変数を使う）
+const RECEIPT_INSERT_BATCH_SIZE: usize = 1000;
+
+/// 指定ユーザーが既読にしたメッセージIDのサブクエリです（記録のないメッセージは未読として扱います）。
+fn read_message_ids(user_id: i32) -> SelectStatement {
+    Query::select()
+        .column(message_receipts::Column::MessageId)
+        .from(message_receipts::Entity)
+        .and_where(message_receipts::Column::UserId.eq(user_id))
+        .and_where(message_receipts::Column::ReadAt.is_not_null())
+        .to_owned()
+}
+
+/// (メッセージID, 受信者のユーザーID) ごとに配達または既読を記録し、新たに記録した件数を返します。
+///
+/// 既に記録済みの日時は上書きしません。既読を記録する場合、配達日時が未記録であれば同時に記録します。
+async fn record_receipts<C: ConnectionTrait>(
+    db: &C,
+    targets: Vec<(i32, i32)>,
+    read: bool,
+) -> Result<u64, DbErr> {
+    if targets.is_empty() {
+        return Ok(0);
+    }
+    let now = Utc::now().naive_utc();
+    let receipts = targets
+        .into_iter()
+        .map(|(message_id, user_id)| message_receipts::ActiveModel {
+            message_id: Set(message_id),
+            user_id: Set(user_id),
+            delivered_at: Set(Some(now)),
+            read_at: Set(read.then_some(now)),
+        });
+
+    let pending = if read {
+        message_receipts::Column::ReadAt
+    } else {
+        message_receipts::Column::DeliveredAt
+    };
+    let mut on_conflict = OnConflict::columns([
+        message_receipts::Column::MessageId,
+        message_receipts::Column::UserId,
+    ]);
+    on_conflict
+        .update_column(pending)
+        .action_and_where(Expr::col((message_receipts::Entity, pending)).is_null());
+    if read {
+        on_conflict.value(
+            message_receipts::Column::DeliveredAt,
+            Expr::cust("COALESCE(message_receipts.delivered_at, EXCLUDED.delivered_at)"),
+        );
+    }
+    // バインド変数の上限を超えないよう、一定件数ごとに分けて登録する
+    let receipts: Vec<_> = receipts.collect();
+    let mut recorded = 0;
+    for chunk in receipts.chunks(RECEIPT_INSERT_BATCH_SIZE) {
+        recorded += message_receipts::Entity::insert_many(chunk.to_vec())
+            .on_conflict(on_conflict.clone())
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(recorded)
+}
+
 #[async_trait]
 impl MessageRepository for PgMessageRepository {
     async fn send_message(
         &self,
         sender_id: i32,
-        receiver_id: i32,
+        receiver_id: Option<i32>,
+        conversation_id: Option<i32>,
         content: String,
+        reply_to_message_id: Option<i32>,
+        attachment_ids: &[i32],
     ) -> Result<messages::Model, DbErr> {
+        if receiver_id.is_some() == conversation_id.is_some() {
+            return Err(DbErr::Custom(
+                "receiver_id と conversation_id のどちらか一方を指定してください".into(),
+            ));
+        }
         let now = Utc::now().naive_utc();
         let new_message = messages::ActiveModel {
             id: NotSet,
             sender_id: Set(sender_id),
             receiver_id: Set(receiver_id),
             content: Set(content),
-            is_read: Set(false),
             created_at: Set(now),
             updated_at: Set(now),
             deleted_at: NotSet,
+            conversation_id: Set(conversation_id),
+            reply_to_message_id: Set(reply_to_message_id),
+            edited_at: NotSet,
         };
-        // 挿入して、生成されたIDからエンティティを取得
-        let res = messages::Entity::insert(new_message).exec(&self.db).await?;
+        // 挿入して添付ファイルを紐付け、生成されたIDからエンティティを取得
+        let txn = self.db.begin().await?;
+        let res = messages::Entity::insert(new_message).exec(&txn).await?;
+        link_attachments(
+            &txn,
+            sender_id,
+            AttachmentTarget::Message(res.last_insert_id),
+            attachment_ids,
+        )
+        .await?;
         let message = messages::Entity::find_by_id(res.last_insert_id)
-            .one(&self.db)
+            .one(&txn)
             .await?
             .ok_or(DbErr::Custom("メッセージの挿入に失敗しました".into()))?;
+        txn.commit().await?;
         Ok(message)
     }
 
@@ -59,7 +148,7 @@ impl MessageRepository for PgMessageRepository {
             .filter(messages::Column::DeletedAt.is_null());
 
         if unread_only {
-            query = query.filter(messages::Column::IsRead.eq(false));
+            query = query.filter(messages::Column::Id.not_in_subquery(read_message_ids(user_id)));
         }
         let total_count = query.clone().count(&self.db).await?;
         let paginator = query.paginate(&self.db, per_page as u64);
@@ -68,7 +157,7 @@ impl MessageRepository for PgMessageRepository {
         // 未読数も取得（削除されていないメッセージのみ）
         let unread_count = messages::Entity::find()
             .filter(messages::Column::ReceiverId.eq(user_id))
-            .filter(messages::Column::IsRead.eq(false))
+            .filter(messages::Column::Id.not_in_subquery(read_message_ids(user_id)))
             .filter(messages::Column::DeletedAt.is_null())
             .count(&self.db)
             .await?;
@@ -78,22 +167,58 @@ impl MessageRepository for PgMessageRepository {
     async fn get_conversation(
         &self,
         user_id: i32,
-        peer_id: i32,
+        peer_id: Option<i32>,
+        conversation_id: Option<i32>,
         page: i32,
         per_page: i32,
-    ) -> Result<(Vec<messages::Model>, i32), DbErr> {
-        // ユーザー間の会話：どちらが送信者でもOK
-        let condition = Condition::any()
-            .add(
-                Condition::all()
-                    .add(messages::Column::SenderId.eq(user_id))
-                    .add(messages::Column::ReceiverId.eq(peer_id)),
-            )
-            .add(
-                Condition::all()
-                    .add(messages::Column::SenderId.eq(peer_id))
-                    .add(messages::Column::ReceiverId.eq(user_id)),
-            );
+    ) -> Result<(Vec<messages::Model>, i32, i32), DbErr> {
+        let (condition, unread_query) = match (peer_id, conversation_id) {
+            (_, Some(conversation_id)) => {
+                // グループの会話：メンバーの既読位置より後の他者のメッセージを未読とする
+                let last_read =
+                    conversation_members::Entity::find_by_id((conversation_id, user_id))
+                        .one(&self.db)
+                        .await?
+                        .ok_or(DbErr::RecordNotFound(format!(
+                            "Conversation with id {} not found",
+                            conversation_id
+                        )))?
+                        .last_read_message_id
+                        .unwrap_or(0);
+                let unread_query = messages::Entity::find()
+                    .filter(messages::Column::ConversationId.eq(conversation_id))
+                    .filter(messages::Column::SenderId.ne(user_id))
+                    .filter(messages::Column::Id.gt(last_read));
+                (
+                    Condition::all().add(messages::Column::ConversationId.eq(conversation_id)),
+                    unread_query,
+                )
+            }
+            (Some(peer_id), None) => {
+                // ユーザー間の会話：どちらが送信者でもOK
+                let condition = Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(messages::Column::SenderId.eq(user_id))
+                            .add(messages::Column::ReceiverId.eq(peer_id)),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(messages::Column::SenderId.eq(peer_id))
+                            .add(messages::Column::ReceiverId.eq(user_id)),
+                    );
+                let unread_query = messages::Entity::find()
+                    .filter(messages::Column::SenderId.eq(peer_id))
+                    .filter(messages::Column::ReceiverId.eq(user_id))
+                    .filter(messages::Column::Id.not_in_subquery(read_message_ids(user_id)));
+                (condition, unread_query)
+            }
+            (None, None) => {
+                return Err(DbErr::Custom(
+                    "peer_id と conversation_id のどちらかを指定してください".into(),
+                ));
+            }
+        };
 
         // 論理削除されていないメッセージのみを対象に
         let query = messages::Entity::find()
@@ -104,10 +229,14 @@ impl MessageRepository for PgMessageRepository {
         let total_count = query.clone().count(&self.db).await?;
         let paginator = query.paginate(&self.db, per_page as u64);
         let msgs = paginator.fetch_page(page as u64).await?;
-        Ok((msgs, total_count as i32))
+
+        let unread_count = unread_query
+            .filter(messages::Column::DeletedAt.is_null())
+            .count(&self.db)
+            .await?;
+        Ok((msgs, total_count as i32, unread_count as i32))
     }
 
-    // 他のメソッドは変更なし
     async fn mark_as_read(
         &self,
         message_id: Option<i32>,
@@ -115,46 +244,183 @@ impl MessageRepository for PgMessageRepository {
         from_user_id: Option<i32>,
         to_user_id: Option<i32>,
     ) -> Result<i32, DbErr> {
-        let result = if message_id.is_some() || !message_ids.is_empty() {
-            // 指定されたID（単体 or 複数）に対して更新
-            let mut condition = Condition::any();
-            if let Some(mid) = message_id {
-                condition = condition.add(messages::Column::Id.eq(mid));
-            }
-            if !message_ids.is_empty() {
-                condition = condition.add(messages::Column::Id.is_in(message_ids));
-            }
-            messages::Entity::update_many()
-                .filter(condition)
-                .filter(messages::Column::DeletedAt.is_null()) // 論理削除されていないものだけ
-                .col_expr(messages::Column::IsRead, Expr::value(true))
-                .col_expr(
-                    messages::Column::UpdatedAt,
-                    Expr::value(Utc::now().naive_utc()),
-                )
-                .exec(&self.db)
-                .await?
-        } else if from_user_id.is_some() && to_user_id.is_some() {
-            // 特定のユーザー間の全メッセージを更新
-            let from = from_user_id.unwrap();
-            let to = to_user_id.unwrap();
-            messages::Entity::update_many()
+        // 1対1のメッセージのみが対象。グループの既読はメンバーの既読位置とともに記録する
+        let query = messages::Entity::find()
+            .filter(messages::Column::ReceiverId.is_not_null())
+            .filter(messages::Column::DeletedAt.is_null()); // 論理削除されていないものだけ
+        let query = if message_id.is_some() || !message_ids.is_empty() {
+            // 指定されたID（単体 or 複数）の受信者の既読を記録
+            query.filter(messages::Column::Id.is_in(message_id.into_iter().chain(message_ids)))
+        } else if let (Some(from), Some(to)) = (from_user_id, to_user_id) {
+            // 特定のユーザー間の未読メッセージを既読にする
+            query
                 .filter(messages::Column::SenderId.eq(from))
                 .filter(messages::Column::ReceiverId.eq(to))
-                .filter(messages::Column::DeletedAt.is_null()) // 論理削除されていないものだけ
-                .col_expr(messages::Column::IsRead, Expr::value(true))
-                .col_expr(
-                    messages::Column::UpdatedAt,
-                    Expr::value(Utc::now().naive_utc()),
-                )
-                .exec(&self.db)
-                .await?
+                .filter(messages::Column::Id.not_in_subquery(read_message_ids(to)))
         } else {
             return Err(DbErr::Custom(
                 "mark_as_read のための有効なパラメータが提供されませんでした".into(),
             ));
         };
-        Ok(result.rows_affected as i32)
+
+        let targets = query
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|m| m.receiver_id.map(|receiver_id| (m.id, receiver_id)))
+            .collect();
+        let recorded = record_receipts(&self.db, targets, true).await?;
+        Ok(recorded as i32)
+    }
+
+    async fn mark_as_delivered(
+        &self,
+        user_id: i32,
+        message_ids: Vec<i32>,
+        limit: u64,
+    ) -> Result<i32, DbErr> {
+        // 自分宛ての1対1のメッセージと、参加中のグループで他のメンバーが送信したメッセージが対象
+        let member_of = Query::select()
+            .column(conversation_members::Column::ConversationId)
+            .from(conversation_members::Entity)
+            .and_where(conversation_members::Column::UserId.eq(user_id))
+            .to_owned();
+        let delivered = Query::select()
+            .column(message_receipts::Column::MessageId)
+            .from(message_receipts::Entity)
+            .and_where(message_receipts::Column::UserId.eq(user_id))
+            .and_where(message_receipts::Column::DeliveredAt.is_not_null())
+            .to_owned();
+        let mut query = messages::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(messages::Column::ReceiverId.eq(user_id))
+                    .add(
+                        Condition::all()
+                            .add(messages::Column::ConversationId.in_subquery(member_of))
+                            .add(messages::Column::SenderId.ne(user_id)),
+                    ),
+            )
+            .filter(messages::Column::DeletedAt.is_null())
+            .filter(messages::Column::Id.not_in_subquery(delivered))
+            .order_by_asc(messages::Column::Id)
+            .limit(limit);
+        if !message_ids.is_empty() {
+            query = query.filter(messages::Column::Id.is_in(message_ids));
+        }
+
+        let targets = query
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|m| (m.id, user_id))
+            .collect();
+        let recorded = record_receipts(&self.db, targets, false).await?;
+        Ok(recorded as i32)
+    }
+
+    async fn find_receipts(&self, message_ids: &[i32]) -> Result<Vec<MessageReceipt>, DbErr> {
+        if message_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        message_receipts::Entity::find()
+            .filter(message_receipts::Column::MessageId.is_in(message_ids.iter().copied()))
+            .order_by_asc(message_receipts::Column::MessageId)
+            .order_by_asc(message_receipts::Column::UserId)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_attachments(&self, message_ids: &[i32]) -> Result<Vec<Attachment>, DbErr> {
+        find_by_message_ids(&self.db, message_ids).await
+    }
+
+    async fn find_by_ids(&self, message_ids: Vec<i32>) -> Result<Vec<messages::Model>, DbErr> {
+        if message_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        messages::Entity::find()
+            .filter(messages::Column::Id.is_in(message_ids))
+            .filter(messages::Column::DeletedAt.is_null())
+            .all(&self.db)
+            .await
+    }
+
+    async fn has_direct_messages(&self, user_id: i32, peer_id: i32) -> Result<bool, DbErr> {
+        let message = messages::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        messages::Column::SenderId
+                            .eq(user_id)
+                            .and(messages::Column::ReceiverId.eq(peer_id)),
+                    )
+                    .add(
+                        messages::Column::SenderId
+                            .eq(peer_id)
+                            .and(messages::Column::ReceiverId.eq(user_id)),
+                    ),
+            )
+            .one(&self.db)
+            .await?;
+        Ok(message.is_some())
+    }
+
+    async fn find_by_ids_including_deleted(
+        &self,
+        message_ids: &[i32],
+    ) -> Result<Vec<messages::Model>, DbErr> {
+        if message_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        messages::Entity::find()
+            .filter(messages::Column::Id.is_in(message_ids.iter().copied()))
+            .all(&self.db)
+            .await
+    }
+
+    async fn edit_message(
+        &self,
+        message_id: i32,
+        content: String,
+    ) -> Result<messages::Model, DbErr> {
+        let txn = self.db.begin().await?;
+        // 同時編集で履歴が欠けないよう、更新対象の行をロックしてから読み出す
+        let existing = messages::Entity::find_by_id(message_id)
+            .filter(messages::Column::DeletedAt.is_null())
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound(format!(
+                "Message with id {} not found",
+                message_id
+            )))?;
+
+        let now = Utc::now().naive_utc();
+        message_revisions::ActiveModel {
+            id: NotSet,
+            message_id: Set(existing.id),
+            content: Set(existing.content.clone()),
+            created_at: Set(now),
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut active: messages::ActiveModel = existing.into();
+        active.content = Set(content);
+        active.edited_at = Set(Some(now));
+        active.updated_at = Set(now);
+        let updated = active.update(&txn).await?;
+        txn.commit().await?;
+        Ok(updated)
+    }
+
+    async fn list_revisions(&self, message_id: i32) -> Result<Vec<MessageRevision>, DbErr> {
+        message_revisions::Entity::find()
+            .filter(message_revisions::Column::MessageId.eq(message_id))
+            .order_by_asc(message_revisions::Column::Id)
+            .all(&self.db)
+            .await
     }
 
     async fn delete_message(&self, message_id: i32) -> Result<bool, DbErr> {
@@ -169,4 +435,406 @@ impl MessageRepository for PgMessageRepository {
             .await?;
         Ok(result.rows_affected > 0)
     }
+
+    async fn add_reaction(
+        &self,
+        message_id: i32,
+        user_id: i32,
+        emoji: String,
+    ) -> Result<bool, DbErr> {
+        let reaction = message_reactions::ActiveModel {
+            message_id: Set(message_id),
+            user_id: Set(user_id),
+            emoji: Set(emoji),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        let result = message_reactions::Entity::insert(reaction)
+            .on_conflict(
+                OnConflict::columns([
+                    message_reactions::Column::MessageId,
+                    message_reactions::Column::UserId,
+                    message_reactions::Column::Emoji,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn remove_reaction(
+        &self,
+        message_id: i32,
+        user_id: i32,
+        emoji: &str,
+    ) -> Result<bool, DbErr> {
+        let result = message_reactions::Entity::delete_many()
+            .filter(message_reactions::Column::MessageId.eq(message_id))
+            .filter(message_reactions::Column::UserId.eq(user_id))
+            .filter(message_reactions::Column::Emoji.eq(emoji))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn find_reaction_counts(
+        &self,
+        viewer_id: i32,
+        message_ids: &[i32],
+    ) -> Result<Vec<ReactionCount>, DbErr> {
+        if message_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        message_reactions::Entity::find()
+            .select_only()
+            .column(message_reactions::Column::MessageId)
+            .column(message_reactions::Column::Emoji)
+            .column_as(
+                Expr::col(message_reactions::Column::UserId).count(),
+                "count",
+            )
+            .column_as(
+                Expr::expr(
+                    Func::cust(Alias::new("BOOL_OR"))
+                        .arg(Expr::col(message_reactions::Column::UserId).eq(viewer_id)),
+                ),
+                "reacted_by_viewer",
+            )
+            .filter(message_reactions::Column::MessageId.is_in(message_ids.iter().copied()))
+            .group_by(message_reactions::Column::MessageId)
+            .group_by(message_reactions::Column::Emoji)
+            .order_by_asc(message_reactions::Column::MessageId)
+            .order_by(
+                Expr::col(message_reactions::Column::CreatedAt).min(),
+                Order::Asc,
+            )
+            .order_by_asc(message_reactions::Column::Emoji)
+            .into_model::<ReactionCount>()
+            .all(&self.db)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_support::insert_user;
+    use dotenv::dotenv;
+    use sea_orm::Database;
+    use std::env;
+
+    async fn setup_test_db() -> DatabaseConnection {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        Database::connect(&database_url)
+            .await
+            .expect("Failed to connect to database")
+    }
+
+    #[tokio::test]
+    async fn test_has_direct_messages() {
+        let db = setup_test_db().await;
+        let sender_id = insert_user(&db).await;
+        let receiver_id = insert_user(&db).await;
+        let stranger_id = insert_user(&db).await;
+        let repo = PgMessageRepository::new(db);
+        repo.send_message(
+            sender_id,
+            Some(receiver_id),
+            None,
+            "hello".to_string(),
+            None,
+            &[],
+        )
+        .await
+        .expect("Send message failed");
+
+        // どちらが送信したかに関わらず、やり取りがあれば true を返す
+        assert!(repo
+            .has_direct_messages(sender_id, receiver_id)
+            .await
+            .unwrap());
+        assert!(repo
+            .has_direct_messages(receiver_id, sender_id)
+            .await
+            .unwrap());
+        assert!(!repo
+            .has_direct_messages(sender_id, stranger_id)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reaction_counts() {
+        let db = setup_test_db().await;
+        let sender_id = insert_user(&db).await;
+        let receiver_id = insert_user(&db).await;
+        let repo = PgMessageRepository::new(db.clone());
+        let message = repo
+            .send_message(
+                sender_id,
+                Some(receiver_id),
+                None,
+                "hello".to_string(),
+                None,
+                &[],
+            )
+            .await
+            .expect("Send message failed");
+
+        assert!(repo
+            .add_reaction(message.id, sender_id, "👍".to_string())
+            .await
+            .expect("Add reaction failed"));
+        assert!(repo
+            .add_reaction(message.id, receiver_id, "👍".to_string())
+            .await
+            .expect("Add reaction failed"));
+        assert!(repo
+            .add_reaction(message.id, receiver_id, "🎉".to_string())
+            .await
+            .expect("Add reaction failed"));
+        // 同じ絵文字の二重登録は無視される
+        assert!(!repo
+            .add_reaction(message.id, receiver_id, "🎉".to_string())
+            .await
+            .expect("Add reaction failed"));
+
+        let counts = repo
+            .find_reaction_counts(sender_id, &[message.id])
+            .await
+            .expect("Find reaction counts failed");
+        assert_eq!(counts.len(), 2);
+        assert_eq!((counts[0].emoji.as_str(), counts[0].count), ("👍", 2));
+        assert!(counts[0].reacted_by_viewer);
+        assert_eq!((counts[1].emoji.as_str(), counts[1].count), ("🎉", 1));
+        assert!(!counts[1].reacted_by_viewer);
+
+        assert!(repo
+            .remove_reaction(message.id, receiver_id, "🎉")
+            .await
+            .expect("Remove reaction failed"));
+        assert!(!repo
+            .remove_reaction(message.id, receiver_id, "🎉")
+            .await
+            .expect("Remove reaction failed"));
+        let counts = repo
+            .find_reaction_counts(receiver_id, &[message.id])
+            .await
+            .expect("Find reaction counts failed");
+        assert_eq!(counts.len(), 1);
+        assert!(counts[0].reacted_by_viewer);
+    }
+
+    #[tokio::test]
+    async fn test_reply_to_deleted_message() {
+        let db = setup_test_db().await;
+        let sender_id = insert_user(&db).await;
+        let receiver_id = insert_user(&db).await;
+        let repo = PgMessageRepository::new(db.clone());
+        let original = repo
+            .send_message(
+                sender_id,
+                Some(receiver_id),
+                None,
+                "original".to_string(),
+                None,
+                &[],
+            )
+            .await
+            .expect("Send message failed");
+        let reply = repo
+            .send_message(
+                receiver_id,
+                Some(sender_id),
+                None,
+                "reply".to_string(),
+                Some(original.id),
+                &[],
+            )
+            .await
+            .expect("Send reply failed");
+        assert_eq!(reply.reply_to_message_id, Some(original.id));
+
+        // 論理削除された返信元も、返信の表示用には取得できる
+        assert!(repo
+            .delete_message(original.id)
+            .await
+            .expect("Delete failed"));
+        assert!(repo
+            .find_by_ids(vec![original.id])
+            .await
+            .expect("Find failed")
+            .is_empty());
+        let originals = repo
+            .find_by_ids_including_deleted(&[original.id])
+            .await
+            .expect("Find failed");
+        assert_eq!(originals.len(), 1);
+        assert!(originals[0].deleted_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_edit_message_keeps_revisions() {
+        let db = setup_test_db().await;
+        let sender_id = insert_user(&db).await;
+        let receiver_id = insert_user(&db).await;
+        let repo = PgMessageRepository::new(db.clone());
+        let message = repo
+            .send_message(
+                sender_id,
+                Some(receiver_id),
+                None,
+                "first".to_string(),
+                None,
+                &[],
+            )
+            .await
+            .expect("Send message failed");
+        assert!(message.edited_at.is_none());
+
+        // 既読化では編集日時は変わらない
+        repo.mark_as_read(Some(message.id), vec![], None, None)
+            .await
+            .expect("Mark as read failed");
+        let read = repo
+            .find_by_ids(vec![message.id])
+            .await
+            .expect("Find failed");
+        assert!(read[0].edited_at.is_none());
+
+        let edited = repo
+            .edit_message(message.id, "second".to_string())
+            .await
+            .expect("Edit failed");
+        assert_eq!(edited.content, "second");
+        assert!(edited.edited_at.is_some());
+        repo.edit_message(message.id, "third".to_string())
+            .await
+            .expect("Edit failed");
+
+        let revisions = repo
+            .list_revisions(message.id)
+            .await
+            .expect("List revisions failed");
+        let contents: Vec<&str> = revisions.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, vec!["first", "second"]);
+
+        assert!(repo
+            .delete_message(message.id)
+            .await
+            .expect("Delete failed"));
+        assert!(matches!(
+            repo.edit_message(message.id, "fourth".to_string()).await,
+            Err(DbErr::RecordNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delivery_and_read_receipts() {
+        let db = setup_test_db().await;
+        let sender_id = insert_user(&db).await;
+        let receiver_id = insert_user(&db).await;
+        let repo = PgMessageRepository::new(db.clone());
+        let message = repo
+            .send_message(
+                sender_id,
+                Some(receiver_id),
+                None,
+                "hello".to_string(),
+                None,
+                &[],
+            )
+            .await
+            .expect("Send message failed");
+        let (_, _, unread_count) = repo
+            .list_messages(receiver_id, true, 0, 10)
+            .await
+            .expect("List messages failed");
+        assert_eq!(unread_count, 1);
+
+        // 配達・既読はそれぞれ最初の 1 回だけ記録される
+        assert_eq!(
+            repo.mark_as_delivered(receiver_id, vec![], 10)
+                .await
+                .expect("Mark as delivered failed"),
+            1
+        );
+        assert_eq!(
+            repo.mark_as_delivered(receiver_id, vec![message.id], 1)
+                .await
+                .expect("Mark as delivered failed"),
+            0
+        );
+        let receipts = repo
+            .find_receipts(&[message.id])
+            .await
+            .expect("Find receipts failed");
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].user_id, receiver_id);
+        let delivered_at = receipts[0].delivered_at.expect("delivered_at is not set");
+        assert!(receipts[0].read_at.is_none());
+
+        assert_eq!(
+            repo.mark_as_read(None, vec![], Some(sender_id), Some(receiver_id))
+                .await
+                .expect("Mark as read failed"),
+            1
+        );
+        assert_eq!(
+            repo.mark_as_read(Some(message.id), vec![], None, None)
+                .await
+                .expect("Mark as read failed"),
+            0
+        );
+        let receipts = repo
+            .find_receipts(&[message.id])
+            .await
+            .expect("Find receipts failed");
+        assert_eq!(receipts[0].delivered_at, Some(delivered_at));
+        assert!(receipts[0].read_at.is_some());
+
+        // 既読化でメッセージ自体は更新されない
+        let read = repo
+            .find_by_ids(vec![message.id])
+            .await
+            .expect("Find failed");
+        assert_eq!(read[0].updated_at, message.updated_at);
+        let (_, _, unread_count) = repo
+            .list_messages(receiver_id, true, 0, 10)
+            .await
+            .expect("List messages failed");
+        assert_eq!(unread_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_mark_as_delivered_respects_limit() {
+        let db = setup_test_db().await;
+        let sender_id = insert_user(&db).await;
+        let receiver_id = insert_user(&db).await;
+        let repo = PgMessageRepository::new(db.clone());
+        for _ in 0..3 {
+            repo.send_message(
+                sender_id,
+                Some(receiver_id),
+                None,
+                "hello".to_string(),
+                None,
+                &[],
+            )
+            .await
+            .expect("Send message failed");
+        }
+
+        // 未配達のメッセージは古い順に limit 件ずつ配達済みになる
+        for expected in [2, 1, 0] {
+            assert_eq!(
+                repo.mark_as_delivered(receiver_id, vec![], 2)
+                    .await
+                    .expect("Mark as delivered failed"),
+                expected
+            );
+        }
+    }
 }
@@ -1,3 +1,11 @@
+pub mod attachment_repository;
+pub mod comment_repository;
+pub mod conversation_repository;
+pub mod follow_repository;
+pub mod mention_repository;
 pub mod message_repository;
 pub mod post_repository;
+pub mod refresh_token_repository;
+#[cfg(test)]
+pub mod test_support;
 pub mod user_repository;
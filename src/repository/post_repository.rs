@@ -1,27 +1,187 @@
-use crate::domain::entity::post;
+use crate::domain::entity::attachments::Model as Attachment;
 use crate::domain::entity::post::{Column, Entity as Posts, Model as Post};
-use crate::domain::repository::post::PostRepository;
+use crate::domain::entity::post_revisions::Model as PostRevision;
+use crate::domain::entity::sea_orm_active_enums::RepostKind;
+use crate::domain::entity::users::Model as User;
+use crate::domain::entity::{
+    follows, hashtags, post, post_hashtags, post_likes, post_revisions, timeline_entries, users,
+};
+use crate::domain::repository::attachment::AttachmentTarget;
+use crate::domain::repository::post::{HashtagCount, PostPage, PostRepository};
+use crate::repository::attachment_repository::{find_by_post_ids, link_attachments};
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use sea_orm::entity::prelude::*;
-use sea_orm::{ColumnTrait, DatabaseConnection, NotSet, Set};
+use sea_orm::sea_query::{Expr, OnConflict, Query};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbBackend, JoinType, NotSet, PaginatorTrait,
+    QueryOrder, QuerySelect, Select, Set, Statement, TransactionTrait,
+};
+
+// 投稿者のフォロワー全員のタイムラインに投稿を書き込む
+const FAN_OUT_SQL: &str = r#"
+INSERT INTO timeline_entries (user_id, post_id, author_id)
+SELECT follower_id, $1, $2 FROM follows WHERE followee_id = $2
+ON CONFLICT DO NOTHING
+"#;
 
 pub struct PgPostRepository {
     db: DatabaseConnection,
 }
 
+// いいね数の非正規化カラムを増減する
+async fn adjust_like_count<C: ConnectionTrait>(
+    db: &C,
+    post_id: i32,
+    delta: i32,
+) -> Result<(), DbErr> {
+    Posts::update_many()
+        .col_expr(Column::LikeCount, Expr::col(Column::LikeCount).add(delta))
+        .filter(Column::Id.eq(post_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
 impl PgPostRepository {
     pub fn new(db: DatabaseConnection) -> Self {
         Self { db }
     }
+
+    // ID の降順に並べ、カーソルより古い投稿を limit 件取得する（オフセットは使わない）
+    async fn fetch_page(&self, query: Select<Posts>, page: PostPage) -> Result<Vec<Post>, DbErr> {
+        let mut query = query.order_by_desc(Column::Id).limit(page.limit);
+        if let Some(before_id) = page.before_id {
+            query = query.filter(Column::Id.lt(before_id));
+        }
+        query
+            .all(&self.db)
+            .await
+            .map_err(|e| DbErr::Exec(RuntimeErr::Internal(format!("Error: {}", e))))
+    }
+}
+
+// 投稿のハッシュタグを tags（正規化済み）に置き換える
+async fn replace_hashtags<C: ConnectionTrait>(
+    db: &C,
+    post_id: i32,
+    tags: &[String],
+) -> Result<(), DbErr> {
+    let hashtag_ids: Vec<i32> = if tags.is_empty() {
+        Vec::new()
+    } else {
+        hashtags::Entity::insert_many(tags.iter().map(|tag| hashtags::ActiveModel {
+            id: NotSet,
+            name: Set(tag.clone()),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        }))
+        .on_conflict(
+            OnConflict::column(hashtags::Column::Name)
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+        hashtags::Entity::find()
+            .select_only()
+            .column(hashtags::Column::Id)
+            .filter(hashtags::Column::Name.is_in(tags.iter().cloned()))
+            .into_tuple()
+            .all(db)
+            .await?
+    };
+
+    // 本文から消えたタグだけを外し、残ったタグの付与日時はそのままにする
+    post_hashtags::Entity::delete_many()
+        .filter(post_hashtags::Column::PostId.eq(post_id))
+        .filter(post_hashtags::Column::HashtagId.is_not_in(hashtag_ids.iter().copied()))
+        .exec(db)
+        .await?;
+    if !hashtag_ids.is_empty() {
+        post_hashtags::Entity::insert_many(hashtag_ids.iter().map(|&hashtag_id| {
+            post_hashtags::ActiveModel {
+                post_id: Set(post_id),
+                hashtag_id: Set(hashtag_id),
+                created_at: Set(chrono::Utc::now().naive_utc()),
+            }
+        }))
+        .on_conflict(
+            OnConflict::columns([
+                post_hashtags::Column::PostId,
+                post_hashtags::Column::HashtagId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    }
+    Ok(())
 }
 
 #[async_trait]
 impl PostRepository for PgPostRepository {
-    async fn find_all(&self) -> Result<Vec<Post>, DbErr> {
-        Posts::find()
-            .all(&self.db)
-            .await
-            .map_err(|e| DbErr::Exec(RuntimeErr::Internal(format!("Error: {}", e.to_string()))))
+    async fn find_all(&self, page: PostPage) -> Result<Vec<Post>, DbErr> {
+        self.fetch_page(Posts::find(), page).await
+    }
+
+    async fn find_home_timeline(&self, user_id: i32, page: PostPage) -> Result<Vec<Post>, DbErr> {
+        let delivered = Query::select()
+            .column(timeline_entries::Column::PostId)
+            .from(timeline_entries::Entity)
+            .and_where(timeline_entries::Column::UserId.eq(user_id))
+            .to_owned();
+        let followees = Query::select()
+            .column(follows::Column::FolloweeId)
+            .from(follows::Entity)
+            .and_where(follows::Column::FollowerId.eq(user_id))
+            .to_owned();
+
+        let query = Posts::find().filter(
+            Condition::any()
+                .add(Column::UserId.eq(user_id))
+                .add(Column::Id.in_subquery(delivered))
+                // 配信されていない投稿（フォロワーの多い投稿者など）は読み込み時に集める
+                .add(
+                    Condition::all()
+                        .add(Column::FannedOut.eq(false))
+                        .add(Column::UserId.in_subquery(followees)),
+                ),
+        );
+        self.fetch_page(query, page).await
+    }
+
+    async fn fan_out(
+        &self,
+        post_id: i32,
+        author_id: i32,
+        max_followers: i32,
+    ) -> Result<bool, DbErr> {
+        let followers_count: Option<i32> = users::Entity::find_by_id(author_id)
+            .select_only()
+            .column(users::Column::FollowersCount)
+            .into_tuple()
+            .one(&self.db)
+            .await?;
+        match followers_count {
+            Some(count) if count <= max_followers => {}
+            _ => return Ok(false),
+        }
+
+        let txn = self.db.begin().await?;
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            FAN_OUT_SQL,
+            [post_id.into(), author_id.into()],
+        ))
+        .await?;
+        Posts::update_many()
+            .col_expr(Column::FannedOut, Expr::value(true))
+            .filter(Column::Id.eq(post_id))
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+        Ok(true)
     }
 
     async fn get_by_id(&self, id: i32) -> Result<Option<Post>, DbErr> {
@@ -31,15 +191,28 @@ impl PostRepository for PgPostRepository {
             .map_err(|_e| DbErr::RecordNotFound(format!("Post with id {} not found", id)))
     }
 
-    async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<Post>, DbErr> {
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Post>, DbErr> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
         Posts::find()
-            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Id.is_in(ids.iter().copied()))
             .all(&self.db)
             .await
-            .map_err(|e| DbErr::Exec(RuntimeErr::Internal(format!("Error: {}", e.to_string()))))
     }
 
-    async fn insert(&self, body: String, user_id: i32) -> Result<Post, DbErr> {
+    async fn find_by_user_id(&self, user_id: i32, page: PostPage) -> Result<Vec<Post>, DbErr> {
+        self.fetch_page(Posts::find().filter(Column::UserId.eq(user_id)), page)
+            .await
+    }
+
+    async fn insert(
+        &self,
+        body: String,
+        user_id: i32,
+        attachment_ids: &[i32],
+        tags: &[String],
+    ) -> Result<Post, DbErr> {
         let _now = chrono::Utc::now().naive_utc();
         let post_data = post::ActiveModel {
             id: NotSet,
@@ -48,15 +221,71 @@ impl PostRepository for PgPostRepository {
             ..Default::default()
         };
 
-        post_data
-            .insert(&self.db)
+        let txn = self.db.begin().await?;
+        let post = post_data
+            .insert(&txn)
             .await
-            .map_err(|e| DbErr::Exec(RuntimeErr::Internal(format!("Error: {}", e.to_string()))))
+            .map_err(|e| DbErr::Exec(RuntimeErr::Internal(format!("Error: {}", e))))?;
+        link_attachments(
+            &txn,
+            user_id,
+            AttachmentTarget::Post(post.id),
+            attachment_ids,
+        )
+        .await?;
+        if !tags.is_empty() {
+            replace_hashtags(&txn, post.id, tags).await?;
+        }
+        txn.commit().await?;
+        Ok(post)
     }
 
-    async fn update(&self, id: i32, body: String) -> Result<Post, DbErr> {
+    async fn insert_repost(
+        &self,
+        user_id: i32,
+        repost_of_id: i32,
+        kind: RepostKind,
+        body: String,
+    ) -> Result<Post, DbErr> {
+        let inserted = Posts::insert(post::ActiveModel {
+            id: NotSet,
+            body: Set(body),
+            user_id: Set(user_id),
+            repost_of_id: Set(Some(repost_of_id)),
+            repost_kind: Set(Some(kind)),
+            ..Default::default()
+        })
+        // 単純なリポストの重複は部分一意インデックスで弾かれるため、既存のリポストを返す
+        // （他の一意制約違反まで握りつぶさないよう、対象のインデックスを指定する）
+        .on_conflict(
+            OnConflict::columns([Column::UserId, Column::RepostOfId])
+                .target_and_where(Expr::cust("repost_kind = 'repost'"))
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_with_returning(&self.db)
+        .await;
+
+        // 何も挿入されなかった場合、RETURNING が空のため RecordNotFound が返る
+        match inserted {
+            Ok(post) => Ok(post),
+            Err(DbErr::RecordNotInserted | DbErr::RecordNotFound(_)) => Posts::find()
+                .filter(Column::UserId.eq(user_id))
+                .filter(Column::RepostOfId.eq(repost_of_id))
+                .filter(Column::RepostKind.eq(RepostKind::Repost))
+                .one(&self.db)
+                .await?
+                .ok_or(DbErr::RecordNotInserted),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn update(&self, id: i32, body: String, tags: &[String]) -> Result<Post, DbErr> {
+        let txn = self.db.begin().await?;
+        // 同時編集で履歴が欠けないよう、更新対象の行をロックしてから読み出す
         let existing_post = Posts::find_by_id(id)
-            .one(&self.db)
+            .lock_exclusive()
+            .one(&txn)
             .await
             .map_err(|e| DbErr::Custom(format!("Error retrieving post: {}", e)))?
             .ok_or(DbErr::RecordNotFound(format!(
@@ -64,23 +293,180 @@ impl PostRepository for PgPostRepository {
                 id
             )))?;
 
+        post_revisions::ActiveModel {
+            id: NotSet,
+            post_id: Set(existing_post.id),
+            body: Set(existing_post.body.clone()),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| {
+            DbErr::Exec(RuntimeErr::Internal(format!(
+                "Error saving revision: {}",
+                e
+            )))
+        })?;
+
         let mut active_post: post::ActiveModel = existing_post.into();
         active_post.body = Set(body.clone());
 
-        active_post
-            .update(&self.db)
+        let updated = active_post.update(&txn).await.map_err(|e| {
+            DbErr::Exec(RuntimeErr::Internal(format!("Error updating post: {}", e)))
+        })?;
+        replace_hashtags(&txn, id, tags).await?;
+        txn.commit().await?;
+        Ok(updated)
+    }
+
+    async fn list_revisions(&self, post_id: i32) -> Result<Vec<PostRevision>, DbErr> {
+        post_revisions::Entity::find()
+            .filter(post_revisions::Column::PostId.eq(post_id))
+            .order_by_asc(post_revisions::Column::Id)
+            .all(&self.db)
             .await
-            .map_err(|e| DbErr::Exec(RuntimeErr::Internal(format!("Error updating post: {}", e))))
     }
 
     async fn delete(&self, id: i32) -> Result<(), DbErr> {
-        Posts::delete_by_id(id)
-            .exec(&self.db)
+        let txn = self.db.begin().await?;
+        // 本文を持たない単純なリポストは元の投稿と一緒に消す（引用は外部キーにより参照のみ外れる）
+        Posts::delete_many()
+            .filter(Column::RepostOfId.eq(id))
+            .filter(Column::RepostKind.eq(RepostKind::Repost))
+            .exec(&txn)
+            .await?;
+        let result = Posts::delete_by_id(id).exec(&txn).await?;
+        if result.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound(format!(
+                "Post with id {} not found",
+                id
+            )));
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
+    async fn like(&self, post_id: i32, user_id: i32) -> Result<bool, DbErr> {
+        let txn = self.db.begin().await?;
+        let inserted = post_likes::Entity::insert(post_likes::ActiveModel {
+            post_id: Set(post_id),
+            user_id: Set(user_id),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        })
+        .on_conflict(
+            OnConflict::columns([post_likes::Column::PostId, post_likes::Column::UserId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&txn)
+        .await?;
+
+        // 主キーの一意性により同時に実行されても加算されるのは 1 回だけになる
+        if inserted > 0 {
+            adjust_like_count(&txn, post_id, 1).await?;
+        }
+        txn.commit().await?;
+        Ok(inserted > 0)
+    }
+
+    async fn unlike(&self, post_id: i32, user_id: i32) -> Result<bool, DbErr> {
+        let txn = self.db.begin().await?;
+        let result = post_likes::Entity::delete_by_id((post_id, user_id))
+            .exec(&txn)
+            .await?;
+
+        if result.rows_affected > 0 {
+            adjust_like_count(&txn, post_id, -1).await?;
+        }
+        txn.commit().await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn list_likers(
+        &self,
+        post_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr> {
+        let query = users::Entity::find()
+            .join_rev(JoinType::InnerJoin, post_likes::Relation::Users.def())
+            .filter(post_likes::Column::PostId.eq(post_id))
+            .filter(users::Column::DeletedAt.is_null());
+        let total_count = query.clone().count(&self.db).await?;
+        let users = query
+            .order_by_desc(post_likes::Column::CreatedAt)
+            .limit(per_page as u64)
+            .offset((page as i64 * per_page as i64) as u64)
+            .all(&self.db)
+            .await?;
+        Ok((users, total_count as i32))
+    }
+
+    async fn find_liked_post_ids(&self, user_id: i32, post_ids: &[i32]) -> Result<Vec<i32>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        post_likes::Entity::find()
+            .select_only()
+            .column(post_likes::Column::PostId)
+            .filter(post_likes::Column::UserId.eq(user_id))
+            .filter(post_likes::Column::PostId.is_in(post_ids.iter().copied()))
+            .into_tuple()
+            .all(&self.db)
             .await
-            .map_err(|e| sqlx::Error::Protocol(e.to_string().into()))
-            .expect("delete post");
+    }
+
+    async fn find_attachments(&self, post_ids: &[i32]) -> Result<Vec<Attachment>, DbErr> {
+        find_by_post_ids(&self.db, post_ids).await
+    }
+
+    async fn set_hashtags(&self, post_id: i32, tags: &[String]) -> Result<(), DbErr> {
+        let txn = self.db.begin().await?;
+        replace_hashtags(&txn, post_id, tags).await?;
+        txn.commit().await?;
         Ok(())
     }
+
+    async fn find_by_hashtag(&self, tag: &str, page: PostPage) -> Result<Vec<Post>, DbErr> {
+        let tagged = Query::select()
+            .column((post_hashtags::Entity, post_hashtags::Column::PostId))
+            .from(post_hashtags::Entity)
+            .inner_join(
+                hashtags::Entity,
+                Expr::col((hashtags::Entity, hashtags::Column::Id))
+                    .equals((post_hashtags::Entity, post_hashtags::Column::HashtagId)),
+            )
+            .and_where(Expr::col((hashtags::Entity, hashtags::Column::Name)).eq(tag))
+            .to_owned();
+        self.fetch_page(Posts::find().filter(Column::Id.in_subquery(tagged)), page)
+            .await
+    }
+
+    async fn trending_hashtags(
+        &self,
+        since: NaiveDateTime,
+        limit: u64,
+    ) -> Result<Vec<HashtagCount>, DbErr> {
+        let post_count = Expr::col((post_hashtags::Entity, post_hashtags::Column::PostId)).count();
+        let rows: Vec<(String, i64)> = hashtags::Entity::find()
+            .select_only()
+            .column(hashtags::Column::Name)
+            .column_as(post_count.clone(), "post_count")
+            .join_rev(JoinType::InnerJoin, post_hashtags::Relation::Hashtags.def())
+            .filter(post_hashtags::Column::CreatedAt.gte(since))
+            .group_by(hashtags::Column::Id)
+            .group_by(hashtags::Column::Name)
+            .order_by_desc(post_count)
+            .order_by_asc(hashtags::Column::Name)
+            .limit(limit)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, post_count)| HashtagCount { name, post_count })
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -88,8 +474,9 @@ mod tests {
     use super::*;
     use crate::domain::entity::post::Model as Post;
     use crate::domain::repository::post::PostRepository;
+    use crate::repository::test_support::insert_user;
     use dotenv::dotenv;
-    use sea_orm::{Database, DatabaseConnection, NotSet, Set};
+    use sea_orm::{Database, DatabaseConnection};
     use std::env;
     use tokio;
 
@@ -102,40 +489,16 @@ mod tests {
             .expect("Failed to connect to database")
     }
 
-    // 修正: ダミーユーザを実際に users テーブルへ挿入する
-    async fn insert_dummy_user(db: &DatabaseConnection) -> i32 {
-        use crate::domain::entity::users::{
-            ActiveModel as UserActiveModel, Entity as Users, Model as UserModel,
-        };
-        let dummy_user = UserActiveModel {
-            id: NotSet,
-            name: Set("dummy user".to_string()),
-            email: Set("dummy@example.com".to_string()),
-            description: NotSet,
-            age: NotSet,
-            gender: NotSet,
-            address: NotSet,
-            created_at: Set(chrono::Utc::now().naive_utc()),
-            updated_at: Set(chrono::Utc::now().naive_utc()),
-            deleted_at: NotSet,
-        };
-        let inserted: UserModel = dummy_user
-            .insert(db)
-            .await
-            .expect("Insert dummy user failed");
-        inserted.id
-    }
-
     #[tokio::test]
     async fn test_insert_and_get_by_id() {
         let db = setup_test_db().await;
         // 実際に dummy user を挿入して有効な user_id を取得
-        let dummy_user_id = insert_dummy_user(&db).await;
+        let dummy_user_id = insert_user(&db).await;
         let repo = PgPostRepository::new(db);
 
         // insert を body と user_id で呼び出す
         let inserted_post = repo
-            .insert("Test post body".to_string(), dummy_user_id)
+            .insert("Test post body".to_string(), dummy_user_id, &[], &[])
             .await
             .expect("Insert failed");
         assert!(inserted_post.id > 0);
@@ -154,32 +517,40 @@ mod tests {
     #[tokio::test]
     async fn test_update_post() {
         let db = setup_test_db().await;
-        let dummy_user_id = insert_dummy_user(&db).await;
+        let dummy_user_id = insert_user(&db).await;
         let repo = PgPostRepository::new(db);
 
         // 新規レコードを挿入
         let inserted_post = repo
-            .insert("Original body".to_string(), dummy_user_id)
+            .insert("Original body".to_string(), dummy_user_id, &[], &[])
             .await
             .expect("Insert failed");
 
         // update: id と新しい body を渡す
         let updated_post = repo
-            .update(inserted_post.id, "Updated body".to_string())
+            .update(inserted_post.id, "Updated body".to_string(), &[])
             .await
             .expect("Update failed");
         assert_eq!(updated_post.body, "Updated body");
+
+        // 更新前の本文が編集履歴として残る
+        let revisions = repo
+            .list_revisions(inserted_post.id)
+            .await
+            .expect("List revisions failed");
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].body, "Original body");
     }
 
     #[tokio::test]
     async fn test_delete_post() {
         let db = setup_test_db().await;
-        let dummy_user_id = insert_dummy_user(&db).await;
+        let dummy_user_id = insert_user(&db).await;
         let repo = PgPostRepository::new(db);
 
         // 削除対象のレコードを挿入
         let inserted_post = repo
-            .insert("Post to be deleted".to_string(), dummy_user_id)
+            .insert("Post to be deleted".to_string(), dummy_user_id, &[], &[])
             .await
             .expect("Insert failed");
 
@@ -197,21 +568,27 @@ mod tests {
     #[tokio::test]
     async fn test_find_by_user_id() {
         let db = setup_test_db().await;
-        let dummy_user_id = insert_dummy_user(&db).await;
+        let dummy_user_id = insert_user(&db).await;
         let repo = PgPostRepository::new(db);
 
         // 同一 user_id のレコードを2件挿入
         let _ = repo
-            .insert("User post 1".to_string(), dummy_user_id)
+            .insert("User post 1".to_string(), dummy_user_id, &[], &[])
             .await
             .expect("Insert post1 failed");
         let _ = repo
-            .insert("User post 2".to_string(), dummy_user_id)
+            .insert("User post 2".to_string(), dummy_user_id, &[], &[])
             .await
             .expect("Insert post2 failed");
 
         let posts = repo
-            .find_by_user_id(dummy_user_id)
+            .find_by_user_id(
+                dummy_user_id,
+                PostPage {
+                    before_id: None,
+                    limit: 100,
+                },
+            )
             .await
             .expect("Find by user_id failed");
         // dummy_user_id の投稿が 2 件以上あることを確認
@@ -220,4 +597,273 @@ mod tests {
             assert_eq!(post.user_id, dummy_user_id);
         }
     }
+
+    #[tokio::test]
+    async fn test_find_by_user_id_paginates_newest_first() {
+        let db = setup_test_db().await;
+        let dummy_user_id = insert_user(&db).await;
+        let repo = PgPostRepository::new(db);
+
+        let older = repo
+            .insert("Older post".to_string(), dummy_user_id, &[], &[])
+            .await
+            .expect("Insert older post failed");
+        let newer = repo
+            .insert("Newer post".to_string(), dummy_user_id, &[], &[])
+            .await
+            .expect("Insert newer post failed");
+
+        // 新しい投稿から順に 1 件ずつ取得できる
+        let first = repo
+            .find_by_user_id(
+                dummy_user_id,
+                PostPage {
+                    before_id: Some(newer.id + 1),
+                    limit: 1,
+                },
+            )
+            .await
+            .expect("Find first page failed");
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].id, newer.id);
+
+        let second = repo
+            .find_by_user_id(
+                dummy_user_id,
+                PostPage {
+                    before_id: Some(first[0].id),
+                    limit: 1,
+                },
+            )
+            .await
+            .expect("Find second page failed");
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].id, older.id);
+    }
+
+    #[tokio::test]
+    async fn test_home_timeline_merges_fanned_out_and_pulled_posts() {
+        use crate::domain::repository::follow::FollowRepository;
+        use crate::repository::follow_repository::PgFollowRepository;
+
+        let db = setup_test_db().await;
+        let reader = insert_user(&db).await;
+        let light_author = insert_user(&db).await;
+        let heavy_author = insert_user(&db).await;
+        let stranger = insert_user(&db).await;
+        let follows = PgFollowRepository::new(db.clone());
+        follows
+            .follow(reader, light_author)
+            .await
+            .expect("Follow failed");
+        follows
+            .follow(reader, heavy_author)
+            .await
+            .expect("Follow failed");
+        let repo = PgPostRepository::new(db);
+
+        let own = repo
+            .insert("Own post".to_string(), reader, &[], &[])
+            .await
+            .expect("Insert failed");
+        let pushed = repo
+            .insert("Pushed post".to_string(), light_author, &[], &[])
+            .await
+            .expect("Insert failed");
+        assert!(repo
+            .fan_out(pushed.id, light_author, 10)
+            .await
+            .expect("Fan out failed"));
+        // フォロワー数が上限を超える投稿者の投稿は配信せず、読み込み時に集める
+        let pulled = repo
+            .insert("Pulled post".to_string(), heavy_author, &[], &[])
+            .await
+            .expect("Insert failed");
+        assert!(!repo
+            .fan_out(pulled.id, heavy_author, 0)
+            .await
+            .expect("Fan out failed"));
+        repo.insert("Stranger post".to_string(), stranger, &[], &[])
+            .await
+            .expect("Insert failed");
+
+        let timeline = repo
+            .find_home_timeline(
+                reader,
+                PostPage {
+                    before_id: None,
+                    limit: 10,
+                },
+            )
+            .await
+            .expect("Find home timeline failed");
+        let ids: Vec<i32> = timeline.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![pulled.id, pushed.id, own.id]);
+
+        // フォロー解除すると配信済みの投稿も表示されなくなる
+        follows
+            .unfollow(reader, light_author)
+            .await
+            .expect("Unfollow failed");
+        let timeline = repo
+            .find_home_timeline(
+                reader,
+                PostPage {
+                    before_id: None,
+                    limit: 10,
+                },
+            )
+            .await
+            .expect("Find home timeline failed");
+        assert!(!timeline.iter().any(|p| p.id == pushed.id));
+    }
+
+    #[tokio::test]
+    async fn test_like_is_idempotent_and_counts() {
+        let db = setup_test_db().await;
+        let author = insert_user(&db).await;
+        let fan = insert_user(&db).await;
+        let repo = PgPostRepository::new(db);
+
+        let post = repo
+            .insert("Likeable post".to_string(), author, &[], &[])
+            .await
+            .expect("Insert failed");
+
+        assert!(repo.like(post.id, fan).await.expect("Like failed"));
+        assert!(!repo.like(post.id, fan).await.expect("Like failed"));
+        assert!(repo.like(post.id, author).await.expect("Like failed"));
+
+        let liked = repo.get_by_id(post.id).await.expect("Get failed").unwrap();
+        assert_eq!(liked.like_count, 2);
+
+        let (likers, total_count) = repo
+            .list_likers(post.id, 0, 10)
+            .await
+            .expect("List likers failed");
+        assert_eq!(total_count, 2);
+        assert_eq!(likers[0].id, author);
+
+        assert_eq!(
+            repo.find_liked_post_ids(fan, &[post.id, post.id + 1])
+                .await
+                .expect("Find liked failed"),
+            vec![post.id]
+        );
+
+        assert!(repo.unlike(post.id, fan).await.expect("Unlike failed"));
+        assert!(!repo.unlike(post.id, fan).await.expect("Unlike failed"));
+        let unliked = repo.get_by_id(post.id).await.expect("Get failed").unwrap();
+        assert_eq!(unliked.like_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_repost_and_delete_original() {
+        let db = setup_test_db().await;
+        let author = insert_user(&db).await;
+        let reposter = insert_user(&db).await;
+        let repo = PgPostRepository::new(db);
+
+        let original = repo
+            .insert("Original post".to_string(), author, &[], &[])
+            .await
+            .expect("Insert failed");
+        let repost = repo
+            .insert_repost(reposter, original.id, RepostKind::Repost, String::new())
+            .await
+            .expect("Repost failed");
+        // 単純なリポストを繰り返しても同じ投稿が返る
+        let again = repo
+            .insert_repost(reposter, original.id, RepostKind::Repost, String::new())
+            .await
+            .expect("Repost failed");
+        assert_eq!(again.id, repost.id);
+        let quote = repo
+            .insert_repost(
+                reposter,
+                original.id,
+                RepostKind::Quote,
+                "Quoted".to_string(),
+            )
+            .await
+            .expect("Quote failed");
+        assert_eq!(quote.repost_of_id, Some(original.id));
+
+        let found = repo
+            .find_by_ids(&[original.id, quote.id])
+            .await
+            .expect("Find by ids failed");
+        assert_eq!(found.len(), 2);
+
+        // 元の投稿を消すと単純なリポストは消え、引用は参照だけが外れる
+        repo.delete(original.id).await.expect("Delete failed");
+        assert!(repo
+            .get_by_id(repost.id)
+            .await
+            .expect("Get failed")
+            .is_none());
+        let quote = repo
+            .get_by_id(quote.id)
+            .await
+            .expect("Get failed")
+            .expect("Quote should remain");
+        assert_eq!(quote.repost_of_id, None);
+        assert_eq!(quote.repost_kind, Some(RepostKind::Quote));
+        assert_eq!(quote.body, "Quoted");
+    }
+
+    #[tokio::test]
+    async fn test_hashtags_and_trending() {
+        let db = setup_test_db().await;
+        let author = insert_user(&db).await;
+        let repo = PgPostRepository::new(db);
+
+        let post = repo
+            .insert("Tagged post".to_string(), author, &[], &[])
+            .await
+            .expect("Insert failed");
+        // 他のテストと衝突しないよう投稿ごとに異なるタグを使う
+        let kept = format!("夏休み{}", post.id);
+        let removed = format!("rust{}", post.id);
+        repo.set_hashtags(post.id, &[kept.clone(), removed.clone()])
+            .await
+            .expect("Set hashtags failed");
+
+        let page = PostPage {
+            before_id: None,
+            limit: 10,
+        };
+        let tagged = repo
+            .find_by_hashtag(&removed, page)
+            .await
+            .expect("Find by hashtag failed");
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].id, post.id);
+
+        // 編集で消えたタグは外れる
+        repo.update(
+            post.id,
+            "Edited post".to_string(),
+            std::slice::from_ref(&kept),
+        )
+        .await
+        .expect("Update failed");
+        assert!(repo
+            .find_by_hashtag(&removed, page)
+            .await
+            .expect("Find by hashtag failed")
+            .is_empty());
+
+        let since = chrono::Utc::now().naive_utc() - chrono::Duration::hours(1);
+        let trending = repo
+            .trending_hashtags(since, 1000)
+            .await
+            .expect("Trending hashtags failed");
+        let count = trending
+            .iter()
+            .find(|t| t.name == kept)
+            .map(|t| t.post_count);
+        assert_eq!(count, Some(1));
+        assert!(!trending.iter().any(|t| t.name == removed));
+    }
 }
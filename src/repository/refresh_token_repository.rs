@@ -0,0 +1,232 @@
+use crate::domain::entity::refresh_tokens;
+use crate::domain::repository::refresh_token::RefreshTokenRepository;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{DatabaseConnection, DbErr, NotSet, Set};
+
+pub struct PgRefreshTokenRepository {
+    db: DatabaseConnection,
+}
+
+impl PgRefreshTokenRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for PgRefreshTokenRepository {
+    async fn create(
+        &self,
+        user_id: i32,
+        token_hash: String,
+        expires_at: NaiveDateTime,
+    ) -> Result<refresh_tokens::Model, DbErr> {
+        refresh_tokens::ActiveModel {
+            id: NotSet,
+            user_id: Set(user_id),
+            token_hash: Set(token_hash),
+            expires_at: Set(expires_at),
+            revoked_at: Set(None),
+            created_at: Set(Utc::now().naive_utc()),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<refresh_tokens::Model>, DbErr> {
+        refresh_tokens::Entity::find()
+            .filter(refresh_tokens::Column::TokenHash.eq(token_hash))
+            .one(&self.db)
+            .await
+    }
+
+    async fn revoke(&self, id: i32) -> Result<bool, DbErr> {
+        let result = refresh_tokens::Entity::update_many()
+            .col_expr(
+                refresh_tokens::Column::RevokedAt,
+                Expr::value(Utc::now().naive_utc()),
+            )
+            .filter(refresh_tokens::Column::Id.eq(id))
+            .filter(refresh_tokens::Column::RevokedAt.is_null())
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn revoke_all_for_user(&self, user_id: i32) -> Result<u64, DbErr> {
+        let result = refresh_tokens::Entity::update_many()
+            .col_expr(
+                refresh_tokens::Column::RevokedAt,
+                Expr::value(Utc::now().naive_utc()),
+            )
+            .filter(refresh_tokens::Column::UserId.eq(user_id))
+            .filter(refresh_tokens::Column::RevokedAt.is_null())
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
+
+/// モック実装（テスト用）
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    pub struct MockRefreshTokenRepository {
+        pub tokens: Mutex<HashMap<i32, refresh_tokens::Model>>,
+        pub next_id: Mutex<i32>,
+    }
+
+    impl MockRefreshTokenRepository {
+        pub fn new() -> Self {
+            Self {
+                tokens: Mutex::new(HashMap::new()),
+                next_id: Mutex::new(1),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RefreshTokenRepository for MockRefreshTokenRepository {
+        async fn create(
+            &self,
+            user_id: i32,
+            token_hash: String,
+            expires_at: NaiveDateTime,
+        ) -> Result<refresh_tokens::Model, DbErr> {
+            let mut next_id = self.next_id.lock().unwrap();
+            let token = refresh_tokens::Model {
+                id: *next_id,
+                user_id,
+                token_hash,
+                expires_at,
+                revoked_at: None,
+                created_at: Utc::now().naive_utc(),
+            };
+            *next_id += 1;
+            self.tokens.lock().unwrap().insert(token.id, token.clone());
+            Ok(token)
+        }
+
+        async fn find_by_hash(
+            &self,
+            token_hash: &str,
+        ) -> Result<Option<refresh_tokens::Model>, DbErr> {
+            let tokens = self.tokens.lock().unwrap();
+            Ok(tokens
+                .values()
+                .find(|t| t.token_hash == token_hash)
+                .cloned())
+        }
+
+        async fn revoke(&self, id: i32) -> Result<bool, DbErr> {
+            let mut tokens = self.tokens.lock().unwrap();
+            match tokens.get_mut(&id) {
+                Some(token) if token.revoked_at.is_none() => {
+                    token.revoked_at = Some(Utc::now().naive_utc());
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn revoke_all_for_user(&self, user_id: i32) -> Result<u64, DbErr> {
+            let mut tokens = self.tokens.lock().unwrap();
+            let now = Utc::now().naive_utc();
+            let mut count = 0;
+            for token in tokens
+                .values_mut()
+                .filter(|t| t.user_id == user_id && t.revoked_at.is_none())
+            {
+                token.revoked_at = Some(now);
+                count += 1;
+            }
+            Ok(count)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_support::insert_user;
+    use chrono::Duration;
+    use dotenv::dotenv;
+    use sea_orm::Database;
+    use std::env;
+
+    async fn setup_test_db() -> DatabaseConnection {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        Database::connect(&database_url)
+            .await
+            .expect("Failed to connect to database")
+    }
+
+    #[tokio::test]
+    async fn test_create_find_and_revoke() {
+        let db = setup_test_db().await;
+        let repo = PgRefreshTokenRepository::new(db.clone());
+        let user_id = insert_user(&db).await;
+
+        let token_hash = format!("test-hash-{}-{}", user_id, Utc::now().timestamp_micros());
+        let expires_at = Utc::now().naive_utc() + Duration::days(30);
+        let created = repo
+            .create(user_id, token_hash.clone(), expires_at)
+            .await
+            .expect("Failed to create refresh token");
+
+        let found = repo
+            .find_by_hash(&token_hash)
+            .await
+            .expect("Failed to find refresh token")
+            .expect("Refresh token not found");
+        assert_eq!(found.id, created.id);
+        assert!(found.revoked_at.is_none());
+
+        // 2回目の失効は false を返す
+        assert!(repo.revoke(created.id).await.expect("Failed to revoke"));
+        assert!(!repo.revoke(created.id).await.expect("Failed to revoke"));
+
+        let found = repo
+            .find_by_hash(&token_hash)
+            .await
+            .expect("Failed to find refresh token")
+            .expect("Refresh token not found");
+        assert!(found.revoked_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_all_for_user() {
+        let db = setup_test_db().await;
+        let repo = PgRefreshTokenRepository::new(db.clone());
+        let user_id = insert_user(&db).await;
+        let expires_at = Utc::now().naive_utc() + Duration::days(30);
+
+        for i in 0..2 {
+            repo.create(
+                user_id,
+                format!("test-hash-all-{}-{}", user_id, i),
+                expires_at,
+            )
+            .await
+            .expect("Failed to create refresh token");
+        }
+
+        let revoked = repo
+            .revoke_all_for_user(user_id)
+            .await
+            .expect("Failed to revoke tokens");
+        assert_eq!(revoked, 2);
+        let revoked = repo
+            .revoke_all_for_user(user_id)
+            .await
+            .expect("Failed to revoke tokens");
+        assert_eq!(revoked, 0);
+    }
+}
@@ -0,0 +1,40 @@
+//! リポジトリのテストで共通に使うヘルパー
+use crate::domain::entity::users::{ActiveModel as UserActiveModel, Model as User};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, NotSet, Set};
+
+/// 外部キーの参照先となるダミーユーザーを users テーブルに挿入し、その ID を返します。
+pub async fn insert_user(db: &DatabaseConnection) -> i32 {
+    insert_user_with_handle(db, None).await
+}
+
+/// ハンドルを指定してダミーユーザーを挿入し、その ID を返します。
+pub async fn insert_user_with_handle(db: &DatabaseConnection, handle: Option<String>) -> i32 {
+    let now = Utc::now();
+    let dummy_user = UserActiveModel {
+        id: NotSet,
+        name: Set("dummy user".to_string()),
+        // メールアドレスは一意のため、並行して実行されるテスト間で重複しない値を使う
+        email: Set(format!(
+            "dummy-{}@example.com",
+            now.timestamp_nanos_opt().unwrap_or_default()
+        )),
+        description: NotSet,
+        age: NotSet,
+        gender: NotSet,
+        address: NotSet,
+        created_at: Set(now.naive_utc()),
+        updated_at: Set(now.naive_utc()),
+        deleted_at: NotSet,
+        password_hash: NotSet,
+        role: NotSet,
+        followers_count: NotSet,
+        following_count: NotSet,
+        handle: Set(handle),
+    };
+    let inserted: User = dummy_user
+        .insert(db)
+        .await
+        .expect("Insert dummy user failed");
+    inserted.id
+}
@@ -1,9 +1,36 @@
 // repository.rs
+use crate::domain::entity::sea_orm_active_enums::UserRole;
 use crate::domain::entity::users::Model as User;
 use crate::domain::entity::users::{self, Entity as Users};
-use crate::domain::repository::user::UserRepository;
+use crate::domain::repository::user::{UserFilter, UserRepository};
+use crate::infra::pagination::{normalize_page, DEFAULT_PER_PAGE, MAX_PER_PAGE};
 use async_trait::async_trait;
-use sea_orm::{ActiveModelTrait, ActiveValue::NotSet, DatabaseConnection, EntityTrait, Set};
+use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::{Expr, Func, LikeExpr};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, Condition, DatabaseConnection, DbErr,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, RuntimeErr, Set,
+};
+
+// 一意制約違反などをハンドラで判別できるよう、データベース由来のエラーは sqlx のエラーのまま返す
+fn to_sqlx_error(e: DbErr) -> sqlx::Error {
+    match e {
+        DbErr::Conn(RuntimeErr::SqlxError(e))
+        | DbErr::Exec(RuntimeErr::SqlxError(e))
+        | DbErr::Query(RuntimeErr::SqlxError(e)) => e,
+        e => sqlx::Error::Protocol(e.to_string()),
+    }
+}
+
+// 部分一致検索用の ILIKE パターンを作成する（入力中の % と _ はエスケープする）
+// PostgreSQL の既定のエスケープ文字が \ のため ESCAPE 句は付けない
+fn contains_pattern(value: &str) -> LikeExpr {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    LikeExpr::new(format!("%{}%", escaped))
+}
 
 /// PgUserRepository の実装
 pub struct PgUserRepository {
@@ -22,15 +49,65 @@ impl UserRepository for PgUserRepository {
         Users::find_by_id(id)
             .one(&self.pool)
             .await
-            .map_err(|e| sqlx::Error::Protocol(e.to_string().into()))?
+            .map_err(to_sqlx_error)?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn list(&self, filter: UserFilter) -> Result<(Vec<User>, i32), sqlx::Error> {
+        let mut condition = Condition::all();
+        if !filter.include_deleted {
+            condition = condition.add(users::Column::DeletedAt.is_null());
+        }
+        if let Some(name) = &filter.name {
+            condition = condition.add(Expr::col(users::Column::Name).ilike(contains_pattern(name)));
+        }
+        if let Some(address) = &filter.address {
+            condition =
+                condition.add(Expr::col(users::Column::Address).ilike(contains_pattern(address)));
+        }
+        if let Some(gender) = &filter.gender {
+            condition = condition.add(users::Column::Gender.eq(gender.as_str()));
+        }
+
+        let query = Users::find()
+            .filter(condition)
+            .order_by_asc(users::Column::Id);
+        let total_count = query
+            .clone()
+            .count(&self.pool)
+            .await
+            .map_err(to_sqlx_error)?;
+        let (page, per_page) =
+            normalize_page(filter.page, filter.per_page, DEFAULT_PER_PAGE, MAX_PER_PAGE);
+        let users = query
+            .paginate(&self.pool, per_page as u64)
+            .fetch_page(page as u64)
+            .await
+            .map_err(to_sqlx_error)?;
+        Ok((users, total_count as i32))
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<User, sqlx::Error> {
+        // 一意インデックスと同じく大文字・小文字を区別せずに比較する
+        Users::find()
+            .filter(
+                Expr::expr(Func::lower(Expr::col(users::Column::Email))).eq(email.to_lowercase()),
+            )
+            .filter(users::Column::DeletedAt.is_null())
+            .one(&self.pool)
+            .await
+            .map_err(to_sqlx_error)?
             .ok_or(sqlx::Error::RowNotFound)
     }
 
-    async fn list(&self) -> Result<Vec<User>, sqlx::Error> {
+    async fn find_by_handle(&self, handle: &str) -> Result<User, sqlx::Error> {
         Users::find()
-            .all(&self.pool)
+            .filter(users::Column::Handle.eq(handle))
+            .filter(users::Column::DeletedAt.is_null())
+            .one(&self.pool)
             .await
-            .map_err(|e| sqlx::Error::Protocol(e.to_string().into()))
+            .map_err(to_sqlx_error)?
+            .ok_or(sqlx::Error::RowNotFound)
     }
 
     async fn create(
@@ -41,6 +118,7 @@ impl UserRepository for PgUserRepository {
         age: Option<i32>,
         gender: Option<String>,
         address: Option<String>,
+        password_hash: Option<String>,
     ) -> Result<User, sqlx::Error> {
         let now = chrono::Utc::now().naive_utc();
         let user = users::ActiveModel {
@@ -51,14 +129,17 @@ impl UserRepository for PgUserRepository {
             age: Set(age),
             gender: Set(gender),
             address: Set(address),
-            created_at: Default::default(),
-            updated_at: Default::default(),
+            created_at: Set(now),
+            updated_at: Set(now),
             deleted_at: Default::default(),
+            password_hash: Set(password_hash),
+            role: NotSet,
+            followers_count: NotSet,
+            following_count: NotSet,
+            handle: NotSet,
         };
 
-        user.insert(&self.pool)
-            .await
-            .map_err(|e| sqlx::Error::Protocol(e.to_string().into()))
+        user.insert(&self.pool).await.map_err(to_sqlx_error)
     }
 
     async fn update(
@@ -70,11 +151,12 @@ impl UserRepository for PgUserRepository {
         age: Option<i32>,
         gender: Option<String>,
         address: Option<String>,
+        handle: Option<String>,
     ) -> Result<User, sqlx::Error> {
         let user = Users::find_by_id(id)
             .one(&self.pool)
             .await
-            .map_err(|e| sqlx::Error::Protocol(e.to_string().into()))?
+            .map_err(to_sqlx_error)?
             .ok_or(sqlx::Error::RowNotFound)?;
 
         let mut user: users::ActiveModel = user.into();
@@ -97,11 +179,12 @@ impl UserRepository for PgUserRepository {
         if let Some(addr) = address {
             user.address = Set(Some(addr));
         }
+        if let Some(handle) = handle {
+            user.handle = Set(Some(handle));
+        }
         user.updated_at = Set(chrono::Utc::now().naive_utc());
 
-        user.update(&self.pool)
-            .await
-            .map_err(|e| sqlx::Error::Protocol(e.to_string().into()))
+        user.update(&self.pool).await.map_err(to_sqlx_error)
     }
 
     async fn delete(&self, id: i32) -> Result<User, sqlx::Error> {
@@ -109,16 +192,35 @@ impl UserRepository for PgUserRepository {
         let mut user: users::ActiveModel = user.into();
 
         user.deleted_at = Set(Some(chrono::Utc::now().naive_utc()));
-        user.update(&self.pool)
-            .await
-            .map_err(|e| sqlx::Error::Protocol(e.to_string().into()))
+        user.update(&self.pool).await.map_err(to_sqlx_error)
+    }
+
+    async fn restore(&self, id: i32) -> Result<User, sqlx::Error> {
+        let user = self.get_by_id(id).await?;
+        let mut user: users::ActiveModel = user.into();
+
+        user.deleted_at = Set(None);
+        user.updated_at = Set(chrono::Utc::now().naive_utc());
+        user.update(&self.pool).await.map_err(to_sqlx_error)
+    }
+
+    async fn update_role(&self, id: i32, role: UserRole) -> Result<User, sqlx::Error> {
+        let user = self.get_by_id(id).await?;
+        let mut user: users::ActiveModel = user.into();
+
+        user.role = Set(role);
+        user.updated_at = Set(chrono::Utc::now().naive_utc());
+        user.update(&self.pool).await.map_err(to_sqlx_error)
     }
 
     async fn hard_delete(&self, id: i32) -> Result<(), sqlx::Error> {
-        Users::delete_by_id(id)
+        let result = Users::delete_by_id(id)
             .exec(&self.pool)
             .await
-            .map_err(|e| sqlx::Error::Protocol(e.to_string().into()))?;
+            .map_err(to_sqlx_error)?;
+        if result.rows_affected == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
         Ok(())
     }
 }
@@ -154,9 +256,51 @@ pub mod mock {
                 .ok_or_else(|| sqlx::Error::RowNotFound)
         }
 
-        async fn list(&self) -> Result<Vec<User>, sqlx::Error> {
+        async fn list(&self, filter: UserFilter) -> Result<(Vec<User>, i32), sqlx::Error> {
             let users = self.users.lock().unwrap();
-            Ok(users.values().cloned().collect())
+            let contains = |value: &Option<String>, query: &Option<String>| match query {
+                Some(query) => value
+                    .as_ref()
+                    .is_some_and(|v| v.to_lowercase().contains(&query.to_lowercase())),
+                None => true,
+            };
+            let mut matched: Vec<User> = users
+                .values()
+                .filter(|u| filter.include_deleted || u.deleted_at.is_none())
+                .filter(|u| contains(&Some(u.name.clone()), &filter.name))
+                .filter(|u| contains(&u.address, &filter.address))
+                .filter(|u| filter.gender.is_none() || u.gender == filter.gender)
+                .cloned()
+                .collect();
+            matched.sort_by_key(|u| u.id);
+
+            let total_count = matched.len() as i32;
+            let (page, per_page) =
+                normalize_page(filter.page, filter.per_page, DEFAULT_PER_PAGE, MAX_PER_PAGE);
+            let page = matched
+                .into_iter()
+                .skip((page * per_page) as usize)
+                .take(per_page as usize)
+                .collect();
+            Ok((page, total_count))
+        }
+
+        async fn find_by_email(&self, email: &str) -> Result<User, sqlx::Error> {
+            let users = self.users.lock().unwrap();
+            users
+                .values()
+                .find(|u| u.email.eq_ignore_ascii_case(email) && u.deleted_at.is_none())
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn find_by_handle(&self, handle: &str) -> Result<User, sqlx::Error> {
+            let users = self.users.lock().unwrap();
+            users
+                .values()
+                .find(|u| u.handle.as_deref() == Some(handle) && u.deleted_at.is_none())
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
         }
 
         async fn create(
@@ -167,6 +311,7 @@ pub mod mock {
             age: Option<i32>,
             gender: Option<String>,
             address: Option<String>,
+            password_hash: Option<String>,
         ) -> Result<User, sqlx::Error> {
             let mut next_id = self.next_id.lock().unwrap();
             let id = *next_id;
@@ -184,6 +329,11 @@ pub mod mock {
                 created_at: now,
                 updated_at: now,
                 deleted_at: None,
+                password_hash,
+                role: UserRole::User,
+                followers_count: 0,
+                following_count: 0,
+                handle: None,
             };
 
             let mut users = self.users.lock().unwrap();
@@ -200,6 +350,7 @@ pub mod mock {
             age: Option<i32>,
             gender: Option<String>,
             address: Option<String>,
+            handle: Option<String>,
         ) -> Result<User, sqlx::Error> {
             let mut users = self.users.lock().unwrap();
             if let Some(user) = users.get_mut(&id) {
@@ -221,6 +372,9 @@ pub mod mock {
                 if let Some(addr) = address {
                     user.address = Some(addr);
                 }
+                if let Some(handle) = handle {
+                    user.handle = Some(handle);
+                }
                 user.updated_at = chrono::Utc::now().naive_utc();
                 Ok(user.clone())
             } else {
@@ -238,6 +392,22 @@ pub mod mock {
             }
         }
 
+        async fn restore(&self, id: i32) -> Result<User, sqlx::Error> {
+            let mut users = self.users.lock().unwrap();
+            let user = users.get_mut(&id).ok_or(sqlx::Error::RowNotFound)?;
+            user.deleted_at = None;
+            user.updated_at = chrono::Utc::now().naive_utc();
+            Ok(user.clone())
+        }
+
+        async fn update_role(&self, id: i32, role: UserRole) -> Result<User, sqlx::Error> {
+            let mut users = self.users.lock().unwrap();
+            let user = users.get_mut(&id).ok_or(sqlx::Error::RowNotFound)?;
+            user.role = role;
+            user.updated_at = chrono::Utc::now().naive_utc();
+            Ok(user.clone())
+        }
+
         async fn hard_delete(&self, id: i32) -> Result<(), sqlx::Error> {
             let mut users = self.users.lock().unwrap();
             if users.remove(&id).is_some() {
@@ -267,15 +437,26 @@ mod tests {
 
     mod pg_repository_tests {
         use super::*;
+        use crate::domain::repository::user::is_unique_violation;
+
+        // メールアドレスは一意のため、並行して実行されるテスト間で重複しない値を使う
+        fn unique_email(prefix: &str) -> String {
+            format!(
+                "{}-{}@example.com",
+                prefix,
+                chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+            )
+        }
 
         async fn create_test_user(repo: &PgUserRepository) -> User {
             repo.create(
                 "Test User".to_string(),
-                "test@example.com".to_string(),
+                unique_email("test"),
                 Some("Test Description".to_string()),
                 Some(25),
                 Some("Male".to_string()),
                 None, // address
+                None, // password_hash
             )
             .await
             .expect("Failed to create test user")
@@ -289,11 +470,12 @@ mod tests {
             let user = repo
                 .create(
                     "Test User".to_string(),
-                    "test@example.com".to_string(),
+                    unique_email("test"),
                     Some("Test Description".to_string()),
                     Some(25),
                     Some("Male".to_string()),
                     None,
+                    None, // password_hash
                 )
                 .await
                 .expect("Failed to create user");
@@ -335,29 +517,142 @@ mod tests {
             let pool = setup_test_db().await;
             let repo = PgUserRepository::new(pool);
 
-            let user1 = create_test_user(&repo).await;
+            // 他のテストのユーザーに一致しないよう、名前に一意な値を含める
+            let tag = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+            let user1 = repo
+                .create(
+                    format!("{} Test User", tag),
+                    unique_email("test"),
+                    Some("Test Description".to_string()),
+                    Some(25),
+                    Some("Male".to_string()),
+                    None,
+                    None, // password_hash
+                )
+                .await
+                .expect("Failed to create first user");
             let user2 = repo
                 .create(
-                    "Another User".to_string(),
-                    "another@example.com".to_string(),
+                    format!("{} Another User", tag),
+                    unique_email("another"),
                     Some("Another Description".to_string()),
                     Some(30),
                     Some("Female".to_string()),
                     None,
+                    None, // password_hash
                 )
                 .await
                 .expect("Failed to create second user");
 
-            let users = repo.list().await.expect("Failed to list users");
+            let (users, total_count) = repo
+                .list(UserFilter {
+                    name: Some(tag.to_string()),
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+
+            assert_eq!(total_count, 2);
+            assert_eq!(
+                users.iter().map(|u| u.id).collect::<Vec<_>>(),
+                vec![user1.id, user2.id]
+            );
 
-            assert!(users.len() >= 2);
-            assert!(users.iter().any(|u| u.id == user1.id));
-            assert!(users.iter().any(|u| u.id == user2.id));
+            // 名前の部分一致と論理削除の除外
+            repo.delete(user1.id).await.expect("Failed to soft delete");
+            let (users, total_count) = repo
+                .list(UserFilter {
+                    name: Some(format!("{} another", tag)),
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+            assert_eq!(total_count, 1);
+            assert_eq!(users.len(), 1);
+            assert_eq!(users[0].id, user2.id);
 
             repo.hard_delete(user1.id).await.expect("Failed to cleanup");
             repo.hard_delete(user2.id).await.expect("Failed to cleanup");
         }
 
+        #[tokio::test]
+        async fn test_list_users_clamps_per_page() {
+            let pool = setup_test_db().await;
+            let repo = PgUserRepository::new(pool);
+
+            // per_page を指定しない場合も上限を超える場合も、全件ではなく既定値・上限で区切る
+            let (users, _) = repo
+                .list(UserFilter {
+                    include_deleted: true,
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+            assert!(users.len() <= DEFAULT_PER_PAGE as usize);
+
+            let (users, _) = repo
+                .list(UserFilter {
+                    include_deleted: true,
+                    per_page: 1_000_000,
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+            assert!(users.len() <= MAX_PER_PAGE as usize);
+        }
+
+        #[tokio::test]
+        async fn test_list_users_escapes_wildcards() {
+            let pool = setup_test_db().await;
+            let repo = PgUserRepository::new(pool);
+
+            // 他のテストのユーザーに一致しないよう、名前に一意な値を含める
+            let tag = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+            let literal = repo
+                .create(
+                    format!("{}%_off", tag),
+                    unique_email("literal"),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await
+                .expect("Failed to create user");
+            let wildcard = repo
+                .create(
+                    format!("{}xyoff", tag),
+                    unique_email("wildcard"),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await
+                .expect("Failed to create user");
+
+            // % と _ はワイルドカードではなく文字そのものとして扱われる
+            let (users, total_count) = repo
+                .list(UserFilter {
+                    name: Some(format!("{}%_", tag)),
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+            assert_eq!(total_count, 1);
+            assert_eq!(users.len(), 1);
+            assert_eq!(users[0].id, literal.id);
+
+            repo.hard_delete(literal.id)
+                .await
+                .expect("Failed to cleanup");
+            repo.hard_delete(wildcard.id)
+                .await
+                .expect("Failed to cleanup");
+        }
+
         #[tokio::test]
         async fn test_update_user() {
             let pool = setup_test_db().await;
@@ -369,11 +664,12 @@ mod tests {
                 .update(
                     created_user.id,
                     Some("Updated Name".to_string()),
-                    Some("updated@example.com".to_string()),
+                    Some(unique_email("updated")),
                     Some("Updated Description".to_string()),
                     Some(26),
                     Some("Female".to_string()),
                     None, // address
+                    None, // handle
                 )
                 .await
                 .expect("Failed to update user");
@@ -409,6 +705,7 @@ mod tests {
                     None, // age は更新しない
                     None, // gender は更新しない
                     None, // address は更新しない
+                    None, // handle は更新しない
                 )
                 .await
                 .expect("Failed to partially update user");
@@ -445,6 +742,90 @@ mod tests {
             let result = repo.get_by_id(created_user.id).await;
             assert!(result.is_err());
         }
+
+        #[tokio::test]
+        async fn test_restore_and_update_role() {
+            let pool = setup_test_db().await;
+            let repo = PgUserRepository::new(pool);
+
+            let created_user = create_test_user(&repo).await;
+            assert_eq!(created_user.role, UserRole::User);
+
+            repo.delete(created_user.id)
+                .await
+                .expect("Failed to delete user");
+            let restored_user = repo
+                .restore(created_user.id)
+                .await
+                .expect("Failed to restore user");
+            assert!(restored_user.deleted_at.is_none());
+
+            let updated_user = repo
+                .update_role(created_user.id, UserRole::Moderator)
+                .await
+                .expect("Failed to update role");
+            assert_eq!(updated_user.role, UserRole::Moderator);
+
+            repo.hard_delete(created_user.id)
+                .await
+                .expect("Failed to hard delete user");
+            let result = repo.hard_delete(created_user.id).await;
+            assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        }
+
+        #[tokio::test]
+        async fn test_unique_email_and_handle() {
+            let pool = setup_test_db().await;
+            let repo = PgUserRepository::new(pool);
+
+            let user = create_test_user(&repo).await;
+            // 大文字・小文字だけが異なるメールアドレスも重複とみなす
+            let result = repo
+                .create(
+                    "Duplicate User".to_string(),
+                    user.email.to_uppercase(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None, // password_hash
+                )
+                .await;
+            assert!(result.as_ref().is_err_and(is_unique_violation));
+            let found = repo
+                .find_by_email(&user.email.to_uppercase())
+                .await
+                .expect("Failed to find user by email");
+            assert_eq!(found.id, user.id);
+
+            let handle = format!("h{}", chrono::Utc::now().timestamp_micros());
+            repo.update(
+                user.id,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(handle.clone()),
+            )
+            .await
+            .expect("Failed to set handle");
+            let found = repo
+                .find_by_handle(&handle)
+                .await
+                .expect("Failed to find user by handle");
+            assert_eq!(found.id, user.id);
+
+            let other = create_test_user(&repo).await;
+            let result = repo
+                .update(other.id, None, None, None, None, None, None, Some(handle))
+                .await;
+            assert!(result.as_ref().is_err_and(is_unique_violation));
+
+            repo.hard_delete(user.id).await.expect("Failed to cleanup");
+            repo.hard_delete(other.id).await.expect("Failed to cleanup");
+        }
     }
 
     mod mock_repository_tests {
@@ -462,6 +843,7 @@ mod tests {
                     Some(25),
                     Some("Male".to_string()),
                     None,
+                    None, // password_hash
                 )
                 .await
                 .expect("Failed to create user");
@@ -485,6 +867,7 @@ mod tests {
                     None,
                     None,
                     None,
+                    None, // password_hash
                 )
                 .await
                 .expect("Failed to create user 1");
@@ -496,16 +879,118 @@ mod tests {
                     None,
                     None,
                     None,
+                    None, // password_hash
                 )
                 .await
                 .expect("Failed to create user 2");
 
-            let users = repo.list().await.expect("Failed to list users");
+            let (users, total_count) = repo
+                .list(UserFilter::default())
+                .await
+                .expect("Failed to list users");
             assert_eq!(users.len(), 2);
+            assert_eq!(total_count, 2);
             assert!(users.iter().any(|u| u.id == user1.id));
             assert!(users.iter().any(|u| u.id == user2.id));
         }
 
+        #[tokio::test]
+        async fn test_mock_list_users_clamps_per_page() {
+            let repo = mock::MockUserRepository::new();
+            for i in 0..MAX_PER_PAGE + 1 {
+                repo.create(
+                    format!("User {}", i),
+                    format!("user{}@example.com", i),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None, // password_hash
+                )
+                .await
+                .expect("Failed to create user");
+            }
+
+            let (users, total_count) = repo
+                .list(UserFilter::default())
+                .await
+                .expect("Failed to list users");
+            assert_eq!(users.len(), DEFAULT_PER_PAGE as usize);
+            assert_eq!(total_count, MAX_PER_PAGE + 1);
+
+            let (users, _) = repo
+                .list(UserFilter {
+                    per_page: MAX_PER_PAGE + 1,
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+            assert_eq!(users.len(), MAX_PER_PAGE as usize);
+        }
+
+        #[tokio::test]
+        async fn test_mock_list_users_filter_and_paginate() {
+            let repo = mock::MockUserRepository::new();
+
+            for (name, address) in [
+                ("Alice", "Tokyo"),
+                ("Bob", "Osaka"),
+                ("alicia", "Tokyo-to"),
+                ("Carol", "Tokyo"),
+            ] {
+                repo.create(
+                    name.to_string(),
+                    format!("{}@example.com", name.to_lowercase()),
+                    None,
+                    None,
+                    None,
+                    Some(address.to_string()),
+                    None, // password_hash
+                )
+                .await
+                .expect("Failed to create user");
+            }
+            let carol = repo
+                .find_by_email("carol@example.com")
+                .await
+                .expect("Failed to find user");
+            repo.delete(carol.id).await.expect("Failed to delete user");
+
+            let (users, total_count) = repo
+                .list(UserFilter {
+                    name: Some("ALI".to_string()),
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+            assert_eq!(total_count, 2);
+            assert_eq!(users[0].name, "Alice");
+            assert_eq!(users[1].name, "alicia");
+
+            // 論理削除されたユーザーは除外され、全件数はページに依存しない
+            let (users, total_count) = repo
+                .list(UserFilter {
+                    page: 1,
+                    per_page: 1,
+                    address: Some("tokyo".to_string()),
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+            assert_eq!(total_count, 2);
+            assert_eq!(users.len(), 1);
+            assert_eq!(users[0].name, "alicia");
+
+            let (_, total_count) = repo
+                .list(UserFilter {
+                    include_deleted: true,
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to list users");
+            assert_eq!(total_count, 4);
+        }
+
         #[tokio::test]
         async fn test_mock_update() {
             let repo = mock::MockUserRepository::new();
@@ -518,6 +1003,7 @@ mod tests {
                     None,
                     None,
                     None,
+                    None, // password_hash
                 )
                 .await
                 .expect("Failed to create user");
@@ -531,6 +1017,7 @@ mod tests {
                     None, // age は更新しない
                     None, // gender は更新しない
                     None, // address は更新しない
+                    None, // handle は更新しない
                 )
                 .await
                 .expect("Failed to update user");
@@ -550,6 +1037,7 @@ mod tests {
                     None,
                     None,
                     None,
+                    None, // password_hash
                 )
                 .await
                 .expect("Failed to create user");
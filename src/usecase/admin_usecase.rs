@@ -0,0 +1,106 @@
+use crate::domain::entity::sea_orm_active_enums::UserRole;
+use crate::domain::entity::users::Model as User;
+use crate::domain::repository::message::MessageRepository;
+use crate::domain::repository::post::PostRepository;
+use crate::domain::repository::user::{UserFilter, UserRepository};
+use crate::usecase::policy::{ensure_admin, ensure_moderator, AccessError, Caller};
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait AdminUseCase {
+    /// 論理削除されたユーザーを含むユーザー一覧を取得し、(ユーザー一覧, 全件数) を返します（管理者のみ）。
+    async fn list_all_users(
+        &self,
+        caller: Caller,
+        filter: UserFilter,
+    ) -> Result<(Vec<User>, i32), AccessError>;
+
+    /// 論理削除されたユーザーを復元します（管理者のみ）。
+    async fn restore_user(&self, caller: Caller, user_id: i32) -> Result<User, AccessError>;
+
+    /// ユーザーを物理削除します（管理者のみ）。
+    async fn hard_delete_user(&self, caller: Caller, user_id: i32) -> Result<(), AccessError>;
+
+    /// ユーザーのロールを変更します（管理者のみ）。
+    async fn set_user_role(
+        &self,
+        caller: Caller,
+        user_id: i32,
+        role: UserRole,
+    ) -> Result<User, AccessError>;
+
+    /// 任意の投稿を削除します（モデレーター以上）。
+    async fn remove_post(&self, caller: Caller, post_id: i32) -> Result<(), AccessError>;
+
+    /// 任意のメッセージを削除します（モデレーター以上）。
+    async fn remove_message(&self, caller: Caller, message_id: i32) -> Result<bool, AccessError>;
+}
+
+pub struct AdminUseCaseImpl<U, P, M> {
+    users: U,
+    posts: P,
+    messages: M,
+}
+
+impl<U: UserRepository, P: PostRepository, M: MessageRepository> AdminUseCaseImpl<U, P, M> {
+    pub fn new(users: U, posts: P, messages: M) -> Self {
+        Self {
+            users,
+            posts,
+            messages,
+        }
+    }
+}
+
+#[async_trait]
+impl<U, P, M> AdminUseCase for AdminUseCaseImpl<U, P, M>
+where
+    U: UserRepository + Send + Sync,
+    P: PostRepository + Send + Sync,
+    M: MessageRepository + Send + Sync,
+{
+    async fn list_all_users(
+        &self,
+        caller: Caller,
+        filter: UserFilter,
+    ) -> Result<(Vec<User>, i32), AccessError> {
+        ensure_admin(&caller)?;
+        Ok(self
+            .users
+            .list(UserFilter {
+                include_deleted: true,
+                ..filter
+            })
+            .await?)
+    }
+
+    async fn restore_user(&self, caller: Caller, user_id: i32) -> Result<User, AccessError> {
+        ensure_admin(&caller)?;
+        Ok(self.users.restore(user_id).await?)
+    }
+
+    async fn hard_delete_user(&self, caller: Caller, user_id: i32) -> Result<(), AccessError> {
+        ensure_admin(&caller)?;
+        Ok(self.users.hard_delete(user_id).await?)
+    }
+
+    async fn set_user_role(
+        &self,
+        caller: Caller,
+        user_id: i32,
+        role: UserRole,
+    ) -> Result<User, AccessError> {
+        ensure_admin(&caller)?;
+        Ok(self.users.update_role(user_id, role).await?)
+    }
+
+    async fn remove_post(&self, caller: Caller, post_id: i32) -> Result<(), AccessError> {
+        ensure_moderator(&caller)?;
+        Ok(self.posts.delete(post_id).await?)
+    }
+
+    async fn remove_message(&self, caller: Caller, message_id: i32) -> Result<bool, AccessError> {
+        ensure_moderator(&caller)?;
+        Ok(self.messages.delete_message(message_id).await?)
+    }
+}
@@ -0,0 +1,277 @@
+use crate::domain::entity::attachments::Model as Attachment;
+use crate::domain::repository::attachment::{AttachmentRepository, NewAttachment};
+use crate::infra::blob_store::{BlobStore, BlobStoreError};
+use async_trait::async_trait;
+use bytes::Bytes;
+use sea_orm::DbErr;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// 添付ファイル 1 件の既定の最大バイト数
+pub const DEFAULT_MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+/// 1 件のメッセージ・投稿に添付できる最大数
+pub const MAX_ATTACHMENTS: usize = 10;
+/// ファイル名の最大文字数
+pub const MAX_FILENAME_CHARS: usize = 255;
+
+#[derive(Debug, Error)]
+pub enum AttachmentError {
+    #[error("invalid filename")]
+    InvalidFilename,
+    #[error("invalid mime_type")]
+    InvalidMimeType,
+    #[error("attachment is empty")]
+    Empty,
+    #[error("attachment exceeds {0} bytes")]
+    TooLarge(usize),
+    /// 添付ファイルが存在しない、または閲覧できない
+    #[error("attachment not found")]
+    NotFound,
+    #[error(transparent)]
+    Storage(#[from] BlobStoreError),
+    #[error(transparent)]
+    Database(#[from] DbErr),
+}
+
+/// 添付ファイルのファイル名として受け付けるかどうかを判定します。
+/// パス区切りや制御文字を含むもの、`.`・`..`、長すぎるものは受け付けません。
+pub fn is_valid_filename(filename: &str) -> bool {
+    !filename.is_empty()
+        && filename != "."
+        && filename != ".."
+        && filename.chars().count() <= MAX_FILENAME_CHARS
+        && !filename
+            .chars()
+            .any(|c| c == '/' || c == '\\' || c.is_control())
+}
+
+/// `image/png` のような `type/subtype` 形式の MIME タイプを小文字に正規化します。
+/// パラメーター（`; charset=...` など）付きのものや形式が正しくないものは `None` を返します。
+pub fn normalize_mime_type(mime_type: &str) -> Option<String> {
+    // RFC 6838 の restricted-name
+    let is_token = |s: &str| {
+        !s.is_empty()
+            && s.len() <= 127
+            && s.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+    };
+    let (kind, subtype) = mime_type.split_once('/')?;
+    (is_token(kind) && is_token(subtype)).then(|| mime_type.to_ascii_lowercase())
+}
+
+// 同じ内容の添付ファイルは同じキーに保存する（ディレクトリあたりのファイル数を抑えるため先頭 2 文字で分ける）
+fn storage_key(checksum: &str) -> String {
+    format!("{}/{}", &checksum[..2], &checksum[2..])
+}
+
+#[async_trait]
+pub trait AttachmentUseCase {
+    /// アップロードされた内容を保存し、まだどこにも紐付いていない添付ファイルとして記録します。
+    /// 送信・投稿時に ID を指定すると、そのメッセージ・投稿に紐付きます。
+    async fn upload(
+        &self,
+        uploader_id: i32,
+        filename: String,
+        mime_type: String,
+        data: Bytes,
+    ) -> Result<Attachment, AttachmentError>;
+
+    /// `user_id` のユーザーが閲覧できる添付ファイルのメタデータと内容を取得します。
+    async fn download(&self, user_id: i32, id: i32)
+        -> Result<(Attachment, Bytes), AttachmentError>;
+
+    /// 添付ファイル 1 件の最大バイト数
+    fn max_bytes(&self) -> usize;
+}
+
+pub struct AttachmentUseCaseImpl<R> {
+    repository: R,
+    store: Arc<dyn BlobStore>,
+    max_bytes: usize,
+}
+
+impl<R: AttachmentRepository> AttachmentUseCaseImpl<R> {
+    /// `max_bytes` を超える添付ファイルはアップロードできません。
+    pub fn new(repository: R, store: Arc<dyn BlobStore>, max_bytes: usize) -> Self {
+        Self {
+            repository,
+            store,
+            max_bytes,
+        }
+    }
+}
+
+#[async_trait]
+impl<R> AttachmentUseCase for AttachmentUseCaseImpl<R>
+where
+    R: AttachmentRepository + Send + Sync,
+{
+    async fn upload(
+        &self,
+        uploader_id: i32,
+        filename: String,
+        mime_type: String,
+        data: Bytes,
+    ) -> Result<Attachment, AttachmentError> {
+        if !is_valid_filename(&filename) {
+            return Err(AttachmentError::InvalidFilename);
+        }
+        let mime_type = normalize_mime_type(&mime_type).ok_or(AttachmentError::InvalidMimeType)?;
+        if data.is_empty() {
+            return Err(AttachmentError::Empty);
+        }
+        if data.len() > self.max_bytes {
+            return Err(AttachmentError::TooLarge(self.max_bytes));
+        }
+
+        let checksum = hex::encode(Sha256::digest(&data));
+        let storage_key = storage_key(&checksum);
+        let size_bytes = data.len() as i64;
+        // 記録に失敗した場合に残る本体は、同じ内容が再度アップロードされた際に上書きされる
+        self.store.put(&storage_key, data, &mime_type).await?;
+        Ok(self
+            .repository
+            .insert(NewAttachment {
+                uploader_id,
+                storage_key,
+                filename,
+                mime_type,
+                size_bytes,
+                checksum,
+            })
+            .await?)
+    }
+
+    async fn download(
+        &self,
+        user_id: i32,
+        id: i32,
+    ) -> Result<(Attachment, Bytes), AttachmentError> {
+        let attachment = self
+            .repository
+            .find_visible(user_id, id)
+            .await?
+            .ok_or(AttachmentError::NotFound)?;
+        let data = self.store.get(&attachment.storage_key).await?;
+        Ok((attachment, data))
+    }
+
+    fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::blob_store::LocalBlobStore;
+    use crate::repository::attachment_repository::mock::MockAttachmentRepository;
+
+    fn new_usecase(
+        dir: &tempfile::TempDir,
+        max_bytes: usize,
+    ) -> AttachmentUseCaseImpl<MockAttachmentRepository> {
+        AttachmentUseCaseImpl::new(
+            MockAttachmentRepository::new(),
+            Arc::new(LocalBlobStore::new(dir.path())),
+            max_bytes,
+        )
+    }
+
+    #[test]
+    fn test_is_valid_filename() {
+        assert!(is_valid_filename("photo.png"));
+        assert!(is_valid_filename("写真 1.jpeg"));
+        assert!(!is_valid_filename(""));
+        assert!(!is_valid_filename(".."));
+        assert!(!is_valid_filename("../photo.png"));
+        assert!(!is_valid_filename("dir\\photo.png"));
+        assert!(!is_valid_filename("photo\n.png"));
+        assert!(!is_valid_filename(&"a".repeat(MAX_FILENAME_CHARS + 1)));
+    }
+
+    #[test]
+    fn test_normalize_mime_type() {
+        assert_eq!(
+            normalize_mime_type("image/PNG").as_deref(),
+            Some("image/png")
+        );
+        assert_eq!(
+            normalize_mime_type("application/vnd.ms-excel").as_deref(),
+            Some("application/vnd.ms-excel")
+        );
+        assert_eq!(normalize_mime_type("image"), None);
+        assert_eq!(normalize_mime_type("image/"), None);
+        assert_eq!(normalize_mime_type("text/plain; charset=utf-8"), None);
+        assert_eq!(normalize_mime_type("image/png/x"), None);
+    }
+
+    #[tokio::test]
+    async fn test_upload_and_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let usecase = new_usecase(&dir, 16);
+
+        let attachment = usecase
+            .upload(
+                1,
+                "hello.txt".to_string(),
+                "Text/Plain".to_string(),
+                Bytes::from_static(b"hello"),
+            )
+            .await
+            .expect("Upload failed");
+        assert_eq!(attachment.mime_type, "text/plain");
+        assert_eq!(attachment.size_bytes, 5);
+        assert_eq!(
+            attachment.checksum,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert!(dir.path().join(&attachment.storage_key).exists());
+
+        let (found, data) = usecase
+            .download(1, attachment.id)
+            .await
+            .expect("Download failed");
+        assert_eq!(found.id, attachment.id);
+        assert_eq!(data, "hello");
+        assert!(matches!(
+            usecase.download(2, attachment.id).await,
+            Err(AttachmentError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_invalid_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let usecase = new_usecase(&dir, 4);
+        let upload = |filename: &str, mime_type: &str, data: &'static [u8]| {
+            usecase.upload(
+                1,
+                filename.to_string(),
+                mime_type.to_string(),
+                Bytes::from_static(data),
+            )
+        };
+
+        assert!(matches!(
+            upload("../a.txt", "text/plain", b"a").await,
+            Err(AttachmentError::InvalidFilename)
+        ));
+        assert!(matches!(
+            upload("a.txt", "text", b"a").await,
+            Err(AttachmentError::InvalidMimeType)
+        ));
+        assert!(matches!(
+            upload("a.txt", "text/plain", b"").await,
+            Err(AttachmentError::Empty)
+        ));
+        assert!(matches!(
+            upload("a.txt", "text/plain", b"hello").await,
+            Err(AttachmentError::TooLarge(4))
+        ));
+        // 拒否したアップロードは保存しない
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::domain::entity::users::Model as User;
+use crate::domain::repository::refresh_token::RefreshTokenRepository;
+use crate::domain::repository::user::{is_unique_violation, UserRepository};
+use crate::infra::password::{dummy_password_hash, hash_password, verify_password};
+use crate::infra::token::{generate_refresh_token, hash_refresh_token, TokenManager};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::DbErr;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// パスワードの最小文字数
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    /// メールアドレスまたはパスワードが一致しない
+    #[error("invalid email or password")]
+    InvalidCredentials,
+    #[error("password must be at least {MIN_PASSWORD_LENGTH} characters")]
+    WeakPassword,
+    #[error("email is already registered")]
+    EmailTaken,
+    /// リフレッシュトークンが存在しない、失効済み、または期限切れ
+    #[error("invalid or expired refresh token")]
+    InvalidToken,
+    #[error("failed to hash password: {0}")]
+    Hash(String),
+    #[error("failed to issue access token: {0}")]
+    Token(#[from] jsonwebtoken::errors::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Repository(#[from] DbErr),
+}
+
+/// ログイン・リフレッシュ時に発行されるトークン一式
+#[derive(Clone, Debug)]
+pub struct AuthTokens {
+    pub user_id: i32,
+    pub access_token: String,
+    pub refresh_token: String,
+    /// アクセストークンの有効期間（秒）
+    pub expires_in: i64,
+}
+
+#[async_trait]
+pub trait AuthUseCase {
+    /// パスワード付きでユーザーを登録します。
+    async fn register(
+        &self,
+        name: String,
+        email: String,
+        password: String,
+    ) -> Result<User, AuthError>;
+
+    /// メールアドレスとパスワードを検証し、アクセストークンとリフレッシュトークンを発行します。
+    async fn login(&self, email: String, password: String) -> Result<AuthTokens, AuthError>;
+
+    /// リフレッシュトークンを失効させ、新しいトークン一式を発行します（ローテーション）。
+    ///
+    /// 失効済みのトークンが再利用された場合は漏洩とみなし、そのユーザーの全トークンを失効させます。
+    async fn refresh(&self, refresh_token: String) -> Result<AuthTokens, AuthError>;
+
+    /// リフレッシュトークンを失効させます。
+    /// - `all_devices`: true の場合はユーザーの全リフレッシュトークンを失効させます。
+    ///
+    /// 失効させたトークンがあった場合は true を返します。
+    async fn logout(&self, refresh_token: String, all_devices: bool) -> Result<bool, AuthError>;
+}
+
+pub struct AuthUseCaseImpl<R, T> {
+    repository: R,
+    refresh_tokens: T,
+    tokens: Arc<TokenManager>,
+}
+
+impl<R: UserRepository, T: RefreshTokenRepository + Send + Sync> AuthUseCaseImpl<R, T> {
+    pub fn new(repository: R, refresh_tokens: T, tokens: Arc<TokenManager>) -> Self {
+        Self {
+            repository,
+            refresh_tokens,
+            tokens,
+        }
+    }
+
+    // アクセストークンを発行し、新しいリフレッシュトークンを保存する
+    async fn issue_tokens(&self, user: &User) -> Result<AuthTokens, AuthError> {
+        let user_id = user.id;
+        let access_token = self.tokens.issue_access_token(user_id, user.role)?;
+        let refresh_token = generate_refresh_token();
+        let expires_at = Utc::now().naive_utc() + self.tokens.refresh_token_ttl();
+        self.refresh_tokens
+            .create(user_id, hash_refresh_token(&refresh_token), expires_at)
+            .await?;
+
+        Ok(AuthTokens {
+            user_id,
+            access_token,
+            refresh_token,
+            expires_in: self.tokens.access_token_ttl().num_seconds(),
+        })
+    }
+}
+
+#[async_trait]
+impl<R, T> AuthUseCase for AuthUseCaseImpl<R, T>
+where
+    R: UserRepository + Send + Sync,
+    T: RefreshTokenRepository + Send + Sync,
+{
+    async fn register(
+        &self,
+        name: String,
+        email: String,
+        password: String,
+    ) -> Result<User, AuthError> {
+        if password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Err(AuthError::WeakPassword);
+        }
+
+        match self.repository.find_by_email(&email).await {
+            Ok(_) => return Err(AuthError::EmailTaken),
+            Err(sqlx::Error::RowNotFound) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        // Argon2 の計算には数十ミリ秒かかるため、ランタイムのワーカースレッドを塞がないよう別スレッドで行う
+        let password_hash = tokio::task::spawn_blocking(move || hash_password(&password))
+            .await
+            .map_err(|e| AuthError::Hash(e.to_string()))?
+            .map_err(|e| AuthError::Hash(e.to_string()))?;
+        // 確認後に同じメールアドレスで登録された場合は一意制約で検出する
+        let user = self
+            .repository
+            .create(name, email, None, None, None, None, Some(password_hash))
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    AuthError::EmailTaken
+                } else {
+                    e.into()
+                }
+            })?;
+        Ok(user)
+    }
+
+    async fn login(&self, email: String, password: String) -> Result<AuthTokens, AuthError> {
+        // ユーザーが存在しない場合もパスワード不一致と区別しない
+        let user = match self.repository.find_by_email(&email).await {
+            Ok(user) => Some(user),
+            Err(sqlx::Error::RowNotFound) => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        // ハッシュ化と同様に検証も別スレッドで行う。照合するハッシュがない場合も
+        // ダミーのハッシュを検証し、応答時間からユーザーの有無を区別できないようにする
+        let hash = user.as_ref().and_then(|u| u.password_hash.clone());
+        let verified = tokio::task::spawn_blocking(move || match hash {
+            Some(hash) => verify_password(&password, &hash),
+            None => {
+                verify_password(&password, dummy_password_hash());
+                false
+            }
+        })
+        .await
+        .map_err(|e| AuthError::Hash(e.to_string()))?;
+        match user {
+            Some(user) if verified => self.issue_tokens(&user).await,
+            _ => Err(AuthError::InvalidCredentials),
+        }
+    }
+
+    async fn refresh(&self, refresh_token: String) -> Result<AuthTokens, AuthError> {
+        let token = self
+            .refresh_tokens
+            .find_by_hash(&hash_refresh_token(&refresh_token))
+            .await?
+            .ok_or(AuthError::InvalidToken)?;
+
+        if token.revoked_at.is_some() {
+            self.refresh_tokens
+                .revoke_all_for_user(token.user_id)
+                .await?;
+            return Err(AuthError::InvalidToken);
+        }
+        if token.expires_at <= Utc::now().naive_utc() {
+            return Err(AuthError::InvalidToken);
+        }
+        // 同じトークンで同時にリフレッシュされた場合は先に失効させた方のみ成功させる
+        if !self.refresh_tokens.revoke(token.id).await? {
+            return Err(AuthError::InvalidToken);
+        }
+
+        // 最新のロールでアクセストークンを発行する。削除済みユーザーには発行しない
+        let user = match self.repository.get_by_id(token.user_id).await {
+            Ok(user) if user.deleted_at.is_none() => user,
+            Ok(_) | Err(sqlx::Error::RowNotFound) => return Err(AuthError::InvalidToken),
+            Err(e) => return Err(e.into()),
+        };
+        self.issue_tokens(&user).await
+    }
+
+    async fn logout(&self, refresh_token: String, all_devices: bool) -> Result<bool, AuthError> {
+        let Some(token) = self
+            .refresh_tokens
+            .find_by_hash(&hash_refresh_token(&refresh_token))
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        if all_devices {
+            Ok(self
+                .refresh_tokens
+                .revoke_all_for_user(token.user_id)
+                .await?
+                > 0)
+        } else {
+            Ok(self.refresh_tokens.revoke(token.id).await?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::refresh_token_repository::mock::MockRefreshTokenRepository;
+    use crate::repository::user_repository::mock::MockUserRepository;
+    use chrono::Duration;
+
+    fn new_usecase() -> AuthUseCaseImpl<MockUserRepository, MockRefreshTokenRepository> {
+        AuthUseCaseImpl::new(
+            MockUserRepository::new(),
+            MockRefreshTokenRepository::new(),
+            Arc::new(TokenManager::new(
+                b"test-secret",
+                Duration::minutes(15),
+                Duration::days(30),
+            )),
+        )
+    }
+
+    async fn register_and_login(usecase: &impl AuthUseCase) -> AuthTokens {
+        usecase
+            .register(
+                "Token User".to_string(),
+                "token@example.com".to_string(),
+                "password123".to_string(),
+            )
+            .await
+            .expect("Failed to register");
+        usecase
+            .login("token@example.com".to_string(), "password123".to_string())
+            .await
+            .expect("Failed to login")
+    }
+
+    #[tokio::test]
+    async fn test_register_and_login() {
+        let usecase = new_usecase();
+
+        let user = usecase
+            .register(
+                "Auth User".to_string(),
+                "auth@example.com".to_string(),
+                "password123".to_string(),
+            )
+            .await
+            .expect("Failed to register");
+        assert_ne!(user.password_hash.as_deref(), Some("password123"));
+
+        let tokens = usecase
+            .login("auth@example.com".to_string(), "password123".to_string())
+            .await
+            .expect("Failed to login");
+        assert_eq!(tokens.user_id, user.id);
+        assert_eq!(tokens.expires_in, 15 * 60);
+        let claims = usecase
+            .tokens
+            .verify_access_token(&tokens.access_token)
+            .expect("Invalid access token");
+        assert_eq!(claims.user_id(), Some(user.id));
+
+        let result = usecase
+            .login("auth@example.com".to_string(), "wrong-password".to_string())
+            .await;
+        assert!(matches!(result, Err(AuthError::InvalidCredentials)));
+
+        let result = usecase
+            .login("missing@example.com".to_string(), "password123".to_string())
+            .await;
+        assert!(matches!(result, Err(AuthError::InvalidCredentials)));
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_weak_password_and_duplicate_email() {
+        let usecase = new_usecase();
+
+        let result = usecase
+            .register(
+                "Weak".to_string(),
+                "weak@example.com".to_string(),
+                "short".to_string(),
+            )
+            .await;
+        assert!(matches!(result, Err(AuthError::WeakPassword)));
+
+        usecase
+            .register(
+                "First".to_string(),
+                "dup@example.com".to_string(),
+                "password123".to_string(),
+            )
+            .await
+            .expect("Failed to register");
+        let result = usecase
+            .register(
+                "Second".to_string(),
+                "dup@example.com".to_string(),
+                "password123".to_string(),
+            )
+            .await;
+        assert!(matches!(result, Err(AuthError::EmailTaken)));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_token_and_detects_reuse() {
+        let usecase = new_usecase();
+        let tokens = register_and_login(&usecase).await;
+
+        let rotated = usecase
+            .refresh(tokens.refresh_token.clone())
+            .await
+            .expect("Failed to refresh");
+        assert_eq!(rotated.user_id, tokens.user_id);
+        assert_ne!(rotated.refresh_token, tokens.refresh_token);
+
+        // 使用済みのトークンを再利用すると、ローテーション後のトークンも失効する
+        let result = usecase.refresh(tokens.refresh_token).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+        let result = usecase.refresh(rotated.refresh_token).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+
+        let result = usecase.refresh("unknown".to_string()).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_refresh_token() {
+        let usecase = new_usecase();
+        let tokens = register_and_login(&usecase).await;
+        let other = usecase
+            .login("token@example.com".to_string(), "password123".to_string())
+            .await
+            .expect("Failed to login");
+
+        assert!(usecase
+            .logout(tokens.refresh_token.clone(), false)
+            .await
+            .expect("Failed to logout"));
+        assert!(!usecase
+            .logout(tokens.refresh_token.clone(), false)
+            .await
+            .expect("Failed to logout"));
+
+        // 他の端末のトークンは全端末ログアウトで失効する
+        assert!(usecase
+            .logout(other.refresh_token.clone(), true)
+            .await
+            .expect("Failed to logout"));
+        let result = usecase.refresh(tokens.refresh_token).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+        let result = usecase.refresh(other.refresh_token).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+    }
+}
@@ -0,0 +1,437 @@
+use crate::domain::entity::comments::Model as Comment;
+use crate::domain::repository::comment::{CommentRepository, NewComment};
+use crate::infra::pagination::clamp_per_page;
+use crate::usecase::policy::{ensure_comment_author, ensure_comment_owner, AccessError, Caller};
+use async_trait::async_trait;
+use sea_orm::DbErr;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// per_page が指定されなかった場合の 1 ページあたりのスレッド数
+pub const DEFAULT_THREADS_PER_PAGE: i32 = 20;
+/// 1 ページあたりの最大スレッド数
+pub const MAX_THREADS_PER_PAGE: i32 = 100;
+/// コメント一覧で各スレッドに含める返信の件数（続きは `list_replies` で取得する）
+pub const REPLIES_PER_THREAD: u64 = 3;
+/// per_page が指定されなかった場合の 1 ページあたりの返信数
+pub const DEFAULT_REPLIES_PER_PAGE: i32 = 20;
+/// 1 ページあたりの最大返信数
+pub const MAX_REPLIES_PER_PAGE: i32 = 100;
+
+#[derive(Debug, Error)]
+pub enum CommentError {
+    #[error("post not found")]
+    PostNotFound,
+    /// コメントが存在しない、または論理削除されている
+    #[error("comment not found")]
+    CommentNotFound,
+    /// 返信先のコメントが別の投稿のもの、または論理削除されている
+    #[error("parent comment is not a comment on this post")]
+    InvalidParent,
+    #[error("permission denied")]
+    PermissionDenied,
+    #[error(transparent)]
+    Database(#[from] DbErr),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+impl From<AccessError> for CommentError {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::PermissionDenied => CommentError::PermissionDenied,
+            AccessError::Database(e) => CommentError::Database(e),
+            AccessError::Sqlx(e) => CommentError::Sqlx(e),
+        }
+    }
+}
+
+/// トップレベルのコメントと、そのスレッドに属する返信
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommentThread {
+    pub comment: Comment,
+    /// 古い順に先頭の `REPLIES_PER_THREAD` 件まで。返信先は各コメントの `parent_comment_id` で表す
+    pub replies: Vec<Comment>,
+    /// スレッドに属する返信の総数
+    pub reply_count: i32,
+    /// `replies` に含まれなかった返信がある場合に `list_replies` へ渡す `after_id`
+    pub next_reply_after_id: Option<i32>,
+}
+
+#[async_trait]
+pub trait CommentUseCase {
+    /// 投稿にコメントします。`parent_comment_id` を指定した場合はそのコメントへの返信になります。
+    async fn create_comment(
+        &self,
+        user_id: i32,
+        post_id: i32,
+        parent_comment_id: Option<i32>,
+        body: String,
+    ) -> Result<Comment, CommentError>;
+
+    /// 投稿のコメントをスレッド単位で古い順に 1 ページ分取得します。
+    /// 各スレッドの返信は先頭の `REPLIES_PER_THREAD` 件までで、返信の総数を合わせて返します。
+    /// 続きがある場合は次のページの `after_id` を合わせて返します。
+    async fn list_comments(
+        &self,
+        post_id: i32,
+        after_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<CommentThread>, Option<i32>), CommentError>;
+
+    /// トップレベルのコメント `root_comment_id` のスレッドの返信を古い順に 1 ページ分取得します。
+    /// 続きがある場合は次のページの `after_id` を合わせて返します。
+    async fn list_replies(
+        &self,
+        root_comment_id: i32,
+        after_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<Comment>, Option<i32>), CommentError>;
+
+    /// コメントの本文を編集します。投稿者本人以外は `PermissionDenied` を返します。
+    async fn update_comment(
+        &self,
+        caller: Caller,
+        id: i32,
+        body: String,
+    ) -> Result<Comment, CommentError>;
+
+    /// コメントを論理削除します。投稿者本人（またはモデレーター）以外は `PermissionDenied` を返します。
+    async fn delete_comment(&self, caller: Caller, id: i32) -> Result<(), CommentError>;
+}
+
+pub struct CommentUseCaseImpl<R> {
+    repository: R,
+}
+
+impl<R: CommentRepository> CommentUseCaseImpl<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    // 論理削除されたコメントは存在しないものとして扱う
+    async fn find_active_comment(&self, id: i32) -> Result<Comment, CommentError> {
+        match self.repository.get_by_id(id).await? {
+            Some(comment) if comment.deleted_at.is_none() => Ok(comment),
+            _ => Err(CommentError::CommentNotFound),
+        }
+    }
+}
+
+// 1 件多く取得した結果を 1 ページ分に切り詰め、続きがあれば次のページの after_id を返す
+fn split_page(items: &mut Vec<Comment>, per_page: usize) -> Option<i32> {
+    if items.len() > per_page {
+        items.truncate(per_page);
+        items.last().map(|c| c.id)
+    } else {
+        None
+    }
+}
+
+// 論理削除されたコメントはスレッドの構造を保つため、本文を伏せて返す
+fn redact(mut comment: Comment) -> Comment {
+    if comment.deleted_at.is_some() {
+        comment.body.clear();
+    }
+    comment
+}
+
+#[async_trait]
+impl<R: CommentRepository + Send + Sync> CommentUseCase for CommentUseCaseImpl<R> {
+    async fn create_comment(
+        &self,
+        user_id: i32,
+        post_id: i32,
+        parent_comment_id: Option<i32>,
+        body: String,
+    ) -> Result<Comment, CommentError> {
+        if !self.repository.post_exists(post_id).await? {
+            return Err(CommentError::PostNotFound);
+        }
+
+        let root_comment_id = match parent_comment_id {
+            Some(parent_id) => {
+                let parent = self
+                    .find_active_comment(parent_id)
+                    .await
+                    .map_err(|_| CommentError::InvalidParent)?;
+                if parent.post_id != post_id {
+                    return Err(CommentError::InvalidParent);
+                }
+                Some(parent.root_comment_id.unwrap_or(parent.id))
+            }
+            None => None,
+        };
+
+        Ok(self
+            .repository
+            .insert(NewComment {
+                post_id,
+                user_id,
+                parent_comment_id,
+                root_comment_id,
+                body,
+            })
+            .await?)
+    }
+
+    async fn list_comments(
+        &self,
+        post_id: i32,
+        after_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<CommentThread>, Option<i32>), CommentError> {
+        if !self.repository.post_exists(post_id).await? {
+            return Err(CommentError::PostNotFound);
+        }
+        let per_page =
+            clamp_per_page(per_page, DEFAULT_THREADS_PER_PAGE, MAX_THREADS_PER_PAGE) as usize;
+
+        // 次のページの有無を判定するため 1 件多く取得する
+        let mut roots = self
+            .repository
+            .find_threads(post_id, after_id, per_page as u64 + 1)
+            .await?;
+        let next_after_id = split_page(&mut roots, per_page);
+
+        let root_ids: Vec<i32> = roots.iter().map(|c| c.id).collect();
+        let mut replies_by_root: HashMap<i32, Vec<Comment>> = HashMap::new();
+        for reply in self
+            .repository
+            .find_replies(&root_ids, REPLIES_PER_THREAD)
+            .await?
+        {
+            if let Some(root_id) = reply.root_comment_id {
+                replies_by_root
+                    .entry(root_id)
+                    .or_default()
+                    .push(redact(reply));
+            }
+        }
+        let reply_counts = self.repository.count_replies(&root_ids).await?;
+        let threads = roots
+            .into_iter()
+            .map(|root| {
+                let replies = replies_by_root.remove(&root.id).unwrap_or_default();
+                let reply_count = reply_counts.get(&root.id).copied().unwrap_or(0) as i32;
+                let next_reply_after_id = if reply_count as usize > replies.len() {
+                    replies.last().map(|c| c.id)
+                } else {
+                    None
+                };
+                CommentThread {
+                    replies,
+                    reply_count,
+                    next_reply_after_id,
+                    comment: redact(root),
+                }
+            })
+            .collect();
+        Ok((threads, next_after_id))
+    }
+
+    async fn list_replies(
+        &self,
+        root_comment_id: i32,
+        after_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<Comment>, Option<i32>), CommentError> {
+        // 論理削除されたトップレベルのコメントでも返信は残るため、削除済みかどうかは問わない
+        match self.repository.get_by_id(root_comment_id).await? {
+            Some(root) if root.root_comment_id.is_none() => {}
+            _ => return Err(CommentError::CommentNotFound),
+        }
+        let per_page =
+            clamp_per_page(per_page, DEFAULT_REPLIES_PER_PAGE, MAX_REPLIES_PER_PAGE) as usize;
+
+        let mut replies = self
+            .repository
+            .find_thread_replies(root_comment_id, after_id, per_page as u64 + 1)
+            .await?;
+        let next_after_id = split_page(&mut replies, per_page);
+        Ok((replies.into_iter().map(redact).collect(), next_after_id))
+    }
+
+    async fn update_comment(
+        &self,
+        caller: Caller,
+        id: i32,
+        body: String,
+    ) -> Result<Comment, CommentError> {
+        let comment = self.find_active_comment(id).await?;
+        ensure_comment_author(&caller, &comment)?;
+        if comment.body == body {
+            return Ok(comment);
+        }
+        Ok(self.repository.update_body(id, body).await?)
+    }
+
+    async fn delete_comment(&self, caller: Caller, id: i32) -> Result<(), CommentError> {
+        let comment = self.find_active_comment(id).await?;
+        ensure_comment_owner(&caller, &comment)?;
+        self.repository.soft_delete(id).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entity::sea_orm_active_enums::UserRole;
+    use crate::repository::comment_repository::mock::MockCommentRepository;
+
+    const POST_ID: i32 = 1;
+    const OTHER_POST_ID: i32 = 2;
+
+    fn new_usecase() -> CommentUseCaseImpl<MockCommentRepository> {
+        CommentUseCaseImpl::new(MockCommentRepository::new(&[POST_ID, OTHER_POST_ID]))
+    }
+
+    async fn comment(
+        usecase: &CommentUseCaseImpl<MockCommentRepository>,
+        post_id: i32,
+        parent_comment_id: Option<i32>,
+    ) -> Comment {
+        usecase
+            .create_comment(1, post_id, parent_comment_id, "comment".to_string())
+            .await
+            .expect("Failed to create comment")
+    }
+
+    #[tokio::test]
+    async fn test_replies_are_grouped_into_threads() {
+        let usecase = new_usecase();
+        let first = comment(&usecase, POST_ID, None).await;
+        let second = comment(&usecase, POST_ID, None).await;
+        let reply = comment(&usecase, POST_ID, Some(first.id)).await;
+        let nested = comment(&usecase, POST_ID, Some(reply.id)).await;
+        // 返信への返信もトップレベルのコメントのスレッドに属する
+        assert_eq!(nested.parent_comment_id, Some(reply.id));
+        assert_eq!(nested.root_comment_id, Some(first.id));
+
+        let (threads, next_after_id) = usecase
+            .list_comments(POST_ID, None, 1)
+            .await
+            .expect("List comments failed");
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].comment.id, first.id);
+        assert_eq!(
+            threads[0].replies.iter().map(|c| c.id).collect::<Vec<_>>(),
+            vec![reply.id, nested.id]
+        );
+        assert_eq!(next_after_id, Some(first.id));
+
+        let (threads, next_after_id) = usecase
+            .list_comments(POST_ID, next_after_id, 1)
+            .await
+            .expect("List comments failed");
+        assert_eq!(threads[0].comment.id, second.id);
+        assert!(threads[0].replies.is_empty());
+        assert_eq!(threads[0].reply_count, 0);
+        assert_eq!(threads[0].next_reply_after_id, None);
+        assert_eq!(next_after_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_replies_are_capped_per_thread() {
+        let usecase = new_usecase();
+        let root = comment(&usecase, POST_ID, None).await;
+        let mut reply_ids = Vec::new();
+        for _ in 0..REPLIES_PER_THREAD + 2 {
+            reply_ids.push(comment(&usecase, POST_ID, Some(root.id)).await.id);
+        }
+
+        let (threads, _) = usecase
+            .list_comments(POST_ID, None, 0)
+            .await
+            .expect("List comments failed");
+        assert_eq!(
+            threads[0].replies.iter().map(|c| c.id).collect::<Vec<_>>(),
+            reply_ids[..REPLIES_PER_THREAD as usize]
+        );
+        assert_eq!(threads[0].reply_count, reply_ids.len() as i32);
+
+        // 一覧に含まれなかった返信はスレッドのカーソルで続きから取得する
+        let (replies, next_after_id) = usecase
+            .list_replies(root.id, threads[0].next_reply_after_id, 1)
+            .await
+            .expect("List replies failed");
+        assert_eq!(replies[0].id, reply_ids[REPLIES_PER_THREAD as usize]);
+        let (replies, next_after_id) = usecase
+            .list_replies(root.id, next_after_id, 1)
+            .await
+            .expect("List replies failed");
+        assert_eq!(replies[0].id, *reply_ids.last().unwrap());
+        assert_eq!(next_after_id, None);
+
+        // 返信を起点にしたスレッドは存在しない
+        assert!(matches!(
+            usecase.list_replies(reply_ids[0], None, 0).await,
+            Err(CommentError::CommentNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_missing_post_and_invalid_parent() {
+        let usecase = new_usecase();
+        let other = comment(&usecase, OTHER_POST_ID, None).await;
+
+        assert!(matches!(
+            usecase.create_comment(1, 999, None, "x".to_string()).await,
+            Err(CommentError::PostNotFound)
+        ));
+        assert!(matches!(
+            usecase
+                .create_comment(1, POST_ID, Some(other.id), "x".to_string())
+                .await,
+            Err(CommentError::InvalidParent)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_edit_and_soft_delete() {
+        let usecase = new_usecase();
+        let root = comment(&usecase, POST_ID, None).await;
+        let _reply = comment(&usecase, POST_ID, Some(root.id)).await;
+
+        assert!(matches!(
+            usecase
+                .update_comment(
+                    Caller::new(2, UserRole::Moderator),
+                    root.id,
+                    "x".to_string()
+                )
+                .await,
+            Err(CommentError::PermissionDenied)
+        ));
+        let updated = usecase
+            .update_comment(
+                Caller::new(1, UserRole::User),
+                root.id,
+                "edited".to_string(),
+            )
+            .await
+            .expect("Update failed");
+        assert_eq!(updated.body, "edited");
+
+        usecase
+            .delete_comment(Caller::new(2, UserRole::Moderator), root.id)
+            .await
+            .expect("Delete failed");
+        assert!(matches!(
+            usecase
+                .delete_comment(Caller::new(1, UserRole::User), root.id)
+                .await,
+            Err(CommentError::CommentNotFound)
+        ));
+
+        // 削除されたコメントは本文を伏せたまま返信と一緒に残る
+        let (threads, _) = usecase
+            .list_comments(POST_ID, None, 0)
+            .await
+            .expect("List comments failed");
+        assert!(threads[0].comment.deleted_at.is_some());
+        assert!(threads[0].comment.body.is_empty());
+        assert_eq!(threads[0].replies.len(), 1);
+    }
+}
@@ -0,0 +1,334 @@
+use crate::domain::entity::conversations::Model as Conversation;
+use crate::domain::repository::conversation::{ConversationRepository, ConversationSummary};
+use crate::infra::pagination::normalize_page;
+use crate::usecase::policy::{ensure_group_owner, AccessError, Caller};
+use async_trait::async_trait;
+use sea_orm::DbErr;
+use thiserror::Error;
+
+/// per_page が指定されなかった場合の 1 ページあたりの件数
+pub const DEFAULT_CONVERSATIONS_PER_PAGE: i32 = 20;
+/// 1 ページあたりの最大件数
+pub const MAX_CONVERSATIONS_PER_PAGE: i32 = 100;
+
+#[derive(Debug, Error)]
+pub enum ConversationError {
+    /// 作成者が抜けるとグループを管理できる人がいなくなるため、他のメンバーが残っている間は抜けられない
+    #[error("the group creator cannot leave while other members remain")]
+    OwnerHasMembers,
+    #[error("permission denied")]
+    PermissionDenied,
+    #[error(transparent)]
+    Database(#[from] DbErr),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+impl From<AccessError> for ConversationError {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::PermissionDenied => ConversationError::PermissionDenied,
+            AccessError::Database(e) => ConversationError::Database(e),
+            AccessError::Sqlx(e) => ConversationError::Sqlx(e),
+        }
+    }
+}
+
+#[async_trait]
+pub trait ConversationUseCase {
+    /// グループ会話を作成し、(グループ, メンバーID一覧) を返します。
+    async fn create_group(
+        &self,
+        user_id: i32,
+        name: String,
+        member_ids: Vec<i32>,
+    ) -> Result<(Conversation, Vec<i32>), DbErr>;
+
+    /// メンバーであるグループ会話の詳細を取得します。
+    async fn get_group(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+    ) -> Result<(Conversation, Vec<i32>), DbErr>;
+
+    /// グループにメンバーを追加します。
+    async fn add_members(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+        member_ids: Vec<i32>,
+    ) -> Result<i32, DbErr>;
+
+    /// グループからメンバーを削除します。グループの作成者（または管理者）以外は `PermissionDenied` を返します。
+    /// 他のメンバーが残っている間は作成者を削除できず、`OwnerHasMembers` を返します。
+    async fn remove_member(
+        &self,
+        caller: Caller,
+        conversation_id: i32,
+        member_id: i32,
+    ) -> Result<bool, ConversationError>;
+
+    /// グループ名を変更します。
+    async fn rename_group(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+        name: String,
+    ) -> Result<(Conversation, Vec<i32>), DbErr>;
+
+    /// グループから退出します。
+    /// 作成者は他のメンバーが残っている間は退出できず、`OwnerHasMembers` を返します。
+    async fn leave_group(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+    ) -> Result<bool, ConversationError>;
+
+    /// グループのメッセージを既読にし、更新後の既読位置を返します。
+    async fn mark_as_read(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+        message_id: Option<i32>,
+    ) -> Result<Option<i32>, DbErr>;
+
+    /// 会話一覧（受信箱）を取得します。
+    async fn list_conversations(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<ConversationSummary>, i32), DbErr>;
+}
+
+pub struct ConversationUseCaseImpl<R> {
+    repository: R,
+}
+
+impl<R: ConversationRepository + Send + Sync> ConversationUseCaseImpl<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    // メンバー以外にはグループの存在自体を見せない
+    async fn ensure_member(&self, user_id: i32, conversation_id: i32) -> Result<(), DbErr> {
+        if self.repository.is_member(conversation_id, user_id).await? {
+            Ok(())
+        } else {
+            Err(DbErr::RecordNotFound(format!(
+                "Conversation with id {} not found",
+                conversation_id
+            )))
+        }
+    }
+
+    async fn find_group(&self, conversation_id: i32) -> Result<Conversation, DbErr> {
+        self.repository
+            .get_by_id(conversation_id)
+            .await?
+            .ok_or(DbErr::RecordNotFound(format!(
+                "Conversation with id {} not found",
+                conversation_id
+            )))
+    }
+
+    // 作成者は最後のメンバーになるまでグループから抜けられない
+    async fn ensure_can_leave(
+        &self,
+        conversation: &Conversation,
+        user_id: i32,
+    ) -> Result<(), ConversationError> {
+        if conversation.created_by != Some(user_id) {
+            return Ok(());
+        }
+        let member_ids = self.repository.list_member_ids(conversation.id).await?;
+        if member_ids.iter().any(|&id| id != user_id) {
+            return Err(ConversationError::OwnerHasMembers);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<R: ConversationRepository + Send + Sync> ConversationUseCase for ConversationUseCaseImpl<R> {
+    async fn create_group(
+        &self,
+        user_id: i32,
+        name: String,
+        member_ids: Vec<i32>,
+    ) -> Result<(Conversation, Vec<i32>), DbErr> {
+        let conversation = self
+            .repository
+            .create_group(user_id, name, member_ids)
+            .await?;
+        let member_ids = self.repository.list_member_ids(conversation.id).await?;
+        Ok((conversation, member_ids))
+    }
+
+    async fn get_group(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+    ) -> Result<(Conversation, Vec<i32>), DbErr> {
+        self.ensure_member(user_id, conversation_id).await?;
+        let conversation = self.find_group(conversation_id).await?;
+        let member_ids = self.repository.list_member_ids(conversation_id).await?;
+        Ok((conversation, member_ids))
+    }
+
+    async fn add_members(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+        member_ids: Vec<i32>,
+    ) -> Result<i32, DbErr> {
+        self.ensure_member(user_id, conversation_id).await?;
+        self.repository
+            .add_members(conversation_id, member_ids)
+            .await
+    }
+
+    async fn remove_member(
+        &self,
+        caller: Caller,
+        conversation_id: i32,
+        member_id: i32,
+    ) -> Result<bool, ConversationError> {
+        if !caller.is_admin() {
+            self.ensure_member(caller.user_id, conversation_id).await?;
+        }
+        let conversation = self.find_group(conversation_id).await?;
+        ensure_group_owner(&caller, &conversation)?;
+        self.ensure_can_leave(&conversation, member_id).await?;
+        Ok(self
+            .repository
+            .remove_member(conversation_id, member_id)
+            .await?)
+    }
+
+    async fn rename_group(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+        name: String,
+    ) -> Result<(Conversation, Vec<i32>), DbErr> {
+        self.ensure_member(user_id, conversation_id).await?;
+        let conversation = self.repository.rename(conversation_id, name).await?;
+        let member_ids = self.repository.list_member_ids(conversation_id).await?;
+        Ok((conversation, member_ids))
+    }
+
+    async fn leave_group(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+    ) -> Result<bool, ConversationError> {
+        self.ensure_member(user_id, conversation_id).await?;
+        let conversation = self.find_group(conversation_id).await?;
+        self.ensure_can_leave(&conversation, user_id).await?;
+        Ok(self
+            .repository
+            .remove_member(conversation_id, user_id)
+            .await?)
+    }
+
+    async fn mark_as_read(
+        &self,
+        user_id: i32,
+        conversation_id: i32,
+        message_id: Option<i32>,
+    ) -> Result<Option<i32>, DbErr> {
+        self.ensure_member(user_id, conversation_id).await?;
+        self.repository
+            .mark_as_read(conversation_id, user_id, message_id)
+            .await
+    }
+
+    async fn list_conversations(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<ConversationSummary>, i32), DbErr> {
+        let (page, per_page) = normalize_page(
+            page,
+            per_page,
+            DEFAULT_CONVERSATIONS_PER_PAGE,
+            MAX_CONVERSATIONS_PER_PAGE,
+        );
+        self.repository
+            .list_conversations(user_id, page, per_page)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entity::sea_orm_active_enums::UserRole;
+    use crate::repository::conversation_repository::mock::MockConversationRepository;
+
+    const OWNER: i32 = 1;
+    const MEMBER: i32 = 2;
+    const OTHER: i32 = 3;
+
+    #[tokio::test]
+    async fn test_only_owner_can_remove_members() {
+        let usecase = ConversationUseCaseImpl::new(MockConversationRepository::new());
+        let (group, _) = usecase
+            .create_group(OWNER, "group".to_string(), vec![MEMBER, OTHER])
+            .await
+            .unwrap();
+
+        // 作成者以外のメンバーは他のメンバーも作成者も削除できない
+        let member = Caller::new(MEMBER, UserRole::User);
+        for target in [OTHER, OWNER] {
+            assert!(matches!(
+                usecase.remove_member(member, group.id, target).await,
+                Err(ConversationError::PermissionDenied)
+            ));
+        }
+        let (_, member_ids) = usecase.get_group(OWNER, group.id).await.unwrap();
+        assert_eq!(member_ids, vec![OWNER, MEMBER, OTHER]);
+
+        let owner = Caller::new(OWNER, UserRole::User);
+        assert!(usecase.remove_member(owner, group.id, OTHER).await.unwrap());
+        let admin = Caller::new(99, UserRole::Admin);
+        assert!(usecase
+            .remove_member(admin, group.id, MEMBER)
+            .await
+            .unwrap());
+        let (_, member_ids) = usecase.get_group(OWNER, group.id).await.unwrap();
+        assert_eq!(member_ids, vec![OWNER]);
+
+        // メンバーでなくなったユーザーにはグループの存在自体を見せない
+        assert!(matches!(
+            usecase.remove_member(member, group.id, OWNER).await,
+            Err(ConversationError::Database(DbErr::RecordNotFound(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_owner_cannot_leave_while_members_remain() {
+        let usecase = ConversationUseCaseImpl::new(MockConversationRepository::new());
+        let (group, _) = usecase
+            .create_group(OWNER, "group".to_string(), vec![MEMBER])
+            .await
+            .unwrap();
+
+        // 他のメンバーが残っている間は、退出でも管理者による削除でも作成者は抜けられない
+        assert!(matches!(
+            usecase.leave_group(OWNER, group.id).await,
+            Err(ConversationError::OwnerHasMembers)
+        ));
+        let admin = Caller::new(99, UserRole::Admin);
+        assert!(matches!(
+            usecase.remove_member(admin, group.id, OWNER).await,
+            Err(ConversationError::OwnerHasMembers)
+        ));
+
+        // 最後のメンバーになれば退出できる
+        assert!(usecase.leave_group(MEMBER, group.id).await.unwrap());
+        assert!(usecase.leave_group(OWNER, group.id).await.unwrap());
+    }
+}
@@ -0,0 +1,197 @@
+use crate::domain::entity::users::Model as User;
+use crate::domain::repository::follow::FollowRepository;
+use crate::domain::repository::user::UserRepository;
+use crate::infra::pagination::normalize_page;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+use thiserror::Error;
+
+/// per_page が指定されなかった場合の 1 ページあたりの件数
+pub const DEFAULT_FOLLOWS_PER_PAGE: i32 = 20;
+/// 1 ページあたりの最大件数
+pub const MAX_FOLLOWS_PER_PAGE: i32 = 100;
+
+#[derive(Debug, Error)]
+pub enum FollowError {
+    #[error("cannot follow yourself")]
+    SelfFollow,
+    /// 相手のユーザーが存在しない、または論理削除されている
+    #[error("user not found")]
+    UserNotFound,
+    #[error(transparent)]
+    Database(#[from] DbErr),
+    #[error(transparent)]
+    Sqlx(sqlx::Error),
+}
+
+impl From<sqlx::Error> for FollowError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::RowNotFound => FollowError::UserNotFound,
+            e => FollowError::Sqlx(e),
+        }
+    }
+}
+
+#[async_trait]
+pub trait FollowUseCase {
+    /// `user_id` のユーザーが `target_id` のユーザーをフォローします。
+    /// 新たにフォローした場合は true、既にフォロー済みの場合は false を返します。
+    async fn follow(&self, user_id: i32, target_id: i32) -> Result<bool, FollowError>;
+
+    /// フォローを解除します。解除された場合は true を返します。
+    async fn unfollow(&self, user_id: i32, target_id: i32) -> Result<bool, FollowError>;
+
+    /// フォロワー一覧を取得し、(ユーザー一覧, 全件数) を返します。
+    async fn list_followers(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), FollowError>;
+
+    /// フォロー中のユーザー一覧を取得し、(ユーザー一覧, 全件数) を返します。
+    async fn list_following(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), FollowError>;
+}
+
+pub struct FollowUseCaseImpl<F, U> {
+    follows: F,
+    users: U,
+}
+
+impl<F: FollowRepository, U: UserRepository> FollowUseCaseImpl<F, U> {
+    pub fn new(follows: F, users: U) -> Self {
+        Self { follows, users }
+    }
+
+    // 論理削除されたユーザーは存在しないものとして扱う
+    async fn ensure_active_user(&self, user_id: i32) -> Result<(), FollowError> {
+        let user = self.users.get_by_id(user_id).await?;
+        if user.deleted_at.is_some() {
+            return Err(FollowError::UserNotFound);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<F, U> FollowUseCase for FollowUseCaseImpl<F, U>
+where
+    F: FollowRepository + Send + Sync,
+    U: UserRepository + Send + Sync,
+{
+    async fn follow(&self, user_id: i32, target_id: i32) -> Result<bool, FollowError> {
+        if user_id == target_id {
+            return Err(FollowError::SelfFollow);
+        }
+        self.ensure_active_user(target_id).await?;
+        Ok(self.follows.follow(user_id, target_id).await?)
+    }
+
+    async fn unfollow(&self, user_id: i32, target_id: i32) -> Result<bool, FollowError> {
+        Ok(self.follows.unfollow(user_id, target_id).await?)
+    }
+
+    async fn list_followers(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), FollowError> {
+        self.ensure_active_user(user_id).await?;
+        let (page, per_page) = normalize_page(
+            page,
+            per_page,
+            DEFAULT_FOLLOWS_PER_PAGE,
+            MAX_FOLLOWS_PER_PAGE,
+        );
+        Ok(self.follows.list_followers(user_id, page, per_page).await?)
+    }
+
+    async fn list_following(
+        &self,
+        user_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), FollowError> {
+        self.ensure_active_user(user_id).await?;
+        let (page, per_page) = normalize_page(
+            page,
+            per_page,
+            DEFAULT_FOLLOWS_PER_PAGE,
+            MAX_FOLLOWS_PER_PAGE,
+        );
+        Ok(self.follows.list_following(user_id, page, per_page).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::follow_repository::mock::MockFollowRepository;
+    use crate::repository::user_repository::mock::MockUserRepository;
+
+    async fn create_user(users: &MockUserRepository, name: &str) -> i32 {
+        users
+            .create(
+                name.to_string(),
+                format!("{}@example.com", name),
+                None,
+                None,
+                None,
+                None,
+                None, // password_hash
+            )
+            .await
+            .expect("Failed to create user")
+            .id
+    }
+
+    #[tokio::test]
+    async fn test_follow_and_unfollow() {
+        let users = MockUserRepository::new();
+        let alice = create_user(&users, "alice").await;
+        let bob = create_user(&users, "bob").await;
+        let usecase = FollowUseCaseImpl::new(MockFollowRepository::new(), users);
+
+        assert!(usecase.follow(alice, bob).await.expect("Follow failed"));
+        assert!(!usecase.follow(alice, bob).await.expect("Follow failed"));
+
+        let (_, total_count) = usecase
+            .list_followers(bob, 0, 0)
+            .await
+            .expect("List followers failed");
+        assert_eq!(total_count, 1);
+
+        assert!(usecase.unfollow(alice, bob).await.expect("Unfollow failed"));
+        assert!(!usecase.unfollow(alice, bob).await.expect("Unfollow failed"));
+    }
+
+    #[tokio::test]
+    async fn test_follow_rejects_self_and_missing_users() {
+        let users = MockUserRepository::new();
+        let alice = create_user(&users, "alice").await;
+        let carol = create_user(&users, "carol").await;
+        users.delete(carol).await.expect("Failed to delete user");
+        let usecase = FollowUseCaseImpl::new(MockFollowRepository::new(), users);
+
+        assert!(matches!(
+            usecase.follow(alice, alice).await,
+            Err(FollowError::SelfFollow)
+        ));
+        assert!(matches!(
+            usecase.follow(alice, 999).await,
+            Err(FollowError::UserNotFound)
+        ));
+        // 論理削除されたユーザーはフォローできない
+        assert!(matches!(
+            usecase.follow(alice, carol).await,
+            Err(FollowError::UserNotFound)
+        ));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::domain::entity::mentions::Model as Mention;
+use crate::domain::repository::mention::{MentionRepository, MentionSource};
+use crate::infra::mention::extract_mentions;
+use crate::infra::message_hub::{MessageEvent, MessageHub};
+use crate::infra::pagination::clamp_per_page;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+use std::sync::Arc;
+
+/// per_page が指定されなかった場合の 1 ページあたりの件数
+pub const DEFAULT_MENTIONS_PER_PAGE: i32 = 20;
+/// 1 ページあたりの最大件数
+pub const MAX_MENTIONS_PER_PAGE: i32 = 100;
+/// 1 つの本文で記録するメンションの上限（これを超える分は無視する）
+pub const MAX_MENTIONS_PER_BODY: usize = 20;
+
+/// 本文中の `@ハンドル` を記録し、メンションされたユーザーへ通知します。
+///
+/// 投稿とメッセージの作成処理から共通して使います。
+pub struct MentionNotifier<M> {
+    repository: M,
+    hub: Arc<MessageHub>,
+}
+
+impl<M: MentionRepository> MentionNotifier<M> {
+    pub fn new(repository: M, hub: Arc<MessageHub>) -> Self {
+        Self { repository, hub }
+    }
+
+    /// `audience` を指定した場合は、その中に含まれるユーザーへのメンションのみを記録します
+    /// （メッセージの宛先以外へ本文の存在を知らせないため）。自分自身へのメンションは記録しません。
+    ///
+    /// メンションの記録に失敗しても本文の作成は取り消さず、警告を記録するのみとします。
+    pub async fn notify(
+        &self,
+        author_id: i32,
+        source: MentionSource,
+        body: &str,
+        audience: Option<&[i32]>,
+    ) {
+        if let Err(e) = self.try_notify(author_id, source, body, audience).await {
+            tracing::warn!("failed to record mentions for {:?}: {}", source, e);
+        }
+    }
+
+    async fn try_notify(
+        &self,
+        author_id: i32,
+        source: MentionSource,
+        body: &str,
+        audience: Option<&[i32]>,
+    ) -> Result<(), DbErr> {
+        let mut handles = extract_mentions(body);
+        if handles.is_empty() {
+            return Ok(());
+        }
+        handles.truncate(MAX_MENTIONS_PER_BODY);
+
+        let user_ids: Vec<i32> = self
+            .repository
+            .resolve_handles(&handles)
+            .await?
+            .into_iter()
+            .filter(|&id| id != author_id)
+            .filter(|id| audience.is_none_or(|audience| audience.contains(id)))
+            .collect();
+        for mention in self.repository.insert(author_id, source, &user_ids).await? {
+            self.hub
+                .publish(mention.user_id, MessageEvent::Mentioned(mention));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait MentionUseCase {
+    /// `user_id` のユーザーへのメンションを新しい順に 1 ページ分取得します。
+    /// 続きがある場合は次のページの `before_id` を合わせて返します。
+    async fn list_mentions(
+        &self,
+        user_id: i32,
+        before_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<Mention>, Option<i32>), DbErr>;
+}
+
+pub struct MentionUseCaseImpl<M> {
+    repository: M,
+}
+
+impl<M: MentionRepository> MentionUseCaseImpl<M> {
+    pub fn new(repository: M) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl<M: MentionRepository + Send + Sync> MentionUseCase for MentionUseCaseImpl<M> {
+    async fn list_mentions(
+        &self,
+        user_id: i32,
+        before_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<Mention>, Option<i32>), DbErr> {
+        let per_page =
+            clamp_per_page(per_page, DEFAULT_MENTIONS_PER_PAGE, MAX_MENTIONS_PER_PAGE) as usize;
+
+        // 次のページの有無を判定するため 1 件多く取得する
+        let mut mentions = self
+            .repository
+            .find_by_user_id(user_id, before_id, per_page as u64 + 1)
+            .await?;
+        let next_before_id = if mentions.len() > per_page {
+            mentions.truncate(per_page);
+            mentions.last().map(|m| m.id)
+        } else {
+            None
+        };
+        Ok((mentions, next_before_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::mention_repository::mock::MockMentionRepository;
+
+    const ALICE: i32 = 1;
+    const BOB: i32 = 2;
+    const CAROL: i32 = 3;
+
+    fn new_notifier(hub: &Arc<MessageHub>) -> MentionNotifier<MockMentionRepository> {
+        let repository =
+            MockMentionRepository::new(&[("alice", ALICE), ("bob", BOB), ("carol", CAROL)]);
+        MentionNotifier::new(repository, Arc::clone(hub))
+    }
+
+    #[tokio::test]
+    async fn test_notify_records_and_publishes_mentions() {
+        let hub = Arc::new(MessageHub::new(16));
+        let mut bob = hub.subscribe(BOB);
+        let notifier = new_notifier(&hub);
+
+        // 自分自身・未登録のハンドルは無視し、同じユーザーは 1 回だけ記録する
+        notifier
+            .notify(
+                ALICE,
+                MentionSource::Post(10),
+                "@bob @Bob @alice @nobody",
+                None,
+            )
+            .await;
+        match bob.try_recv().unwrap() {
+            MessageEvent::Mentioned(mention) => {
+                assert_eq!(mention.author_id, ALICE);
+                assert_eq!(mention.post_id, Some(10));
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+        assert!(bob.try_recv().is_err());
+        assert_eq!(notifier.repository.mentions.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_notify_message_only_mentions_audience() {
+        let hub = Arc::new(MessageHub::new(16));
+        let mut carol = hub.subscribe(CAROL);
+        let notifier = new_notifier(&hub);
+
+        // メッセージの宛先でないユーザーにはメンションを記録しない
+        notifier
+            .notify(
+                ALICE,
+                MentionSource::Message(20),
+                "@bob @carol",
+                Some(&[BOB]),
+            )
+            .await;
+        let mentions = notifier.repository.mentions.lock().unwrap().clone();
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].user_id, BOB);
+        assert_eq!(mentions[0].message_id, Some(20));
+        assert!(carol.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_mentions_pages() {
+        let repository = MockMentionRepository::new(&[]);
+        for post_id in 1..=3 {
+            repository
+                .insert(ALICE, MentionSource::Post(post_id), &[BOB])
+                .await
+                .unwrap();
+        }
+        let usecase = MentionUseCaseImpl::new(repository);
+
+        let (mentions, next_before_id) = usecase.list_mentions(BOB, None, 2).await.unwrap();
+        assert_eq!(
+            mentions.iter().map(|m| m.post_id).collect::<Vec<_>>(),
+            vec![Some(3), Some(2)]
+        );
+        let (mentions, next_before_id) =
+            usecase.list_mentions(BOB, next_before_id, 2).await.unwrap();
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].post_id, Some(1));
+        assert_eq!(next_before_id, None);
+    }
+}
@@ -1,17 +1,138 @@
+use crate::domain::entity::attachments::Model as Attachment;
+use crate::domain::entity::message_receipts::Model as MessageReceipt;
+use crate::domain::entity::message_revisions::Model as MessageRevision;
 use crate::domain::entity::messages::Model as Message;
-use crate::domain::repository::message::MessageRepository;
+use crate::domain::repository::conversation::ConversationRepository;
+use crate::domain::repository::mention::{MentionRepository, MentionSource};
+use crate::domain::repository::message::{MessageRepository, ReactionCount};
+use crate::infra::message_hub::{MessageEvent, MessageHub, Subscription};
+use crate::usecase::attachment_usecase::MAX_ATTACHMENTS;
+use crate::usecase::mention_usecase::MentionNotifier;
+use crate::usecase::policy::{
+    ensure_message_author, ensure_message_receiver, ensure_message_sender, AccessError, Caller,
+};
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
 use sea_orm::DbErr;
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// 送信後にメッセージを編集できる既定の秒数
+pub const DEFAULT_MESSAGE_EDIT_WINDOW_SECONDS: i64 = 15 * 60;
+
+/// 配達済みにするメッセージ ID も件数も指定されなかった場合に、1 回で配達済みにする件数
+pub const DEFAULT_DELIVERED_PER_CALL: i32 = 100;
+/// 1 回で配達済みにできるメッセージの最大件数
+pub const MAX_DELIVERED_PER_CALL: i32 = 500;
+
+/// 1 回の配達済み記録で対象にする件数を返します。
+/// `message_ids` を指定した場合はその件数、空の場合は `limit` を上限までに丸めた件数です。
+pub fn delivery_limit(message_ids: &[i32], limit: i32) -> Result<u64, MessageError> {
+    if message_ids.len() > MAX_DELIVERED_PER_CALL as usize {
+        return Err(MessageError::TooManyMessageIds);
+    }
+    if !message_ids.is_empty() {
+        return Ok(message_ids.len() as u64);
+    }
+    Ok(if limit > 0 {
+        limit.min(MAX_DELIVERED_PER_CALL) as u64
+    } else {
+        DEFAULT_DELIVERED_PER_CALL as u64
+    })
+}
+
+/// リアクションの絵文字の最大文字数（肌の色や ZWJ で結合された絵文字も収まる長さ）
+pub const MAX_REACTION_CHARS: usize = 32;
+
+/// リアクションとして付けられる文字列かどうかを判定します。
+/// 空文字列、空白・制御文字を含むもの、長すぎるものは受け付けません。
+pub fn is_valid_reaction(emoji: &str) -> bool {
+    !emoji.is_empty()
+        && emoji.chars().count() <= MAX_REACTION_CHARS
+        && !emoji.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+#[derive(Debug, Error)]
+pub enum MessageError {
+    /// 宛先の `receiver_id` と `conversation_id` が両方指定されている、またはどちらも指定されていない
+    #[error("exactly one of receiver_id and conversation_id must be specified")]
+    InvalidTarget,
+    /// 返信元が存在しない、論理削除されている、または別の会話のメッセージ
+    #[error("reply_to_message_id is not a message in this conversation")]
+    InvalidReplyTo,
+    /// 添付ファイルが多すぎる、または送信者がアップロードした未使用の添付ファイルでない
+    #[error("attachment_ids must be at most {MAX_ATTACHMENTS} unused attachments uploaded by the sender")]
+    InvalidAttachments,
+    /// 一度に配達済みにするメッセージ ID が多すぎる
+    #[error("message_ids must contain at most {MAX_DELIVERED_PER_CALL} ids")]
+    TooManyMessageIds,
+    /// メッセージが存在しない、または論理削除されている
+    #[error("message not found")]
+    MessageNotFound,
+    /// 送信から編集可能な期間が過ぎている
+    #[error("edit window has expired")]
+    EditWindowExpired,
+    #[error("permission denied")]
+    PermissionDenied,
+    #[error(transparent)]
+    Database(#[from] DbErr),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+impl From<AccessError> for MessageError {
+    fn from(e: AccessError) -> Self {
+        match e {
+            AccessError::PermissionDenied => MessageError::PermissionDenied,
+            AccessError::Database(e) => MessageError::Database(e),
+            AccessError::Sqlx(e) => MessageError::Sqlx(e),
+        }
+    }
+}
+
+/// 閲覧者から見たメッセージ
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageView {
+    pub message: Message,
+    /// 返信元のメッセージ。論理削除されている場合も `deleted_at` が設定された状態で含む
+    pub reply_to: Option<Message>,
+    /// 絵文字ごとのリアクション数と閲覧者自身のリアクション（`GetConversation` でのみ設定）
+    pub reactions: Vec<ReactionCount>,
+    /// 受信者ごとの配達・既読の記録
+    pub receipts: Vec<MessageReceipt>,
+    /// 添付ファイル（アップロード順）
+    pub attachments: Vec<Attachment>,
+}
+
+impl MessageView {
+    /// リアクションや配達・既読の記録を持たないメッセージ（送信直後のメッセージなど）
+    pub fn new(message: Message, reply_to: Option<Message>, attachments: Vec<Attachment>) -> Self {
+        Self {
+            message,
+            reply_to,
+            reactions: vec![],
+            receipts: vec![],
+            attachments,
+        }
+    }
+}
 
 #[async_trait]
 pub trait MessageUseCase {
     /// 新規メッセージを送信します。
+    /// 1対1の場合は `receiver_id`、グループの場合は `conversation_id` を指定します。
+    /// `reply_to_message_id` には同じ相手との会話（またはグループ）のメッセージのみ指定できます。
+    /// `attachment_ids` には送信者がアップロードした未使用の添付ファイルを指定します。
     async fn send_message(
         &self,
         sender_id: i32,
-        receiver_id: i32,
+        receiver_id: Option<i32>,
+        conversation_id: Option<i32>,
         content: String,
-    ) -> Result<Message, DbErr>;
+        reply_to_message_id: Option<i32>,
+        attachment_ids: Vec<i32>,
+    ) -> Result<MessageView, MessageError>;
 
     /// ユーザーのメッセージ一覧を取得します。
     async fn list_messages(
@@ -20,51 +141,312 @@ pub trait MessageUseCase {
         unread_only: bool,
         page: i32,
         per_page: i32,
-    ) -> Result<(Vec<Message>, i32, i32), DbErr>;
+    ) -> Result<(Vec<MessageView>, i32, i32), DbErr>;
 
-    /// ユーザー間の会話履歴を取得します。
+    /// ユーザー間またはグループの会話履歴を取得します。
     async fn get_conversation(
         &self,
         user_id: i32,
-        peer_id: i32,
+        peer_id: Option<i32>,
+        conversation_id: Option<i32>,
         page: i32,
         per_page: i32,
-    ) -> Result<(Vec<Message>, i32), DbErr>;
+    ) -> Result<(Vec<MessageView>, i32, i32), DbErr>;
 
     /// 指定されたメッセージまたはユーザー間のメッセージを既読に更新します。
+    /// 呼び出し元が受信者でないメッセージが含まれる場合は `PermissionDenied` を返します。
     async fn mark_as_read(
         &self,
+        caller: Caller,
         message_id: Option<i32>,
         message_ids: Vec<i32>,
         from_user_id: Option<i32>,
         to_user_id: Option<i32>,
+    ) -> Result<i32, AccessError>;
+
+    /// 受信したメッセージを配達済みにします。クライアントがメッセージを取得した際に呼び出します。
+    /// `message_ids` が空の場合は未配達のメッセージを古い順に `limit` 件まで配達済みにします。
+    async fn mark_as_delivered(
+        &self,
+        user_id: i32,
+        message_ids: Vec<i32>,
+        limit: i32,
+    ) -> Result<i32, MessageError>;
+
+    /// 指定されたメッセージを削除します。送信者本人以外は `PermissionDenied` を返します。
+    async fn delete_message(&self, caller: Caller, message_id: i32) -> Result<bool, AccessError>;
+
+    /// メッセージの本文を編集し、編集前の本文を履歴に残します。
+    /// 送信者本人以外は `PermissionDenied`、編集可能な期間を過ぎている場合は `EditWindowExpired` を返します。
+    async fn edit_message(
+        &self,
+        caller: Caller,
+        message_id: i32,
+        content: String,
+    ) -> Result<MessageView, MessageError>;
+
+    /// メッセージの編集履歴を古い順に取得します。メッセージを閲覧できるユーザーのみ取得できます。
+    async fn list_message_revisions(
+        &self,
+        user_id: i32,
+        message_id: i32,
+    ) -> Result<Vec<MessageRevision>, AccessError>;
+
+    /// メッセージにリアクションを付け、付与後のメッセージのリアクション一覧を返します。
+    /// メッセージを閲覧できない（送受信者・グループのメンバーでない）場合は `PermissionDenied` を返します。
+    async fn add_reaction(
+        &self,
+        user_id: i32,
+        message_id: i32,
+        emoji: String,
+    ) -> Result<Vec<ReactionCount>, AccessError>;
+
+    /// メッセージのリアクションを取り消し、取り消し後のメッセージのリアクション一覧を返します。
+    async fn remove_reaction(
+        &self,
+        user_id: i32,
+        message_id: i32,
+        emoji: String,
+    ) -> Result<Vec<ReactionCount>, AccessError>;
+
+    /// 相手から受信したメッセージを既読にし、相手へ既読通知を送ります。
+    /// `message_ids` が空の場合は相手から受信した全メッセージを既読にします。
+    async fn acknowledge_read(
+        &self,
+        reader_id: i32,
+        peer_id: i32,
+        message_ids: Vec<i32>,
     ) -> Result<i32, DbErr>;
 
-    /// 指定されたメッセージを削除します。
-    async fn delete_message(&self, message_id: i32) -> Result<bool, DbErr>;
+    /// 入力中／入力終了を相手へ通知します。
+    /// 相手と同じグループのメンバーであるか、1対1のメッセージをやり取りしたことがある場合のみ通知できます。
+    async fn notify_typing(
+        &self,
+        user_id: i32,
+        peer_id: i32,
+        typing: bool,
+    ) -> Result<(), MessageError>;
+
+    /// 指定ユーザー宛てのイベント（新着メッセージ・入力状態・既読）を購読します。
+    fn subscribe(&self, user_id: i32) -> Subscription;
 }
 
-pub struct MessageUseCaseImpl<R> {
+pub struct MessageUseCaseImpl<R, C, M> {
     repository: R,
+    conversations: C,
+    mentions: MentionNotifier<M>,
+    hub: Arc<MessageHub>,
+    edit_window: Duration,
+}
+
+impl<R, C, M> MessageUseCaseImpl<R, C, M>
+where
+    R: MessageRepository,
+    C: ConversationRepository,
+    M: MentionRepository,
+{
+    /// `edit_window` は送信後にメッセージを編集できる期間です。
+    pub fn new(
+        repository: R,
+        conversations: C,
+        mentions: MentionNotifier<M>,
+        hub: Arc<MessageHub>,
+        edit_window: Duration,
+    ) -> Self {
+        Self {
+            repository,
+            conversations,
+            mentions,
+            hub,
+            edit_window,
+        }
+    }
+
+    // リアクションの対象メッセージを取得し、ユーザーが閲覧できるメッセージかを確認する
+    async fn find_visible_message(
+        &self,
+        user_id: i32,
+        message_id: i32,
+    ) -> Result<Message, AccessError> {
+        let message = self
+            .repository
+            .find_by_ids(vec![message_id])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                DbErr::RecordNotFound(format!("Message with id {} not found", message_id))
+            })?;
+        let visible = match message.conversation_id {
+            Some(conversation_id) => {
+                self.conversations
+                    .is_member(conversation_id, user_id)
+                    .await?
+            }
+            None => message.sender_id == user_id || message.receiver_id == Some(user_id),
+        };
+        if !visible {
+            return Err(AccessError::PermissionDenied);
+        }
+        Ok(message)
+    }
+
+    // 返信元のメッセージ、配達・既読の記録、添付ファイルを添えて閲覧用のメッセージに変換する
+    async fn to_views(&self, messages: Vec<Message>) -> Result<Vec<MessageView>, DbErr> {
+        let message_ids: Vec<i32> = messages.iter().map(|m| m.id).collect();
+        let mut receipts: HashMap<i32, Vec<MessageReceipt>> = HashMap::new();
+        for receipt in self.repository.find_receipts(&message_ids).await? {
+            receipts
+                .entry(receipt.message_id)
+                .or_default()
+                .push(receipt);
+        }
+        let mut attachments: HashMap<i32, Vec<Attachment>> = HashMap::new();
+        for attachment in self.repository.find_attachments(&message_ids).await? {
+            if let Some(message_id) = attachment.message_id {
+                attachments.entry(message_id).or_default().push(attachment);
+            }
+        }
+        let reply_to_ids: Vec<i32> = messages
+            .iter()
+            .filter_map(|m| m.reply_to_message_id)
+            .collect();
+        let originals: HashMap<i32, Message> = self
+            .repository
+            .find_by_ids_including_deleted(&reply_to_ids)
+            .await?
+            .into_iter()
+            .map(|m| (m.id, m))
+            .collect();
+        Ok(messages
+            .into_iter()
+            .map(|message| MessageView {
+                reply_to: message
+                    .reply_to_message_id
+                    .and_then(|id| originals.get(&id).cloned()),
+                reactions: vec![],
+                receipts: receipts.remove(&message.id).unwrap_or_default(),
+                attachments: attachments.remove(&message.id).unwrap_or_default(),
+                message,
+            })
+            .collect())
+    }
 }
 
-impl<R: MessageRepository> MessageUseCaseImpl<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+// 返信元が送信先と同じ会話のメッセージかどうか（1対1の場合は同じ2人の間のメッセージ）
+fn is_same_conversation(
+    original: &Message,
+    sender_id: i32,
+    receiver_id: Option<i32>,
+    conversation_id: Option<i32>,
+) -> bool {
+    match (receiver_id, conversation_id) {
+        (None, Some(conversation_id)) => original.conversation_id == Some(conversation_id),
+        (Some(receiver_id), None) => {
+            original.conversation_id.is_none()
+                && ((original.sender_id == sender_id && original.receiver_id == Some(receiver_id))
+                    || (original.sender_id == receiver_id
+                        && original.receiver_id == Some(sender_id)))
+        }
+        _ => false,
     }
 }
 
 #[async_trait]
-impl<R: MessageRepository + Send + Sync> MessageUseCase for MessageUseCaseImpl<R> {
+impl<R, C, M> MessageUseCase for MessageUseCaseImpl<R, C, M>
+where
+    R: MessageRepository + Send + Sync,
+    C: ConversationRepository + Send + Sync,
+    M: MentionRepository + Send + Sync,
+{
     async fn send_message(
         &self,
         sender_id: i32,
-        receiver_id: i32,
+        receiver_id: Option<i32>,
+        conversation_id: Option<i32>,
         content: String,
-    ) -> Result<Message, DbErr> {
-        self.repository
-            .send_message(sender_id, receiver_id, content)
+        reply_to_message_id: Option<i32>,
+        attachment_ids: Vec<i32>,
+    ) -> Result<MessageView, MessageError> {
+        if attachment_ids.len() > MAX_ATTACHMENTS {
+            return Err(MessageError::InvalidAttachments);
+        }
+        // グループ宛ての場合は送信者がメンバーであることを確認し、配信先をメンバー全員とする
+        let recipients = match (receiver_id, conversation_id) {
+            (None, Some(conversation_id)) => {
+                let member_ids = self.conversations.list_member_ids(conversation_id).await?;
+                if !member_ids.contains(&sender_id) {
+                    return Err(DbErr::RecordNotFound(format!(
+                        "Conversation with id {} not found",
+                        conversation_id
+                    ))
+                    .into());
+                }
+                member_ids
+                    .into_iter()
+                    .filter(|&id| id != sender_id)
+                    .collect()
+            }
+            (Some(receiver_id), None) => vec![receiver_id],
+            _ => return Err(MessageError::InvalidTarget),
+        };
+
+        let reply_to = match reply_to_message_id {
+            Some(reply_to_message_id) => {
+                let original = self
+                    .repository
+                    .find_by_ids(vec![reply_to_message_id])
+                    .await?
+                    .into_iter()
+                    .next()
+                    .filter(|m| is_same_conversation(m, sender_id, receiver_id, conversation_id))
+                    .ok_or(MessageError::InvalidReplyTo)?;
+                Some(original)
+            }
+            None => None,
+        };
+
+        let message = self
+            .repository
+            .send_message(
+                sender_id,
+                receiver_id,
+                conversation_id,
+                content,
+                reply_to_message_id,
+                &attachment_ids,
+            )
             .await
+            .map_err(|e| match e {
+                DbErr::RecordNotUpdated => MessageError::InvalidAttachments,
+                e => e.into(),
+            })?;
+        let attachments = if attachment_ids.is_empty() {
+            vec![]
+        } else {
+            self.repository.find_attachments(&[message.id]).await?
+        };
+        // 保存が確定したメッセージを受信者の購読者へ配信
+        for &recipient in &recipients {
+            self.hub.publish(
+                recipient,
+                MessageEvent::Received {
+                    message: message.clone(),
+                    reply_to: reply_to.clone(),
+                    attachments: attachments.clone(),
+                },
+            );
+        }
+        // メッセージを読めるのは受信者のみのため、受信者以外へのメンションは記録しない
+        self.mentions
+            .notify(
+                sender_id,
+                MentionSource::Message(message.id),
+                &message.content,
+                Some(recipients.as_slice()),
+            )
+            .await;
+        Ok(MessageView::new(message, reply_to, attachments))
     }
 
     async fn list_messages(
@@ -73,37 +455,292 @@ impl<R: MessageRepository + Send + Sync> MessageUseCase for MessageUseCaseImpl<R
         unread_only: bool,
         page: i32,
         per_page: i32,
-    ) -> Result<(Vec<Message>, i32, i32), DbErr> {
-        self.repository
+    ) -> Result<(Vec<MessageView>, i32, i32), DbErr> {
+        let (messages, total_count, unread_count) = self
+            .repository
             .list_messages(user_id, unread_only, page, per_page)
-            .await
+            .await?;
+        Ok((self.to_views(messages).await?, total_count, unread_count))
     }
 
     async fn get_conversation(
         &self,
         user_id: i32,
-        peer_id: i32,
+        peer_id: Option<i32>,
+        conversation_id: Option<i32>,
         page: i32,
         per_page: i32,
-    ) -> Result<(Vec<Message>, i32), DbErr> {
-        self.repository
-            .get_conversation(user_id, peer_id, page, per_page)
-            .await
+    ) -> Result<(Vec<MessageView>, i32, i32), DbErr> {
+        let (messages, total_count, unread_count) = self
+            .repository
+            .get_conversation(user_id, peer_id, conversation_id, page, per_page)
+            .await?;
+
+        let message_ids: Vec<i32> = messages.iter().map(|m| m.id).collect();
+        let mut reactions: HashMap<i32, Vec<ReactionCount>> = HashMap::new();
+        for reaction in self
+            .repository
+            .find_reaction_counts(user_id, &message_ids)
+            .await?
+        {
+            reactions
+                .entry(reaction.message_id)
+                .or_default()
+                .push(reaction);
+        }
+
+        let mut views = self.to_views(messages).await?;
+        for view in &mut views {
+            view.reactions = reactions.remove(&view.message.id).unwrap_or_default();
+        }
+        Ok((views, total_count, unread_count))
     }
 
     async fn mark_as_read(
         &self,
+        caller: Caller,
         message_id: Option<i32>,
         message_ids: Vec<i32>,
         from_user_id: Option<i32>,
         to_user_id: Option<i32>,
-    ) -> Result<i32, DbErr> {
-        self.repository
+    ) -> Result<i32, AccessError> {
+        let target_ids: Vec<i32> = message_id.into_iter().chain(message_ids.clone()).collect();
+        if !target_ids.is_empty() {
+            for message in self.repository.find_by_ids(target_ids).await? {
+                ensure_message_receiver(&caller, &message)?;
+            }
+        } else if to_user_id.is_some_and(|to| to != caller.user_id) && !caller.is_admin() {
+            return Err(AccessError::PermissionDenied);
+        }
+
+        Ok(self
+            .repository
             .mark_as_read(message_id, message_ids, from_user_id, to_user_id)
-            .await
+            .await?)
+    }
+
+    async fn mark_as_delivered(
+        &self,
+        user_id: i32,
+        message_ids: Vec<i32>,
+        limit: i32,
+    ) -> Result<i32, MessageError> {
+        let limit = delivery_limit(&message_ids, limit)?;
+        Ok(self
+            .repository
+            .mark_as_delivered(user_id, message_ids, limit)
+            .await?)
+    }
+
+    async fn delete_message(&self, caller: Caller, message_id: i32) -> Result<bool, AccessError> {
+        let Some(message) = self
+            .repository
+            .find_by_ids(vec![message_id])
+            .await?
+            .into_iter()
+            .next()
+        else {
+            return Ok(false);
+        };
+        ensure_message_sender(&caller, &message)?;
+        Ok(self.repository.delete_message(message_id).await?)
+    }
+
+    async fn edit_message(
+        &self,
+        caller: Caller,
+        message_id: i32,
+        content: String,
+    ) -> Result<MessageView, MessageError> {
+        let message = self
+            .repository
+            .find_by_ids(vec![message_id])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(MessageError::MessageNotFound)?;
+        ensure_message_author(&caller, &message)?;
+        if Utc::now().naive_utc() - message.created_at > self.edit_window {
+            return Err(MessageError::EditWindowExpired);
+        }
+        // 本文が変わらない場合は履歴を増やさない
+        let message = if message.content == content {
+            message
+        } else {
+            self.repository.edit_message(message_id, content).await?
+        };
+        let mut views = self.to_views(vec![message]).await?;
+        Ok(views.remove(0))
+    }
+
+    async fn list_message_revisions(
+        &self,
+        user_id: i32,
+        message_id: i32,
+    ) -> Result<Vec<MessageRevision>, AccessError> {
+        self.find_visible_message(user_id, message_id).await?;
+        Ok(self.repository.list_revisions(message_id).await?)
+    }
+
+    async fn add_reaction(
+        &self,
+        user_id: i32,
+        message_id: i32,
+        emoji: String,
+    ) -> Result<Vec<ReactionCount>, AccessError> {
+        self.find_visible_message(user_id, message_id).await?;
+        self.repository
+            .add_reaction(message_id, user_id, emoji)
+            .await?;
+        Ok(self
+            .repository
+            .find_reaction_counts(user_id, &[message_id])
+            .await?)
+    }
+
+    async fn remove_reaction(
+        &self,
+        user_id: i32,
+        message_id: i32,
+        emoji: String,
+    ) -> Result<Vec<ReactionCount>, AccessError> {
+        self.find_visible_message(user_id, message_id).await?;
+        self.repository
+            .remove_reaction(message_id, user_id, &emoji)
+            .await?;
+        Ok(self
+            .repository
+            .find_reaction_counts(user_id, &[message_id])
+            .await?)
+    }
+
+    async fn acknowledge_read(
+        &self,
+        reader_id: i32,
+        peer_id: i32,
+        message_ids: Vec<i32>,
+    ) -> Result<i32, DbErr> {
+        let (updated_count, message_ids) = if message_ids.is_empty() {
+            let updated_count = self
+                .repository
+                .mark_as_read(None, vec![], Some(peer_id), Some(reader_id))
+                .await?;
+            (updated_count, message_ids)
+        } else {
+            // 相手から自分宛てに届いたメッセージのみ既読にする
+            let message_ids: Vec<i32> = self
+                .repository
+                .find_by_ids(message_ids)
+                .await?
+                .into_iter()
+                .filter(|m| m.sender_id == peer_id && m.receiver_id == Some(reader_id))
+                .map(|m| m.id)
+                .collect();
+            if message_ids.is_empty() {
+                return Ok(0);
+            }
+            let updated_count = self
+                .repository
+                .mark_as_read(None, message_ids.clone(), None, None)
+                .await?;
+            (updated_count, message_ids)
+        };
+        self.hub.publish(
+            peer_id,
+            MessageEvent::Read {
+                reader_id,
+                message_ids,
+            },
+        );
+        Ok(updated_count)
+    }
+
+    async fn notify_typing(
+        &self,
+        user_id: i32,
+        peer_id: i32,
+        typing: bool,
+    ) -> Result<(), MessageError> {
+        // 面識のない相手へ入力状態を送りつけられないよう、会話のある相手に限る
+        let related = self
+            .repository
+            .has_direct_messages(user_id, peer_id)
+            .await?
+            || self
+                .conversations
+                .shares_conversation(user_id, peer_id)
+                .await?;
+        if !related {
+            return Err(MessageError::PermissionDenied);
+        }
+        self.hub
+            .publish(peer_id, MessageEvent::Typing { user_id, typing });
+        Ok(())
+    }
+
+    fn subscribe(&self, user_id: i32) -> Subscription {
+        self.hub.subscribe(user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn message(sender_id: i32, receiver_id: Option<i32>, conversation_id: Option<i32>) -> Message {
+        let now = Utc::now().naive_utc();
+        Message {
+            id: 1,
+            sender_id,
+            receiver_id,
+            content: "hello".to_string(),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            conversation_id,
+            reply_to_message_id: None,
+            edited_at: None,
+        }
+    }
+
+    #[test]
+    fn test_is_same_conversation() {
+        // 1対1のメッセージにはどちらの向きからでも返信できる
+        let direct = message(1, Some(2), None);
+        assert!(is_same_conversation(&direct, 1, Some(2), None));
+        assert!(is_same_conversation(&direct, 2, Some(1), None));
+        assert!(!is_same_conversation(&direct, 1, Some(3), None));
+        assert!(!is_same_conversation(&direct, 3, Some(2), None));
+        assert!(!is_same_conversation(&direct, 1, None, Some(10)));
+
+        let group = message(1, None, Some(10));
+        assert!(is_same_conversation(&group, 2, None, Some(10)));
+        assert!(!is_same_conversation(&group, 2, None, Some(11)));
+        assert!(!is_same_conversation(&group, 2, Some(1), None));
+    }
+
+    #[test]
+    fn test_is_valid_reaction() {
+        assert!(is_valid_reaction("👍"));
+        assert!(is_valid_reaction("👨‍👩‍👧‍👦"));
+        assert!(is_valid_reaction(":tada:"));
+        assert!(!is_valid_reaction(""));
+        assert!(!is_valid_reaction("👍 👍"));
+        assert!(!is_valid_reaction("\n"));
+        assert!(!is_valid_reaction(&"a".repeat(MAX_REACTION_CHARS + 1)));
     }
 
-    async fn delete_message(&self, message_id: i32) -> Result<bool, DbErr> {
-        self.repository.delete_message(message_id).await
+    #[test]
+    fn test_delivery_limit() {
+        assert_eq!(delivery_limit(&[], 0).unwrap(), 100);
+        assert_eq!(delivery_limit(&[], 10).unwrap(), 10);
+        assert_eq!(delivery_limit(&[], 10_000).unwrap(), 500);
+        assert_eq!(delivery_limit(&[1, 2, 3], 1).unwrap(), 3);
+        let too_many: Vec<i32> = (0..MAX_DELIVERED_PER_CALL + 1).collect();
+        assert!(matches!(
+            delivery_limit(&too_many, 0),
+            Err(MessageError::TooManyMessageIds)
+        ));
     }
 }
@@ -1,3 +1,11 @@
+pub mod admin_usecase;
+pub mod attachment_usecase;
+pub mod auth_usecase;
+pub mod comment_usecase;
+pub mod conversation_usecase;
+pub mod follow_usecase;
+pub mod mention_usecase;
 pub mod message_usecase;
+pub mod policy;
 pub mod post_usecase;
 pub mod user_usecase;
@@ -0,0 +1,228 @@
+use crate::domain::entity::comments::Model as Comment;
+use crate::domain::entity::conversations::Model as Conversation;
+use crate::domain::entity::messages::Model as Message;
+use crate::domain::entity::post::Model as Post;
+use crate::domain::entity::sea_orm_active_enums::UserRole;
+use sea_orm::DbErr;
+use thiserror::Error;
+
+/// 認可の判定に使う呼び出し元の情報
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Caller {
+    pub user_id: i32,
+    pub role: UserRole,
+}
+
+impl Caller {
+    pub fn new(user_id: i32, role: UserRole) -> Self {
+        Self { user_id, role }
+    }
+
+    /// 管理者はすべてのユーザーとコンテンツを操作できます
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+
+    /// モデレーターと管理者は他人の投稿・メッセージを削除できます
+    pub fn can_moderate(&self) -> bool {
+        matches!(self.role, UserRole::Admin | UserRole::Moderator)
+    }
+}
+
+/// 認可チェックを伴うユースケースのエラー
+#[derive(Debug, Error)]
+pub enum AccessError {
+    #[error("permission denied")]
+    PermissionDenied,
+    #[error(transparent)]
+    Database(#[from] DbErr),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+fn ensure(allowed: bool) -> Result<(), AccessError> {
+    if allowed {
+        Ok(())
+    } else {
+        Err(AccessError::PermissionDenied)
+    }
+}
+
+/// 管理者専用の操作を許可します。
+pub fn ensure_admin(caller: &Caller) -> Result<(), AccessError> {
+    ensure(caller.is_admin())
+}
+
+/// モデレーター以上の操作を許可します。
+pub fn ensure_moderator(caller: &Caller) -> Result<(), AccessError> {
+    ensure(caller.can_moderate())
+}
+
+/// 投稿の削除は投稿者本人（またはモデレーター）のみ許可します。
+pub fn ensure_post_owner(caller: &Caller, post: &Post) -> Result<(), AccessError> {
+    ensure(caller.can_moderate() || post.user_id == caller.user_id)
+}
+
+/// 投稿の編集は投稿者本人のみ許可します。モデレーターでも他人の本文は書き換えられません。
+pub fn ensure_post_author(caller: &Caller, post: &Post) -> Result<(), AccessError> {
+    ensure(post.user_id == caller.user_id)
+}
+
+/// コメントの削除は投稿者本人（またはモデレーター）のみ許可します。
+pub fn ensure_comment_owner(caller: &Caller, comment: &Comment) -> Result<(), AccessError> {
+    ensure(caller.can_moderate() || comment.user_id == caller.user_id)
+}
+
+/// コメントの編集は投稿者本人のみ許可します。
+pub fn ensure_comment_author(caller: &Caller, comment: &Comment) -> Result<(), AccessError> {
+    ensure(comment.user_id == caller.user_id)
+}
+
+/// メッセージの削除は送信者本人（またはモデレーター）のみ許可します。
+pub fn ensure_message_sender(caller: &Caller, message: &Message) -> Result<(), AccessError> {
+    ensure(caller.can_moderate() || message.sender_id == caller.user_id)
+}
+
+/// メッセージの編集は送信者本人のみ許可します。モデレーターでも他人の本文は書き換えられません。
+pub fn ensure_message_author(caller: &Caller, message: &Message) -> Result<(), AccessError> {
+    ensure(message.sender_id == caller.user_id)
+}
+
+/// メッセージの既読化は1対1メッセージの受信者本人のみ許可します。
+/// グループのメッセージは既読位置（MarkGroupAsRead）で管理します。
+pub fn ensure_message_receiver(caller: &Caller, message: &Message) -> Result<(), AccessError> {
+    ensure(caller.is_admin() || message.receiver_id == Some(caller.user_id))
+}
+
+/// グループからのメンバーの削除は作成者（または管理者）のみ許可します。
+pub fn ensure_group_owner(caller: &Caller, conversation: &Conversation) -> Result<(), AccessError> {
+    ensure(caller.is_admin() || conversation.created_by == Some(caller.user_id))
+}
+
+/// ユーザー情報の更新・削除は本人のみ許可します。
+pub fn ensure_same_user(caller: &Caller, user_id: i32) -> Result<(), AccessError> {
+    ensure(caller.is_admin() || user_id == caller.user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn message(sender_id: i32, receiver_id: Option<i32>) -> Message {
+        let now = Utc::now().naive_utc();
+        Message {
+            id: 1,
+            sender_id,
+            receiver_id,
+            content: "hello".to_string(),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            conversation_id: None,
+            reply_to_message_id: None,
+            edited_at: None,
+        }
+    }
+
+    #[test]
+    fn test_post_owner() {
+        let post = Post {
+            id: 1,
+            body: "body".to_string(),
+            user_id: 1,
+            created_at: "CURRENT_TIMESTAMP".to_string(),
+            fanned_out: false,
+            like_count: 0,
+            comment_count: 0,
+            repost_of_id: None,
+            repost_kind: None,
+        };
+        assert!(ensure_post_owner(&Caller::new(1, UserRole::User), &post).is_ok());
+        assert!(matches!(
+            ensure_post_owner(&Caller::new(2, UserRole::User), &post),
+            Err(AccessError::PermissionDenied)
+        ));
+        assert!(ensure_post_owner(&Caller::new(2, UserRole::Moderator), &post).is_ok());
+
+        assert!(ensure_post_author(&Caller::new(1, UserRole::User), &post).is_ok());
+        assert!(ensure_post_author(&Caller::new(2, UserRole::Moderator), &post).is_err());
+        assert!(ensure_post_author(&Caller::new(3, UserRole::Admin), &post).is_err());
+    }
+
+    #[test]
+    fn test_comment_owner_and_author() {
+        let now = Utc::now().naive_utc();
+        let comment = Comment {
+            id: 1,
+            post_id: 1,
+            user_id: 1,
+            parent_comment_id: None,
+            root_comment_id: None,
+            body: "comment".to_string(),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+        assert!(ensure_comment_owner(&Caller::new(1, UserRole::User), &comment).is_ok());
+        assert!(ensure_comment_owner(&Caller::new(2, UserRole::User), &comment).is_err());
+        assert!(ensure_comment_owner(&Caller::new(2, UserRole::Moderator), &comment).is_ok());
+        assert!(ensure_comment_author(&Caller::new(1, UserRole::User), &comment).is_ok());
+        assert!(ensure_comment_author(&Caller::new(2, UserRole::Moderator), &comment).is_err());
+    }
+
+    #[test]
+    fn test_message_sender_and_receiver() {
+        let message = message(1, Some(2));
+        assert!(ensure_message_sender(&Caller::new(1, UserRole::User), &message).is_ok());
+        assert!(ensure_message_sender(&Caller::new(2, UserRole::User), &message).is_err());
+        assert!(ensure_message_author(&Caller::new(1, UserRole::User), &message).is_ok());
+        assert!(ensure_message_author(&Caller::new(3, UserRole::Moderator), &message).is_err());
+        assert!(ensure_message_author(&Caller::new(3, UserRole::Admin), &message).is_err());
+        assert!(ensure_message_receiver(&Caller::new(2, UserRole::User), &message).is_ok());
+        assert!(ensure_message_receiver(&Caller::new(1, UserRole::User), &message).is_err());
+        assert!(ensure_message_receiver(&Caller::new(3, UserRole::Admin), &message).is_ok());
+    }
+
+    #[test]
+    fn test_roles() {
+        let user = Caller::new(1, UserRole::User);
+        let moderator = Caller::new(2, UserRole::Moderator);
+        let admin = Caller::new(3, UserRole::Admin);
+        assert!(ensure_admin(&user).is_err());
+        assert!(ensure_admin(&moderator).is_err());
+        assert!(ensure_admin(&admin).is_ok());
+        assert!(ensure_moderator(&user).is_err());
+        assert!(ensure_moderator(&moderator).is_ok());
+        assert!(ensure_moderator(&admin).is_ok());
+
+        // モデレーターはユーザー情報や他人の既読状態は変更できない
+        let message = message(1, Some(4));
+        assert!(ensure_message_sender(&moderator, &message).is_ok());
+        assert!(ensure_message_receiver(&moderator, &message).is_err());
+        assert!(ensure_same_user(&moderator, 1).is_err());
+    }
+
+    #[test]
+    fn test_group_owner() {
+        let now = Utc::now().naive_utc();
+        let conversation = Conversation {
+            id: 1,
+            name: "group".to_string(),
+            created_by: Some(1),
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(ensure_group_owner(&Caller::new(1, UserRole::User), &conversation).is_ok());
+        assert!(ensure_group_owner(&Caller::new(2, UserRole::User), &conversation).is_err());
+        assert!(ensure_group_owner(&Caller::new(2, UserRole::Moderator), &conversation).is_err());
+        assert!(ensure_group_owner(&Caller::new(3, UserRole::Admin), &conversation).is_ok());
+    }
+
+    #[test]
+    fn test_same_user() {
+        assert!(ensure_same_user(&Caller::new(1, UserRole::User), 1).is_ok());
+        assert!(ensure_same_user(&Caller::new(1, UserRole::User), 2).is_err());
+        assert!(ensure_same_user(&Caller::new(1, UserRole::Admin), 2).is_ok());
+    }
+}
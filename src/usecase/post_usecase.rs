@@ -1,41 +1,447 @@
+use crate::domain::entity::attachments::Model as Attachment;
 use crate::domain::entity::post::Model as Post;
-use crate::domain::repository::post::PostRepository;
+use crate::domain::entity::post_revisions::Model as PostRevision;
+use crate::domain::entity::sea_orm_active_enums::RepostKind;
+use crate::domain::entity::users::Model as User;
+use crate::domain::repository::mention::{MentionRepository, MentionSource};
+use crate::domain::repository::post::{HashtagCount, PostPage, PostRepository};
+use crate::infra::hashtag::extract_hashtags;
+use crate::infra::pagination::{clamp_per_page, normalize_page};
+use crate::usecase::attachment_usecase::MAX_ATTACHMENTS;
+use crate::usecase::mention_usecase::MentionNotifier;
+use crate::usecase::policy::{ensure_post_author, ensure_post_owner, AccessError, Caller};
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
 use sea_orm::DbErr;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// per_page が指定されなかった場合の 1 ページあたりの件数
+pub const DEFAULT_POSTS_PER_PAGE: i32 = 20;
+/// 1 ページあたりの最大件数
+pub const MAX_POSTS_PER_PAGE: i32 = 100;
+/// 書き込み時にタイムラインへ配信する投稿者のフォロワー数の既定の上限
+/// これを超える投稿者の投稿は読み込み時にフォロー関係から集めます
+pub const DEFAULT_FAN_OUT_MAX_FOLLOWERS: i32 = 10_000;
+/// トレンドの集計期間が指定されなかった場合の時間数
+pub const DEFAULT_TRENDING_WINDOW_HOURS: i32 = 24;
+/// トレンドの集計期間の最大時間数
+pub const MAX_TRENDING_WINDOW_HOURS: i32 = 24 * 7;
+/// トレンドの件数が指定されなかった場合の件数
+pub const DEFAULT_TRENDING_LIMIT: i32 = 10;
+/// トレンドの最大件数
+pub const MAX_TRENDING_LIMIT: i32 = 100;
+
+#[derive(Debug, Error)]
+pub enum PostError {
+    /// 添付ファイルが多すぎる、または投稿者がアップロードした未使用の添付ファイルでない
+    #[error("attachment_ids must be at most {MAX_ATTACHMENTS} unused attachments uploaded by the author")]
+    InvalidAttachments,
+    #[error(transparent)]
+    Database(#[from] DbErr),
+}
+
+/// 閲覧者から見た投稿
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PostView {
+    pub post: Post,
+    /// 閲覧者がいいね済みかどうか
+    pub liked_by_viewer: bool,
+    /// リポスト・引用の場合のリポスト元（削除済みの場合は `None`）
+    pub original: Option<Box<PostView>>,
+    /// 添付ファイル（アップロード順）
+    pub attachments: Vec<Attachment>,
+}
+
+impl PostView {
+    /// リポスト・引用であるにもかかわらず、リポスト元が削除されているかどうか
+    /// （リポスト元が削除されると外部キーにより `repost_of_id` のみが NULL になる）
+    pub fn original_deleted(&self) -> bool {
+        self.post.repost_kind.is_some() && self.post.repost_of_id.is_none()
+    }
+}
 
 #[async_trait]
 pub trait PostUseCase {
-    async fn create_post(&self, body: String, user_id: i32) -> Result<Post, DbErr>;
-    async fn get_post(&self, id: i32) -> Result<Option<Post>, DbErr>;
-    async fn list_posts(&self) -> Result<Vec<Post>, DbErr>;
-    async fn delete_post(&self, id: i32) -> Result<(), DbErr>;
+    /// 投稿を作成します。`attachment_ids` には投稿者がアップロードした未使用の添付ファイルを指定します。
+    async fn create_post(
+        &self,
+        body: String,
+        user_id: i32,
+        attachment_ids: Vec<i32>,
+    ) -> Result<PostView, PostError>;
+    /// `post_id` の投稿をリポストします。`body` が空でない場合は本文付きの引用になります。
+    /// 単純なリポストを繰り返した場合は既存のリポストを返します。
+    async fn repost(&self, user_id: i32, post_id: i32, body: String) -> Result<PostView, DbErr>;
+    /// `viewer_id` のユーザーから見た投稿を取得します。
+    async fn get_post(&self, viewer_id: i32, id: i32) -> Result<Option<PostView>, DbErr>;
+    /// 投稿を新しい順に 1 ページ分取得します。`user_id` を指定した場合はその投稿者のみに絞り込みます。
+    /// 続きがある場合は次のページの `before_id` を合わせて返します。
+    async fn list_posts(
+        &self,
+        viewer_id: i32,
+        user_id: Option<i32>,
+        before_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<PostView>, Option<i32>), DbErr>;
+    /// ホームタイムライン（自分とフォロー中のユーザーの投稿）を新しい順に 1 ページ分取得します。
+    /// 続きがある場合は次のページの `before_id` を合わせて返します。
+    async fn home_timeline(
+        &self,
+        user_id: i32,
+        before_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<PostView>, Option<i32>), DbErr>;
+    /// 投稿の本文を編集します。投稿者本人以外は `PermissionDenied` を返します。
+    async fn update_post(
+        &self,
+        caller: Caller,
+        id: i32,
+        body: String,
+    ) -> Result<PostView, AccessError>;
+    /// 投稿の編集履歴（編集前の本文）を古い順に取得します。
+    async fn list_post_revisions(&self, id: i32) -> Result<Vec<PostRevision>, DbErr>;
+    /// 投稿を削除します。投稿者本人以外は `PermissionDenied` を返します。
+    async fn delete_post(&self, caller: Caller, id: i32) -> Result<(), AccessError>;
+    /// 投稿にいいねします。既にいいね済みの場合も成功として扱います。
+    async fn like_post(&self, user_id: i32, post_id: i32) -> Result<PostView, DbErr>;
+    /// いいねを取り消します。いいねしていない場合も成功として扱います。
+    async fn unlike_post(&self, user_id: i32, post_id: i32) -> Result<PostView, DbErr>;
+    /// 投稿にいいねしたユーザーを新しい順に取得し、(ユーザー一覧, 全件数) を返します。
+    async fn list_post_likers(
+        &self,
+        post_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr>;
+    /// ハッシュタグ（正規化済み）の付いた投稿を新しい順に 1 ページ分取得します。
+    /// 続きがある場合は次のページの `before_id` を合わせて返します。
+    async fn list_posts_by_hashtag(
+        &self,
+        viewer_id: i32,
+        tag: &str,
+        before_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<PostView>, Option<i32>), DbErr>;
+    /// 直近 `window_hours` 時間に多く使われたハッシュタグを上位 `limit` 件取得します。
+    async fn trending_hashtags(
+        &self,
+        window_hours: i32,
+        limit: i32,
+    ) -> Result<Vec<HashtagCount>, DbErr>;
+}
+
+fn post_not_found(id: i32) -> DbErr {
+    DbErr::RecordNotFound(format!("Post with id {} not found", id))
+}
+
+// per_page を正規化し、次のページの有無を判定するため 1 件多く取得する条件を作る
+fn page_request(before_id: Option<i32>, per_page: i32) -> (PostPage, usize) {
+    let per_page = clamp_per_page(per_page, DEFAULT_POSTS_PER_PAGE, MAX_POSTS_PER_PAGE) as usize;
+    let page = PostPage {
+        before_id,
+        limit: per_page as u64 + 1,
+    };
+    (page, per_page)
 }
 
-pub struct PostUseCaseImpl<R> {
+// 1 件多く取得した結果を per_page 件に切り詰め、続きがあれば次のページの before_id を返す
+fn split_page(mut posts: Vec<Post>, per_page: usize) -> (Vec<Post>, Option<i32>) {
+    if posts.len() > per_page {
+        posts.truncate(per_page);
+        let next_before_id = posts.last().map(|p| p.id);
+        (posts, next_before_id)
+    } else {
+        (posts, None)
+    }
+}
+
+pub struct PostUseCaseImpl<R, M> {
     repository: R,
+    mentions: MentionNotifier<M>,
+    fan_out_max_followers: i32,
 }
 
-impl<R: PostRepository> PostUseCaseImpl<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+impl<R: PostRepository, M: MentionRepository> PostUseCaseImpl<R, M> {
+    /// `fan_out_max_followers` 以下のフォロワー数の投稿者の投稿は、作成時に
+    /// フォロワーのタイムラインへ書き込みます（負の値の場合は常に読み込み時に集めます）。
+    pub fn new(repository: R, mentions: MentionNotifier<M>, fan_out_max_followers: i32) -> Self {
+        Self {
+            repository,
+            mentions,
+            fan_out_max_followers,
+        }
+    }
+
+    // 配信に失敗しても投稿は未配信として読み込み時に集められるため、作成自体は成功させる
+    async fn try_fan_out(&self, post: &Post) {
+        if self.fan_out_max_followers < 0 || post.fanned_out {
+            return;
+        }
+        if let Err(e) = self
+            .repository
+            .fan_out(post.id, post.user_id, self.fan_out_max_followers)
+            .await
+        {
+            tracing::warn!("failed to fan out post {}: {}", post.id, e);
+        }
+    }
+
+    async fn find_post(&self, id: i32) -> Result<Post, DbErr> {
+        self.repository
+            .get_by_id(id)
+            .await?
+            .ok_or_else(|| post_not_found(id))
+    }
+
+    // リポスト元の投稿、閲覧者がいいね済みかどうか、添付ファイルを 1 ページ分まとめて付与する
+    async fn to_views(&self, viewer_id: i32, posts: Vec<Post>) -> Result<Vec<PostView>, DbErr> {
+        let original_ids: Vec<i32> = posts.iter().filter_map(|p| p.repost_of_id).collect();
+        let originals: HashMap<i32, Post> = self
+            .repository
+            .find_by_ids(&original_ids)
+            .await?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect();
+
+        let post_ids: Vec<i32> = posts
+            .iter()
+            .map(|p| p.id)
+            .chain(originals.keys().copied())
+            .collect();
+        let liked: HashSet<i32> = self
+            .repository
+            .find_liked_post_ids(viewer_id, &post_ids)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut attachments: HashMap<i32, Vec<Attachment>> = HashMap::new();
+        for attachment in self.repository.find_attachments(&post_ids).await? {
+            if let Some(post_id) = attachment.post_id {
+                attachments.entry(post_id).or_default().push(attachment);
+            }
+        }
+
+        let view = |post: Post| PostView {
+            liked_by_viewer: liked.contains(&post.id),
+            attachments: attachments.get(&post.id).cloned().unwrap_or_default(),
+            post,
+            original: None,
+        };
+        Ok(posts
+            .into_iter()
+            .map(|post| {
+                let original = post
+                    .repost_of_id
+                    .and_then(|id| originals.get(&id))
+                    .map(|original| Box::new(view(original.clone())));
+                PostView {
+                    original,
+                    ..view(post)
+                }
+            })
+            .collect())
+    }
+
+    async fn to_view(&self, viewer_id: i32, post: Post) -> Result<PostView, DbErr> {
+        let mut views = self.to_views(viewer_id, vec![post]).await?;
+        Ok(views.remove(0))
     }
 }
 
 #[async_trait]
-impl<R: PostRepository + Send + Sync> PostUseCase for PostUseCaseImpl<R> {
-    async fn create_post(&self, body: String, user_id: i32) -> Result<Post, DbErr> {
-        self.repository.insert(body, user_id).await
+impl<R, M> PostUseCase for PostUseCaseImpl<R, M>
+where
+    R: PostRepository + Send + Sync,
+    M: MentionRepository + Send + Sync,
+{
+    async fn create_post(
+        &self,
+        body: String,
+        user_id: i32,
+        attachment_ids: Vec<i32>,
+    ) -> Result<PostView, PostError> {
+        if attachment_ids.len() > MAX_ATTACHMENTS {
+            return Err(PostError::InvalidAttachments);
+        }
+        let tags = extract_hashtags(&body);
+        let post = self
+            .repository
+            .insert(body, user_id, &attachment_ids, &tags)
+            .await
+            .map_err(|e| match e {
+                DbErr::RecordNotUpdated => PostError::InvalidAttachments,
+                e => e.into(),
+            })?;
+        self.mentions
+            .notify(user_id, MentionSource::Post(post.id), &post.body, None)
+            .await;
+        self.try_fan_out(&post).await;
+        let attachments = if attachment_ids.is_empty() {
+            vec![]
+        } else {
+            self.repository.find_attachments(&[post.id]).await?
+        };
+        // 作成直後の投稿はまだ誰にもいいねされていない
+        Ok(PostView {
+            post,
+            liked_by_viewer: false,
+            original: None,
+            attachments,
+        })
+    }
+
+    async fn repost(&self, user_id: i32, post_id: i32, body: String) -> Result<PostView, DbErr> {
+        let target = self.find_post(post_id).await?;
+        let (kind, repost_of_id) = if body.is_empty() {
+            // 単純なリポストをリポストした場合は、その元の投稿をリポストする
+            match (target.repost_kind, target.repost_of_id) {
+                (Some(RepostKind::Repost), Some(original_id)) => (RepostKind::Repost, original_id),
+                _ => (RepostKind::Repost, target.id),
+            }
+        } else {
+            (RepostKind::Quote, target.id)
+        };
+
+        let post = self
+            .repository
+            .insert_repost(user_id, repost_of_id, kind, body)
+            .await?;
+        // 引用の本文のハッシュタグとメンションも記録する
+        let tags = extract_hashtags(&post.body);
+        if !tags.is_empty() {
+            self.repository.set_hashtags(post.id, &tags).await?;
+        }
+        self.mentions
+            .notify(user_id, MentionSource::Post(post.id), &post.body, None)
+            .await;
+        // 既存のリポストが返された場合は配信済みのことがある
+        self.try_fan_out(&post).await;
+        self.to_view(user_id, post).await
+    }
+
+    async fn get_post(&self, viewer_id: i32, id: i32) -> Result<Option<PostView>, DbErr> {
+        match self.repository.get_by_id(id).await? {
+            Some(post) => Ok(Some(self.to_view(viewer_id, post).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_posts(
+        &self,
+        viewer_id: i32,
+        user_id: Option<i32>,
+        before_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<PostView>, Option<i32>), DbErr> {
+        let (page, per_page) = page_request(before_id, per_page);
+        let posts = match user_id {
+            Some(user_id) => self.repository.find_by_user_id(user_id, page).await?,
+            None => self.repository.find_all(page).await?,
+        };
+        let (posts, next_before_id) = split_page(posts, per_page);
+        Ok((self.to_views(viewer_id, posts).await?, next_before_id))
+    }
+
+    async fn home_timeline(
+        &self,
+        user_id: i32,
+        before_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<PostView>, Option<i32>), DbErr> {
+        let (page, per_page) = page_request(before_id, per_page);
+        let posts = self.repository.find_home_timeline(user_id, page).await?;
+        let (posts, next_before_id) = split_page(posts, per_page);
+        Ok((self.to_views(user_id, posts).await?, next_before_id))
+    }
+
+    async fn update_post(
+        &self,
+        caller: Caller,
+        id: i32,
+        body: String,
+    ) -> Result<PostView, AccessError> {
+        let post = self.find_post(id).await?;
+        ensure_post_author(&caller, &post)?;
+        // 本文が変わらない場合は履歴を増やさない
+        let post = if post.body == body {
+            post
+        } else {
+            let tags = extract_hashtags(&body);
+            self.repository.update(id, body, &tags).await?
+        };
+        Ok(self.to_view(caller.user_id, post).await?)
+    }
+
+    async fn list_post_revisions(&self, id: i32) -> Result<Vec<PostRevision>, DbErr> {
+        self.find_post(id).await?;
+        self.repository.list_revisions(id).await
+    }
+
+    async fn delete_post(&self, caller: Caller, id: i32) -> Result<(), AccessError> {
+        let post = self.find_post(id).await?;
+        ensure_post_owner(&caller, &post)?;
+        Ok(self.repository.delete(id).await?)
+    }
+
+    async fn like_post(&self, user_id: i32, post_id: i32) -> Result<PostView, DbErr> {
+        self.find_post(post_id).await?;
+        self.repository.like(post_id, user_id).await?;
+        // いいね数を反映した状態で返す
+        let post = self.find_post(post_id).await?;
+        self.to_view(user_id, post).await
+    }
+
+    async fn unlike_post(&self, user_id: i32, post_id: i32) -> Result<PostView, DbErr> {
+        self.find_post(post_id).await?;
+        self.repository.unlike(post_id, user_id).await?;
+        let post = self.find_post(post_id).await?;
+        self.to_view(user_id, post).await
     }
 
-    async fn get_post(&self, id: i32) -> Result<Option<Post>, DbErr> {
-        self.repository.get_by_id(id).await
+    async fn list_post_likers(
+        &self,
+        post_id: i32,
+        page: i32,
+        per_page: i32,
+    ) -> Result<(Vec<User>, i32), DbErr> {
+        self.find_post(post_id).await?;
+        let (page, per_page) =
+            normalize_page(page, per_page, DEFAULT_POSTS_PER_PAGE, MAX_POSTS_PER_PAGE);
+        self.repository.list_likers(post_id, page, per_page).await
     }
 
-    async fn list_posts(&self) -> Result<Vec<Post>, DbErr> {
-        self.repository.find_all().await
+    async fn list_posts_by_hashtag(
+        &self,
+        viewer_id: i32,
+        tag: &str,
+        before_id: Option<i32>,
+        per_page: i32,
+    ) -> Result<(Vec<PostView>, Option<i32>), DbErr> {
+        let (page, per_page) = page_request(before_id, per_page);
+        let posts = self.repository.find_by_hashtag(tag, page).await?;
+        let (posts, next_before_id) = split_page(posts, per_page);
+        Ok((self.to_views(viewer_id, posts).await?, next_before_id))
     }
 
-    async fn delete_post(&self, id: i32) -> Result<(), DbErr> {
-        self.repository.delete(id).await
+    async fn trending_hashtags(
+        &self,
+        window_hours: i32,
+        limit: i32,
+    ) -> Result<Vec<HashtagCount>, DbErr> {
+        let window_hours = if window_hours > 0 {
+            window_hours.min(MAX_TRENDING_WINDOW_HOURS)
+        } else {
+            DEFAULT_TRENDING_WINDOW_HOURS
+        };
+        let limit = if limit > 0 {
+            limit.min(MAX_TRENDING_LIMIT)
+        } else {
+            DEFAULT_TRENDING_LIMIT
+        };
+        let since = Utc::now().naive_utc() - Duration::hours(window_hours as i64);
+        self.repository.trending_hashtags(since, limit as u64).await
     }
 }
@@ -1,9 +1,22 @@
 use crate::domain::entity::users::Model as User;
-use crate::domain::repository::user::UserRepository;
+use crate::domain::repository::user::{UserFilter, UserRepository};
+use crate::infra::password::hash_password;
+use crate::usecase::policy::{ensure_same_user, AccessError, Caller};
 use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum UserError {
+    #[error("failed to hash password: {0}")]
+    Hash(String),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
 
 #[async_trait]
 pub trait UserUseCase {
+    /// ユーザーを作成します。`password` が指定された場合はハッシュ化して保存します。
+    #[allow(clippy::too_many_arguments)]
     async fn create_user(
         &self,
         name: String,
@@ -12,15 +25,23 @@ pub trait UserUseCase {
         age: Option<i32>,
         gender: Option<String>,
         address: Option<String>,
-    ) -> Result<User, sqlx::Error>;
+        password: Option<String>,
+    ) -> Result<User, UserError>;
 
     async fn get_user(&self, id: i32) -> Result<User, sqlx::Error>;
 
-    async fn list_users(&self) -> Result<Vec<User>, sqlx::Error>;
+    /// 正規化済みのハンドルでユーザーを取得します。
+    async fn get_user_by_handle(&self, handle: &str) -> Result<User, sqlx::Error>;
+
+    /// 論理削除されていないユーザーを条件で絞り込み、(ユーザー一覧, 全件数) を返します。
+    async fn list_users(&self, filter: UserFilter) -> Result<(Vec<User>, i32), sqlx::Error>;
 
     // 修正: sex ではなく gender とし、email, address も含む全パラメータを渡す
+    /// 本人以外のユーザーを更新しようとした場合は `PermissionDenied` を返します。
+    #[allow(clippy::too_many_arguments)]
     async fn update_user(
         &self,
+        caller: Caller,
         id: i32,
         name: Option<String>,
         email: Option<String>,
@@ -28,9 +49,11 @@ pub trait UserUseCase {
         age: Option<i32>,
         gender: Option<String>,
         address: Option<String>,
-    ) -> Result<User, sqlx::Error>;
+        handle: Option<String>,
+    ) -> Result<User, AccessError>;
 
-    async fn delete_user(&self, id: i32) -> Result<User, sqlx::Error>;
+    /// 本人以外のユーザーを削除しようとした場合は `PermissionDenied` を返します。
+    async fn delete_user(&self, caller: Caller, id: i32) -> Result<User, AccessError>;
 }
 
 pub struct UserUseCaseImpl<R> {
@@ -53,22 +76,52 @@ impl<R: UserRepository + Send + Sync> UserUseCase for UserUseCaseImpl<R> {
         age: Option<i32>,
         gender: Option<String>,
         address: Option<String>,
-    ) -> Result<User, sqlx::Error> {
-        self.repository
-            .create(name, email, description, age, gender, address)
-            .await
+        password: Option<String>,
+    ) -> Result<User, UserError> {
+        // Argon2 の計算でランタイムのワーカースレッドを塞がないよう別スレッドで行う
+        let password_hash = match password {
+            Some(password) => Some(
+                tokio::task::spawn_blocking(move || hash_password(&password))
+                    .await
+                    .map_err(|e| UserError::Hash(e.to_string()))?
+                    .map_err(|e| UserError::Hash(e.to_string()))?,
+            ),
+            None => None,
+        };
+        Ok(self
+            .repository
+            .create(
+                name,
+                email,
+                description,
+                age,
+                gender,
+                address,
+                password_hash,
+            )
+            .await?)
     }
 
     async fn get_user(&self, id: i32) -> Result<User, sqlx::Error> {
         self.repository.get_by_id(id).await
     }
 
-    async fn list_users(&self) -> Result<Vec<User>, sqlx::Error> {
-        self.repository.list().await
+    async fn get_user_by_handle(&self, handle: &str) -> Result<User, sqlx::Error> {
+        self.repository.find_by_handle(handle).await
+    }
+
+    async fn list_users(&self, filter: UserFilter) -> Result<(Vec<User>, i32), sqlx::Error> {
+        self.repository
+            .list(UserFilter {
+                include_deleted: false,
+                ..filter
+            })
+            .await
     }
 
     async fn update_user(
         &self,
+        caller: Caller,
         id: i32,
         name: Option<String>,
         email: Option<String>,
@@ -76,13 +129,17 @@ impl<R: UserRepository + Send + Sync> UserUseCase for UserUseCaseImpl<R> {
         age: Option<i32>,
         gender: Option<String>,
         address: Option<String>,
-    ) -> Result<User, sqlx::Error> {
-        self.repository
-            .update(id, name, email, description, age, gender, address)
-            .await
+        handle: Option<String>,
+    ) -> Result<User, AccessError> {
+        ensure_same_user(&caller, id)?;
+        Ok(self
+            .repository
+            .update(id, name, email, description, age, gender, address, handle)
+            .await?)
     }
 
-    async fn delete_user(&self, id: i32) -> Result<User, sqlx::Error> {
-        self.repository.delete(id).await
+    async fn delete_user(&self, caller: Caller, id: i32) -> Result<User, AccessError> {
+        ensure_same_user(&caller, id)?;
+        Ok(self.repository.delete(id).await?)
     }
 }